@@ -3,16 +3,61 @@
 提供了Struct定义、序列化(dumps)和反序列化(loads)功能.
 """
 
+from ._core import (
+    Fixed8,
+    Fixed16,
+    Fixed32,
+    Fixed64,
+    JceFramer,
+    JceReader,
+    JceStr,
+    JceWriter,
+)
 from .adapter import TarsTypeAdapter
-from .api import BytesMode, dump, dumps, load, loads
+from .api import (
+    BytesMode,
+    canonical_hash,
+    canonicalize,
+    debug_dump,
+    denormalize_struct,
+    deserialize_schema,
+    dump,
+    dumps,
+    dumps_batch,
+    dumps_batch_framed,
+    dumps_into,
+    from_json,
+    get_field,
+    index_struct,
+    is_type_compatible,
+    jce_equal,
+    load,
+    loads,
+    loads_batch,
+    loads_batch_parallel,
+    loads_partial,
+    loads_projection,
+    loads_value,
+    normalize_struct,
+    schema_compatible,
+    serialize_schema,
+    set_schema_method_name,
+    to_json,
+)
 from .config import Config
+from .const import JceType
 from .context import (
     SerializationInfo,
+    field_deserializer,
     field_serializer,
 )
 from .exceptions import (
     DecodeError,
     EncodeError,
+    FrameError,
+    FrameTooLarge,
+    InvalidLength,
+    JceFrameDecodeError,
     PartialDataError,
     TarsError,
     TarsTypeError,
@@ -22,6 +67,8 @@ from .options import Option
 from .stream import (
     LengthPrefixedReader,
     LengthPrefixedWriter,
+    load_stream,
+    split_frames,
 )
 from .struct import Field, Struct, StructDict
 from .types import (
@@ -70,6 +117,19 @@ __all__ = [
     "DecodeError",
     "EncodeError",
     "Field",
+    "Fixed8",
+    "Fixed16",
+    "Fixed32",
+    "Fixed64",
+    "FrameError",
+    "FrameTooLarge",
+    "InvalidLength",
+    "JceFrameDecodeError",
+    "JceFramer",
+    "JceReader",
+    "JceStr",
+    "JceType",
+    "JceWriter",
     "LengthPrefixedReader",
     "LengthPrefixedWriter",
     "Option",
@@ -82,9 +142,35 @@ __all__ = [
     "TarsTypeError",
     "TarsValueError",
     "Type",
+    "canonical_hash",
+    "canonicalize",
+    "debug_dump",
+    "denormalize_struct",
+    "deserialize_schema",
     "dump",
     "dumps",
+    "dumps_batch",
+    "dumps_batch_framed",
+    "dumps_into",
+    "field_deserializer",
     "field_serializer",
+    "from_json",
+    "get_field",
+    "index_struct",
+    "is_type_compatible",
+    "jce_equal",
     "load",
+    "load_stream",
     "loads",
+    "loads_batch",
+    "loads_batch_parallel",
+    "loads_partial",
+    "loads_projection",
+    "loads_value",
+    "normalize_struct",
+    "schema_compatible",
+    "serialize_schema",
+    "set_schema_method_name",
+    "split_frames",
+    "to_json",
 ]
@@ -1,20 +1,381 @@
 # 类型存根文件 - 手动维护
 # 基于 Rust PyO3 绑定的类型定义
 
-from collections.abc import Iterator
-from typing import Any, Generic, TypeVar, overload
+from collections.abc import Callable, Iterator, MutableMapping, Sequence
+from typing import Any, Generic, Literal, TypeVar, overload
 
 T = TypeVar("T")
 
 __all__ = [
+    "JCE_TYPES",
+    "Fixed8",
+    "Fixed16",
+    "Fixed32",
+    "Fixed64",
+    "JceFramer",
+    "JceReader",
+    "JceStr",
+    "JceWriter",
     "LengthPrefixedReader",
     "LengthPrefixedWriter",
+    "canonical_hash",
+    "canonicalize",
+    "debug_dump",
+    "denormalize_struct",
+    "deserialize_schema",
     "dumps",
+    "dumps_batch",
+    "dumps_batch_framed",
     "dumps_generic",
+    "dumps_into",
+    "from_json",
+    "get_field",
+    "index_struct",
+    "is_type_compatible",
+    "jce_equal",
     "loads",
+    "loads_batch",
+    "loads_batch_parallel",
     "loads_generic",
+    "loads_generic_partial",
+    "loads_generic_value",
+    "loads_projection",
+    "normalize_struct",
+    "schema_compatible",
+    "serialize_schema",
+    "set_schema_method_name",
+    "split_frames",
+    "to_json",
 ]
 
+JCE_TYPES: list[tuple[str, int]]
+"""全部 JCE 类型的 `(常量名, 类型码)`, 按类型码升序排列, 由 Rust 核心的类型定义生成."""
+
+class Fixed8:
+    """固定以 Int1 (1 字节) 编码的整数.
+
+    通用编码时固定以该宽度写入, 值为 0 时也不压缩为 ZeroTag.
+    """
+
+    @property
+    def value(self) -> int:
+        """整数值."""
+
+    def __init__(self, value: int) -> None:
+        """创建定宽整数.
+
+        Args:
+            value: 整数值, 必须在 8 位有符号整数范围内.
+
+        Raises:
+            ValueError: 值超出范围.
+        """
+
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Fixed16:
+    """固定以 Int2 (2 字节) 编码的整数.
+
+    通用编码时固定以该宽度写入, 不做最小宽度压缩.
+    """
+
+    @property
+    def value(self) -> int:
+        """整数值."""
+
+    def __init__(self, value: int) -> None:
+        """创建定宽整数.
+
+        Args:
+            value: 整数值, 必须在 16 位有符号整数范围内.
+
+        Raises:
+            ValueError: 值超出范围.
+        """
+
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Fixed32:
+    """固定以 Int4 (4 字节) 编码的整数.
+
+    通用编码时固定以该宽度写入, 不做最小宽度压缩.
+    """
+
+    @property
+    def value(self) -> int:
+        """整数值."""
+
+    def __init__(self, value: int) -> None:
+        """创建定宽整数.
+
+        Args:
+            value: 整数值, 必须在 32 位有符号整数范围内.
+
+        Raises:
+            ValueError: 值超出范围.
+        """
+
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Fixed64:
+    """固定以 Int8 (8 字节) 编码的整数.
+
+    通用编码时固定以该宽度写入, 不做最小宽度压缩.
+    """
+
+    @property
+    def value(self) -> int:
+        """整数值."""
+
+    def __init__(self, value: int) -> None:
+        """创建定宽整数.
+
+        Args:
+            value: 整数值, 必须在 64 位有符号整数范围内.
+
+        Raises:
+            ValueError: 值超出范围.
+        """
+
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class JceFramer:
+    """长度前缀分帧器, 只识别数据包边界而不解码数据体.
+
+    分帧规则与 `LengthPrefixedReader` 一致.
+
+    Examples:
+        >>> framer = JceFramer(length_type=4)
+        >>> framer.check_frame(b"\\x00\\x00\\x00\\x06\\x00\\x01")
+        6
+    """
+
+    def __init__(
+        self,
+        length_type: int = 4,
+        inclusive_length: bool = True,
+        little_endian_length: bool = False,
+        max_buffer_size: int = 10485760,
+    ) -> None:
+        """创建分帧器.
+
+        Args:
+            length_type: 长度头字节数 (1, 2, 4).
+            inclusive_length: 长度是否包含头部本身.
+            little_endian_length: 长度头是否为小端序.
+            max_buffer_size: 允许的最大数据包大小 (含长度头, 默认 10MB).
+
+        Raises:
+            ValueError: `length_type` 不是 1, 2 或 4.
+        """
+
+    @property
+    def length_type(self) -> int:
+        """长度头字节数."""
+
+    @property
+    def inclusive_length(self) -> bool:
+        """长度是否包含头部本身."""
+
+    @property
+    def little_endian_length(self) -> bool:
+        """长度头是否为小端序."""
+
+    @property
+    def max_buffer_size(self) -> int:
+        """允许的最大数据包大小."""
+
+    def check_frame(self, buffer: bytes | bytearray | memoryview) -> int | None:
+        """检查缓冲区开头是否为完整的数据包.
+
+        Args:
+            buffer: 以长度头开始的数据.
+
+        Returns:
+            完整数据包的总长度 (含长度头), 数据不足时为 None.
+
+        Raises:
+            FrameTooLarge: 数据包超过 `max_buffer_size`.
+            InvalidLength: 长度小于长度头本身.
+        """
+
+class JceReader:
+    """手动解析 JCE 数据的读取器.
+
+    持有输入数据的副本与当前偏移量, 读取失败时偏移量保持不变.
+
+    Examples:
+        >>> reader = JceReader(data)
+        >>> tag, type_id = reader.peek_head()
+        >>> if tag == 0:
+        ...     reader.read_head()
+        ...     uid = reader.read_int(type_id)
+    """
+
+    def __init__(
+        self,
+        data: bytes | bytearray | memoryview,
+        little_endian: bool = False,
+        max_depth: int = 100,
+    ) -> None:
+        """创建读取器.
+
+        Args:
+            data: JCE 二进制数据.
+            little_endian: 是否按小端序解析.
+            max_depth: `skip_field` 跳过嵌套容器时允许的最大深度.
+        """
+
+    def position(self) -> int:
+        """当前偏移量."""
+
+    def is_end(self) -> bool:
+        """是否已读取到数据末尾."""
+
+    def remaining(self) -> int:
+        """剩余未读取的字节数."""
+
+    def set_position(self, offset: int) -> None:
+        """将偏移量移动到指定位置, 用于回溯解析.
+
+        Args:
+            offset: 目标偏移量, 不能超过数据长度.
+
+        Raises:
+            ValueError: 偏移量超出数据范围.
+        """
+
+    def read_head(self) -> tuple[int, int]:
+        """读取字段头部, 返回 (tag, type_id)."""
+
+    def peek_head(self) -> tuple[int, int]:
+        """预览下一个字段头部 (tag, type_id) 而不移动偏移量.
+
+        2 字节的扩展 Tag 头部 (tag >= 15) 同样会完整回退.
+        """
+
+    def seek_to_tag(self, tag: int) -> bool:
+        """在当前层级向前查找指定 Tag 的字段.
+
+        跳过不匹配的字段 (不进入嵌套结构体). 找到时定位到该字段的头部之前,
+        随后 `read_head` 即可得到其类型; 遇到 StructEnd 或数据结束时偏移量保持不变.
+
+        Args:
+            tag: 目标 Tag.
+
+        Returns:
+            bool: 是否找到该字段.
+        """
+
+    def measure_field(self) -> tuple[int, int, int]:
+        """测量下一个字段的完整编码长度而不移动偏移量.
+
+        长度包含头部 (含扩展 Tag 头部) 与值, 嵌套的容器与结构体计入整体长度.
+
+        Returns:
+            tuple[int, int, int]: (tag, type_id, length).
+
+        Raises:
+            DecodeError: 字段被截断或结构非法.
+        """
+
+    def skip_field(self, type_id: int) -> None:
+        """跳过字段值 (不含头部), 不创建任何 Python 对象.
+
+        Args:
+            type_id: 头部中的类型码.
+
+        Raises:
+            DecodeError: 数据不完整或嵌套深度超过 max_depth.
+        """
+
+    def read_int(self, type_id: int) -> int:
+        """读取整数值 (不含头部)."""
+
+    def read_string(self, type_id: int) -> str:
+        """读取字符串值 (不含头部)."""
+
+class JceWriter:
+    """手动构造 JCE 数据的写入器.
+
+    按调用顺序逐个写入字段, 与 `JceReader` 对应.
+
+    Examples:
+        >>> writer = JceWriter()
+        >>> writer.write_int(0, 1)
+        >>> writer.write_raw_field(1, JceType.INT2, b"\\x00\\x01")
+        >>> writer.getvalue()
+        b'\\x00\\x01\\x11\\x00\\x01'
+    """
+
+    def __init__(self, little_endian: bool = False) -> None:
+        """创建写入器.
+
+        Args:
+            little_endian: 是否按小端序写入.
+        """
+
+    def __len__(self) -> int:
+        """已写入的字节数."""
+
+    def getvalue(self) -> bytes:
+        """返回已写入数据的副本."""
+
+    def clear(self) -> None:
+        """清空已写入的数据, 以便复用写入器."""
+
+    def write_head(self, tag: int, type_id: int) -> None:
+        """写入字段头部."""
+
+    def write_int(self, tag: int, value: int) -> None:
+        """写入整数字段, 按最小宽度编码."""
+
+    def write_string(self, tag: int, value: str) -> None:
+        """写入字符串字段."""
+
+    def write_bytes(self, tag: int, value: bytes | bytearray | memoryview) -> None:
+        """写入字节数组字段 (SimpleList)."""
+
+    def write_raw_field(
+        self, tag: int, type_id: int, body: bytes | bytearray | memoryview
+    ) -> None:
+        """写入字段头部后原样追加已编码的值, 用于逐字节透传捕获的字段.
+
+        不校验 `body` 是否为 `type_id` 的合法编码 (含字节序与整数宽度),
+        由调用方保证; 不合法的内容会破坏之后所有字段的解析.
+
+        Args:
+            tag: 字段 Tag.
+            type_id: 类型码.
+            body: 不含头部的值编码.
+
+        Raises:
+            ValueError: 类型码非法.
+        """
+
+class JceStr:
+    """指向输入缓冲区的字符串视图.
+
+    由 `Option.STR_VIEW` 下的通用解码返回, 仅在调用 `str()` 时才创建 Python 字符串.
+    与同内容的 `str` 相等且哈希值一致; 指向可变缓冲区 (如 bytearray) 的视图不可哈希.
+    """
+
+    @property
+    def view(self) -> memoryview:
+        """指向输入数据的 memoryview 切片."""
+
+    def __bytes__(self) -> bytes: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
 class LengthPrefixedReader(Generic[T]):
     """从流缓冲区读取带长度前缀的 Tarsio 数据包.
 
@@ -38,6 +399,12 @@ class LengthPrefixedReader(Generic[T]):
         inclusive_length: bool = True,
         little_endian_length: bool = False,
         bytes_mode: int = 2,
+        skip_bad_frames: bool = False,
+        max_fields: int | None = None,
+        max_total_elements: int | None = None,
+        allowed_control_bytes: bytes | None = None,
+        verify_length_tag: int | None = None,
+        max_depth: int | None = None,
     ) -> LengthPrefixedReader[T]: ...
     @overload
     def __new__(
@@ -50,6 +417,12 @@ class LengthPrefixedReader(Generic[T]):
         inclusive_length: bool = True,
         little_endian_length: bool = False,
         bytes_mode: int = 2,
+        skip_bad_frames: bool = False,
+        max_fields: int | None = None,
+        max_total_elements: int | None = None,
+        allowed_control_bytes: bytes | None = None,
+        verify_length_tag: int | None = None,
+        max_depth: int | None = None,
     ) -> LengthPrefixedReader[Any]: ...
     def __new__(
         cls,
@@ -61,6 +434,12 @@ class LengthPrefixedReader(Generic[T]):
         inclusive_length: bool = True,
         little_endian_length: bool = False,
         bytes_mode: int = 2,
+        skip_bad_frames: bool = False,
+        max_fields: int | None = None,
+        max_total_elements: int | None = None,
+        allowed_control_bytes: bytes | None = None,
+        verify_length_tag: int | None = None,
+        max_depth: int | None = None,
     ) -> LengthPrefixedReader[Any]:
         """初始化读取器.
 
@@ -73,12 +452,22 @@ class LengthPrefixedReader(Generic[T]):
             inclusive_length: 长度值是否包含长度前缀本身.
             little_endian_length: 长度前缀是否为小端序.
             bytes_mode: 通用解码的字节处理模式（0: Raw, 1: String, 2: Auto）.
+            skip_bad_frames: 数据体解码失败时抛出携带原始数据的 `JceFrameDecodeError`.
+            max_fields: 通用解码时单个结构体允许的最大字段数, 默认不限制.
+            max_total_elements: 通用解码时每个数据包的最大元素总数, 默认不限制.
+            allowed_control_bytes: Auto 模式下文本中允许出现的 ASCII 控制字符,
+                默认为 `b"\t\n\r"`.
+            verify_length_tag: 数据体内声明总长度的字段 Tag, 指定时校验其值与
+                数据体实际解码的字节数一致.
+            max_depth: 最大嵌套深度, 默认为 100.
+            string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+            force_string4: 是否所有字符串都使用 String4.
 
         Raises:
-            ValueError: 如果 length_type 不是 1、2 或 4.
+            ValueError: 如果 length_type 不是 1、2 或 4, 或 string1_max 超出范围.
         """
 
-    def feed(self, data: bytes) -> None:
+    def feed(self, data: bytes | bytearray | memoryview) -> None:
         """将数据追加到内部缓冲区.
 
         Args:
@@ -88,6 +477,19 @@ class LengthPrefixedReader(Generic[T]):
             BufferError: 如果缓冲区超过 max_buffer_size.
         """
 
+    def feed_many(self, chunks: Sequence[bytes | bytearray | memoryview]) -> None:
+        """一次追加多个数据块, 按总大小检查 max_buffer_size.
+
+        Args:
+            chunks: 要追加的数据块.
+
+        Raises:
+            BufferError: 如果缓冲区超过 max_buffer_size.
+        """
+
+    def compact(self) -> None:
+        """压缩缓冲区, 释放已取出数据包占用的空间."""
+
     def __iter__(self) -> Iterator[T]:
         """返回迭代器自身."""
 
@@ -99,8 +501,33 @@ class LengthPrefixedReader(Generic[T]):
 
         Raises:
             StopIteration: 当没有完整数据包可用时.
+            JceFrameDecodeError: 启用 `skip_bad_frames` 且数据体解码失败.
+        """
+
+    def next_raw(self) -> bytes | None:
+        """取出下一个完整数据包的原始数据体, 不进行解码.
+
+        Returns:
+            去除长度前缀后的数据体, 数据不足时返回 None.
+
+        Raises:
+            ValueError: 如果数据包格式错误.
         """
 
+    def peek_frame_length(self) -> int | None:
+        """预览下一个完整数据包的总长度 (含长度前缀), 不取出任何数据.
+
+        Returns:
+            数据包的总字节数, 数据不足时返回 None.
+
+        Raises:
+            ValueError: 如果数据包格式错误.
+        """
+
+    @property
+    def pending(self) -> int:
+        """缓冲区中尚未取出的字节数."""
+
     def clear(self) -> None:
         """清空内部缓冲区."""
 
@@ -122,6 +549,9 @@ class LengthPrefixedWriter:
         little_endian_length: bool = False,
         options: int = 0,
         context: dict[str, Any] | None = None,
+        max_depth: int | None = None,
+        string1_max: int = 255,
+        force_string4: bool = False,
     ) -> LengthPrefixedWriter:
         """初始化写入器.
 
@@ -131,9 +561,12 @@ class LengthPrefixedWriter:
             little_endian_length: 长度前缀是否为小端序.
             options: 序列化选项（位标志）.
             context: 用于序列化的可选上下文字典.
+            max_depth: 最大嵌套深度, 默认为 100.
+            string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+            force_string4: 是否所有字符串都使用 String4.
 
         Raises:
-            ValueError: 如果 length_type 不是 1、2 或 4.
+            ValueError: 如果 length_type 不是 1、2 或 4, 或 string1_max 超出范围.
         """
 
     def pack(self, obj: Any) -> None:
@@ -173,15 +606,24 @@ def dumps(
     schema: list[Any] | type,
     options: int = 0,
     context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+    str_encoding: str | None = None,
 ) -> bytes:
     """将 Struct 序列化为字节.
 
     Args:
-        obj: 要序列化的 Struct 实例.
+        obj: 要序列化的 Struct 实例, 或以字段名为键的 dict.
         schema: 从 Struct 派生的 schema 列表 (id, field_info) 或 Struct 类.
         options: 序列化选项（位标志）.
         context: 用于序列化钩子的可选上下文字典.
-
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        string1_max: 使用 String1 的最大字符串字节长度, 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
 
     Returns:
         序列化后的 JCE 字节数据.
@@ -191,10 +633,106 @@ def dumps(
         ValueError: 如果字段值无效.
     """
 
+def dumps_into(
+    obj: Any,
+    schema: list[Any] | type,
+    out: bytearray,
+    options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> int:
+    """将 Struct 序列化并追加到 bytearray 末尾.
+
+    Args:
+        obj: 要序列化的 Struct 实例.
+        schema: 从 Struct 派生的 schema 列表或 Struct 类.
+        out: 输出缓冲区.
+        options: 序列化选项（位标志）.
+        context: 用于序列化钩子的可选上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+
+    Returns:
+        写入的字节数.
+
+    Raises:
+        TypeError: 如果对象类型与 schema 不匹配.
+        BufferError: 如果 out 无法调整大小.
+    """
+
+def dumps_batch(
+    objs: list[Any],
+    schema: list[Any] | type,
+    options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> list[bytes]:
+    """批量序列化共享同一 Schema 的 Struct 对象.
+
+    Args:
+        objs: 要序列化的 Struct 实例列表.
+        schema: Struct 类 (推荐) 或 schema 列表.
+        options: 序列化选项（位标志）.
+        context: 用于序列化钩子的可选上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+
+    Returns:
+        与 objs 一一对应的 JCE 字节数据列表.
+
+    Raises:
+        TypeError: 如果对象不是 schema 指定类的实例.
+    """
+
+def dumps_batch_framed(
+    objs: list[Any],
+    schema: list[Any] | type,
+    options: int = 0,
+    context: dict[str, Any] | None = None,
+    length_type: int = 4,
+    inclusive_length: bool = True,
+    little_endian_length: bool = False,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> bytes:
+    """批量序列化并拼接为带长度前缀的数据流.
+
+    Args:
+        objs: 要序列化的 Struct 实例列表.
+        schema: Struct 类 (推荐) 或 schema 列表.
+        options: 序列化选项（位标志）.
+        context: 用于序列化钩子的可选上下文字典.
+        length_type: 长度前缀的字节大小（1、2 或 4）.
+        inclusive_length: 长度值是否包含长度前缀本身.
+        little_endian_length: 长度前缀是否为小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+
+    Returns:
+        所有数据包拼接后的字节数据.
+
+    Raises:
+        TypeError: 如果对象不是 schema 指定类的实例.
+        ValueError: 如果 length_type 无效或数据包过大.
+    """
+
 def dumps_generic(
     obj: Any,
     options: int = 0,
     context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+    str_encoding: str | None = None,
 ) -> bytes:
     """将通用对象序列化为字节，无需 schema.
 
@@ -202,6 +740,11 @@ def dumps_generic(
         obj: 要序列化的对象（键为整数 tag 的 dict 或 StructDict）.
         options: 序列化选项（位标志）.
         context: 可选的上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        string1_max: 使用 String1 的最大字符串字节长度, 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
 
     Returns:
         序列化后的 JCE 字节数据.
@@ -212,20 +755,67 @@ def dumps_generic(
 
 @overload
 def loads(
-    data: bytes,
+    data: bytes | bytearray | memoryview,
     target: type[T],
     options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
 ) -> dict[str, Any]: ...
 @overload
 def loads(
-    data: bytes,
+    data: bytes | bytearray | memoryview,
     target: Any,
     options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
 ) -> dict[int, Any]: ...
+@overload
 def loads(
-    data: bytes,
+    data: bytes | bytearray | memoryview,
     target: Any,
     options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    *,
+    return_consumed: bool,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+) -> Any: ...
+def loads(
+    data: bytes | bytearray | memoryview,
+    target: Any,
+    options: int = 0,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: bool = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
 ) -> Any:
     """将字节反序列化为 JceStruct.
 
@@ -233,29 +823,508 @@ def loads(
         data: 要反序列化的 JCE 字节数据.
         target: 目标 JceStruct 类.
         options: 反序列化选项.
+        context: 传递给字段反序列化钩子的可选上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+        verify_length_tag: 声明结构体总长度的字段 Tag, 指定时校验其值与实际
+            消耗的字节数一致.
+        return_consumed: 为 True 时返回 `(结果, 消耗的字节数)`.
+        map_factory: 构造 Map 容器的无参可调用对象, 默认为 dict.
+        hooks: Tag 到 `hook(value, context)` 的映射, 用于按 Tag 后处理字段值.
+            未指定时使用 `context["hooks"]`.
+        max_fields: 单个结构体允许的最大字段数, 默认不限制.
+        max_total_elements: 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
 
     Returns:
-        instance: 实例化的 JceStruct 对象.
+        instance: 实例化的 JceStruct 对象; `return_consumed` 为 True 时为
+            `(实例, 消耗的字节数)`.
 
     Raises:
         ValueError: 如果数据格式无效或解码失败.
     """
 
+def loads_batch(
+    frames: list[bytes | bytearray | memoryview],
+    target: type,
+    options: int = 0,
+    skip_errors: bool = False,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+) -> list[dict[str, Any] | None]:
+    """批量反序列化共享同一 Schema 的数据包.
+
+    Args:
+        frames: JCE 字节数据包列表.
+        target: 目标 Struct 类.
+        options: 反序列化选项（位标志）.
+        skip_errors: 为 True 时因 DecodeError 或 ValueError 解码失败的数据包返回 None.
+        context: 传递给字段反序列化钩子的可选上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        与 frames 一一对应的字段字典（字段名 -> 值）或 None.
+
+    Raises:
+        ValueError: 如果数据无效且 skip_errors 为 False.
+    """
+
+def loads_batch_parallel(
+    frames: list[bytes | bytearray | memoryview],
+    target: type,
+    options: int = 0,
+    threads: int | None = None,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+) -> list[dict[str, Any]]:
+    """多线程校验后批量反序列化共享同一 Schema 的数据包.
+
+    Args:
+        frames: JCE 字节数据包列表.
+        target: 目标 Struct 类.
+        options: 反序列化选项（位标志）.
+        threads: 校验线程数, 默认使用可用的 CPU 核心数.
+        context: 传递给字段反序列化钩子的可选上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        与 frames 顺序一致的字段字典（字段名 -> 值）列表.
+
+    Raises:
+        ValueError: 如果任一数据包无效, 错误信息包含其索引.
+    """
+
 def loads_generic(
-    data: bytes,
+    data: bytes | bytearray | memoryview,
     options: int = 0,
     bytes_mode: int = 2,
-) -> dict[int, Any]:
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: type | list[tuple] | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: bool = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+) -> Any:
     """将字节反序列化为通用字典（StructDict），无需 schema.
 
     Args:
         data: 要反序列化的 JCE 字节数据.
         options: 反序列化选项（位标志）.
-        bytes_mode: 处理字节的模式 (0: Raw, 1: String, 2: Auto).
+        bytes_mode: 处理字节的模式 (0: Raw, 1: String, 2: Auto, 3: View).
+        context: 可选的上下文字典, 传递给 `hooks` 中的钩子.
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+        max_fields: 单个结构体允许的最大字段数, 默认不限制.
+        max_total_elements: 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
+        allowed_control_bytes: Auto 模式下文本中允许出现的 ASCII 控制字符,
+            默认为 `b"\t\n\r"`.
+        schema: Struct 类或 Schema 列表. 指定时已知 Tag 以字段名为键,
+            未知 Tag 保留整数键.
+        verify_length_tag: 声明结构体总长度的字段 Tag, 指定时校验其值与实际
+            消耗的字节数一致.
+        return_consumed: 为 True 时返回 `(结果, 消耗的字节数)`.
+        map_factory: 构造 Map 容器的无参可调用对象, 默认为 dict.
+        hooks: Tag 到 `hook(value, context)` 的映射, 用于按 Tag 后处理字段值.
+            未指定时使用 `context["hooks"]`.
+
+    Returns:
+        包含反序列化数据的字典 (tag -> 值，兼容 StructDict);
+        `return_consumed` 为 True 时为 `(字典, 消耗的字节数)`.
+
+    Raises:
+        ValueError: 如果数据格式无效或解码失败.
+    """
+
+def loads_generic_partial(
+    data: bytes | bytearray | memoryview,
+    options: int = 0,
+    bytes_mode: int = 2,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+) -> tuple[Any, dict[str, Any] | None]:
+    """通用反序列化, 出错时返回出错前已解码的顶层字段.
+
+    Args:
+        data: 要反序列化的字节数据.
+        options: 反序列化选项（位标志）.
+        bytes_mode: 字节处理模式 (0=Raw, 1=String, 2=Auto, 3=View).
+        context: 可选的上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+
+    Returns:
+        已解码的字段与错误信息 (`offset`, `tag`, `error`), 解码成功时错误信息为 None.
+    """
+
+def loads_generic_value(
+    data: bytes | bytearray | memoryview,
+    options: int = 0,
+    bytes_mode: int = 2,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: str | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+) -> Any:
+    """通用反序列化单个 JCE 值 (如顶层的 List 或 Map), 忽略头部中的 Tag.
+
+    Args:
+        data: 要反序列化的字节数据.
+        options: 反序列化选项（位标志）.
+        bytes_mode: 字节处理模式 (0=Raw, 1=String, 2=Auto, 3=View).
+        context: 可选的上下文字典.
+        max_depth: 最大嵌套深度, 默认为 100.
+        endian: 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+        str_encoding: 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+        max_fields: 单个结构体允许的最大字段数, 默认不限制.
+        max_total_elements: 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
+        allowed_control_bytes: Auto 模式下文本中允许出现的 ASCII 控制字符.
+
+    Returns:
+        解码得到的值.
+
+    Raises:
+        DecodeError: 数据格式错误, 超出 `max_fields`/`max_total_elements` 限制,
+            或值之后还有多余的数据.
+    """
+
+def loads_projection(
+    data: bytes | bytearray | memoryview,
+    target: type,
+    tags: set[int],
+    options: int = 0,
+    max_depth: int | None = None,
+) -> dict[str, Any]:
+    """仅反序列化指定 Tag 的字段.
+
+    未请求的字段 (包括容器) 会被直接跳过, 不创建 Python 对象.
+
+    Args:
+        data: 要反序列化的 JCE 字节数据.
+        target: 目标 Struct 类.
+        tags: 需要解码的 Tag 集合.
+        options: 反序列化选项（位标志）.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        仅包含请求字段的字典 (字段名 -> 值).
+
+    Raises:
+        TypeError: 如果 target 不是 Struct 类.
+        ValueError: 如果数据格式无效或解码失败.
+    """
+
+def get_field(
+    data: bytes | bytearray | memoryview,
+    tag: int,
+    little_endian: bool = False,
+    bytes_mode: int = 2,
+    max_depth: int | None = None,
+) -> Any | None:
+    """按 Tag 提取顶层结构中的单个字段.
+
+    找到目标 Tag 后立即停止扫描, 其余字段仅被跳过.
+
+    Args:
+        data: 要解析的 JCE 字节数据.
+        tag: 目标字段的 Tag.
+        little_endian: 是否使用小端序.
+        bytes_mode: 字节处理模式（0: Raw, 1: String, 2: Auto）.
+        max_depth: 最大嵌套深度, 默认为 100.
 
     Returns:
-        包含反序列化数据的字典 (tag -> 值，兼容 StructDict).
+        字段值, 如果 Tag 不存在则返回 None.
 
     Raises:
         ValueError: 如果数据格式无效或解码失败.
     """
+
+def index_struct(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    duplicates_as_list: bool = False,
+    max_depth: int | None = None,
+) -> dict[int, Any]:
+    """建立顶层字段的位置索引.
+
+    通过结构扫描遍历顶层字段, 不解码任何字段值.
+
+    Args:
+        data: 要解析的 JCE 字节数据.
+        little_endian: 是否使用小端序.
+        duplicates_as_list: 为 True 时每个 Tag 对应其所有出现位置的列表,
+            否则重复 Tag 取最后一次出现的位置.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        Tag -> (值偏移量, 值长度), 偏移量与长度均不含字段头部.
+
+    Raises:
+        ValueError: 如果数据格式无效.
+    """
+
+def canonical_hash(
+    data: bytes | bytearray | memoryview,
+    options: int = 0,
+    max_depth: int | None = None,
+) -> bytes:
+    """计算数据包规范形式的 SHA-256 摘要.
+
+    语义相同但编码不同的数据包 (整数宽度, 字段顺序, Map 键顺序,
+    Float/Double) 会得到相同的摘要.
+
+    Args:
+        data: JCE 结构体字节数据.
+        options: 选项（位标志, 仅使用 LITTLE_ENDIAN）.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        32 字节的 SHA-256 摘要.
+
+    Raises:
+        ValueError: 如果数据格式无效.
+    """
+
+def debug_dump(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    max_depth: int | None = None,
+) -> str:
+    """生成 JCE 数据的结构化文本转储.
+
+    Args:
+        data: JCE 字节数据.
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        每个字段一行的转储文本, 非法数据会以 `<parse error at 0xNN>` 结尾.
+    """
+
+def is_type_compatible(
+    expected: int, actual: int, widen_floats: bool = False
+) -> bool:
+    """判断声明为 expected 类型的字段能否接受线上类型为 actual 的数据.
+
+    Args:
+        expected: 声明的 JCE 类型码.
+        actual: 线上的 JCE 类型码.
+        widen_floats: 是否允许 Float 接受 Double (对应 WIDEN_FLOATS 选项).
+
+    Returns:
+        是否兼容.
+
+    Raises:
+        ValueError: 类型码无效.
+    """
+
+def split_frames(
+    buffer: bytes | bytearray | memoryview,
+    length_type: int = 4,
+    inclusive_length: bool = True,
+    little_endian_length: bool = False,
+    max_buffer_size: int = 10485760,
+) -> tuple[list[bytes], int]:
+    """将拼接的带长度前缀数据切分为独立数据包的数据体.
+
+    Args:
+        buffer: 由多个数据包拼接而成的数据.
+        length_type: 长度头字节数 (1, 2, 4).
+        inclusive_length: 长度是否包含头部本身.
+        little_endian_length: 长度头是否为小端序.
+        max_buffer_size: 允许的最大数据包大小.
+
+    Returns:
+        数据体列表 (不含长度前缀) 与末尾不完整数据包的剩余字节数.
+
+    Raises:
+        FrameTooLarge: 某个数据包超过 `max_buffer_size`.
+        InvalidLength: 某个数据包的长度小于长度头本身.
+    """
+
+def set_schema_method_name(name: str | None) -> None:
+    """设置用于获取结构体 Schema 的方法名.
+
+    配置的方法优先, 对象不存在该方法时回退到 `__get_core_schema__`.
+    只影响 Rust 核心内的查找, Python 侧的 `tarsio.set_schema_method_name` 会同时更新两者.
+
+    Args:
+        name: 方法名, 为 None 时恢复默认.
+    """
+
+def serialize_schema(schema: type | list[tuple]) -> bytes:
+    """将 Schema 列表序列化为可持久化的二进制描述.
+
+    类型以 `module:qualname` 引用. 默认值支持基础类型, 容器, 枚举成员, `Decimal`,
+    `datetime`/`date`/`time`/`timedelta` 与 `ipaddress` 类型.
+
+    Args:
+        schema: Struct 类或 Schema 列表.
+
+    Returns:
+        二进制描述.
+
+    Raises:
+        TypeError: Schema 中含有无法序列化的值 (如函数内定义的类型, 任意对象默认值).
+    """
+
+def deserialize_schema(
+    data: bytes | bytearray | memoryview,
+    owner: type | None = None,
+    max_depth: int | None = None,
+) -> Any:
+    """从二进制描述加载并编译 Schema.
+
+    会导入描述中引用的模块并调用其中的枚举类, 只能加载可信来源的描述.
+    引用的对象必须是 Struct 或枚举类.
+
+    Args:
+        data: `serialize_schema` 生成的描述.
+        owner: 描述对应的 Struct 类, 指定时同时缓存到该类上.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        编译后的 Schema 胶囊.
+
+    Raises:
+        ValueError: 如果描述格式无效.
+        TypeError: 描述引用了 Struct 与枚举类以外的对象.
+    """
+
+def normalize_struct(
+    data: dict[str, Any], schema: list[tuple], struct_type: type | None = None
+) -> dict[int, Any]:
+    """将以字段名为键的字典转换为以 Tag 为键的字典.
+
+    Args:
+        data: 以字段名为键的字典.
+        schema: Schema 列表.
+        struct_type: 用于构造结果 (含嵌套结构体) 的字典类型.
+
+    Returns:
+        以 Tag 为键的字典.
+
+    Raises:
+        ValueError: 存在 Schema 中没有的字段名.
+    """
+
+def denormalize_struct(data: dict[int, Any], schema: list[tuple]) -> dict[str, Any]:
+    """将以 Tag 为键的字典转换为以字段名为键的字典.
+
+    Args:
+        data: 以 Tag 为键的字典.
+        schema: Schema 列表.
+
+    Returns:
+        以字段名为键的字典.
+    """
+
+def schema_compatible(old: list[tuple], new: list[tuple]) -> dict[str, Any]:
+    """检查两个版本的 Schema 之间的线上兼容性.
+
+    Args:
+        old: 旧版本的 Schema 列表.
+        new: 新版本的 Schema 列表.
+
+    Returns:
+        `{"compatible": bool, "breaking": list, "non_breaking": list}`,
+        每项变更为 `{"tag", "name", "kind", "old_type", "new_type"}`.
+    """
+
+def to_json(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    bytes_mode: int = 2,
+    max_depth: int | None = None,
+) -> str:
+    """将 JCE 数据通用解码为 JSON 文本.
+
+    Args:
+        data: JCE 字节数据.
+        little_endian: 是否使用小端序.
+        bytes_mode: 字节处理模式 (0: Raw, 1: String, 2: Auto).
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        JSON 文本, Map 表示为 `{"$map": [[键, 值], ...]}`,
+        二进制数据表示为 `{"$bytes": "<Base64>"}`.
+
+    Raises:
+        DecodeError: 数据格式错误.
+    """
+
+def from_json(
+    text: str,
+    little_endian: bool = False,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> bytes:
+    """将 `to_json` 生成的 JSON 文本编码回 JCE 数据.
+
+    Args:
+        text: JSON 文本, 顶层必须是以 Tag 为键的对象.
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 默认为 255.
+        force_string4: 是否所有字符串都使用 String4.
+
+    Returns:
+        JCE 字节数据.
+
+    Raises:
+        TypeError: 顶层不是 JSON 对象.
+        ValueError: 键不是合法 Tag, 存在重复的 Tag 或 Map 键, 或包含无法编码的 `null`.
+    """
+
+def jce_equal(
+    a: bytes | bytearray | memoryview,
+    b: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    max_depth: int | None = None,
+) -> bool:
+    """判断两段 JCE 数据在语义上是否相等.
+
+    Args:
+        a: 第一段 JCE 字节数据.
+        b: 第二段 JCE 字节数据.
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        规范形式相同时为 True.
+
+    Raises:
+        ValueError: 如果任一数据格式错误.
+    """
+
+def canonicalize(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    max_depth: int | None = None,
+) -> bytes:
+    """将 JCE 数据重编码为规范形式.
+
+    Args:
+        data: JCE 结构体字节数据.
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+
+    Returns:
+        规范化后的字节数据, 再次规范化结果不变.
+
+    Raises:
+        ValueError: 如果数据格式错误.
+    """
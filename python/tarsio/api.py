@@ -4,15 +4,20 @@
 支持 Struct 对象、StructDict 以及普通 Python 类型的编解码。
 """
 
+import sys
+from collections.abc import Callable, MutableMapping, Sequence
 from typing import IO, Any, Literal, TypeVar, cast, overload
 
+from pydantic import ValidationError
+
 from . import _core as core
 from .config import Config
 from .options import Option
 from .struct import Struct, StructDict
 
 T = TypeVar("T", bound=Struct)
-BytesMode = Literal["raw", "string", "auto"]
+BytesMode = Literal["raw", "string", "auto", "view"]
+Endian = Literal["big", "little"]
 
 
 @overload
@@ -21,6 +26,12 @@ def dumps(
     option: Option = Option.NONE,
     context: dict[str, Any] | None = None,
     exclude_unset: bool = False,
+    *,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+    str_encoding: str | None = None,
 ) -> bytes: ...
 
 
@@ -30,6 +41,13 @@ def dumps(
     option: Option = Option.NONE,
     context: dict[str, Any] | None = None,
     exclude_unset: bool = False,
+    *,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+    str_encoding: str | None = None,
+    schema: type[Struct] | list[tuple] | None = None,
 ) -> bytes: ...
 
 
@@ -38,6 +56,13 @@ def dumps(
     option: Option = Option.NONE,
     context: dict[str, Any] | None = None,
     exclude_unset: bool = False,
+    *,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+    str_encoding: str | None = None,
+    schema: type[Struct] | list[tuple] | None = None,
 ) -> bytes:
     """序列化对象为 JCE 字节数据.
 
@@ -49,6 +74,18 @@ def dumps(
             用于传递外部状态（如数据库连接、配置等）。
         exclude_unset: 是否排除未显式设置的字段.
             仅对 Struct (Pydantic 模型) 有效. 默认为 False.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+        endian: 字节序 (`"big"` 或 `"little"`).
+            指定时覆盖 `option` 中的 `Option.LITTLE_ENDIAN` 位.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+            规范值为 255, 仅在对端实现有特殊限制时调整.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
+        str_encoding: 字符串字段的文本编码 (如 `"gbk"`, `"utf-16le"`), 默认为 UTF-8.
+            支持 Python `codecs` 模块中的任意编码.
+        schema: Struct 类或 Schema 列表. 指定时按该 Schema 编码 `obj`,
+            `obj` 可以是以字段名为键的普通 dict (有默认值的字段缺少的键视为未设置,
+            必填字段缺少键或混入整数 Tag 键时抛出 ValueError),
+            此时不会调用 `@field_serializer` 钩子.
 
     Returns:
         bytes: 序列化后的二进制数据.
@@ -67,33 +104,208 @@ def dumps(
         exclude_unset=exclude_unset,
     )
 
-    if isinstance(obj, Struct):
+    if schema is not None or _schema_method(type(obj)) is not None:
         # 使用 Rust 核心进行序列化
-        # 内部使用的 EXCLUDE_UNSET 标志位 (64)
-        raw_options = int(config.option)
-        if config.exclude_unset:
-            raw_options |= 64
-
         return core.dumps(
             obj,
-            obj.__get_core_schema__(),
-            raw_options,
+            _core_schema(type(obj) if schema is None else schema),
+            _struct_options(config),
             config.context if config.context is not None else {},
+            max_depth,
+            endian,
+            string1_max,
+            force_string4,
+            str_encoding,
         )
 
     # 使用 Rust 核心进行通用序列化
     # Rust 核心会自动处理 StructDict (作为 Struct) 和 其他类型 (包装在 Tag 0 中)
+    # ORDERED_PAIRS 下顶层的 (tag, value) 列表同样按结构体编码
     data_to_dump = obj
-    if not isinstance(obj, StructDict):
+    ordered = bool(option & Option.ORDERED_PAIRS) and isinstance(obj, list)
+    if not isinstance(obj, StructDict) and not ordered:
         data_to_dump = {0: obj}
 
     return core.dumps_generic(
         data_to_dump,
         int(config.option),
         config.context if config.context is not None else {},
+        max_depth,
+        endian,
+        string1_max,
+        force_string4,
+        str_encoding,
     )
 
 
+def dumps_into(
+    obj: Struct,
+    out: bytearray,
+    option: Option = Option.NONE,
+    context: dict[str, Any] | None = None,
+    exclude_unset: bool = False,
+    *,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> int:
+    """序列化 Struct 并追加到已有的 bytearray 末尾.
+
+    适用于高吞吐场景下复用同一个输出缓冲区, 避免每次调用都创建新的 bytes 对象.
+    编码失败时 `out` 保持不变.
+
+    Args:
+        obj: 要序列化的 Struct 实例.
+        out: 输出缓冲区, 编码结果追加到其末尾.
+        option: 序列化选项.
+        context: 序列化上下文字典.
+        exclude_unset: 是否排除未显式设置的字段.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
+
+    Returns:
+        int: 写入的字节数.
+
+    Raises:
+        BufferError: `out` 存在导出的 memoryview, 无法调整大小.
+
+    Examples:
+        >>> buf = bytearray()
+        >>> dumps_into(User(uid=123), buf)
+        3
+    """
+    config = Config.from_params(
+        option=option,
+        context=context,
+        exclude_unset=exclude_unset,
+    )
+    return core.dumps_into(
+        obj,
+        _core_schema(type(obj)),
+        out,
+        _struct_options(config),
+        config.context if config.context is not None else {},
+        max_depth,
+        string1_max,
+        force_string4,
+    )
+
+
+def dumps_batch(
+    objs: Sequence[Struct],
+    option: Option = Option.NONE,
+    context: dict[str, Any] | None = None,
+    exclude_unset: bool = False,
+    *,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> list[bytes]:
+    """批量序列化同一类型的 Struct 对象.
+
+    Schema 只解析一次, 所有对象复用同一个编码缓冲区,
+    适合在循环中序列化大量小结构体的场景.
+
+    Args:
+        objs: 要序列化的 Struct 对象序列, 必须均为同一个类的实例.
+        option: 序列化选项.
+        context: 序列化上下文.
+        exclude_unset: 是否排除未显式设置的字段.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
+
+    Returns:
+        list[bytes]: 与 `objs` 一一对应的二进制数据,
+            每一项都与单独调用 `dumps` 的结果完全一致.
+
+    Raises:
+        TypeError: 如果对象不是同一个 Struct 类的实例.
+    """
+    if not objs:
+        return []
+    config = Config.from_params(
+        option=option,
+        context=context,
+        exclude_unset=exclude_unset,
+    )
+    return core.dumps_batch(
+        list(objs),
+        type(objs[0]),
+        _struct_options(config),
+        config.context if config.context is not None else {},
+        max_depth,
+        string1_max,
+        force_string4,
+    )
+
+
+def dumps_batch_framed(
+    objs: Sequence[Struct],
+    option: Option = Option.NONE,
+    context: dict[str, Any] | None = None,
+    exclude_unset: bool = False,
+    *,
+    length_type: int = 4,
+    inclusive_length: bool = True,
+    little_endian_length: bool = False,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> bytes:
+    """批量序列化并拼接为带长度前缀的数据流.
+
+    一次性完成编码与分帧, 输出与依次调用 `LengthPrefixedWriter.write` 相同.
+
+    Args:
+        objs: 要序列化的 Struct 对象序列, 必须均为同一个类的实例.
+        option: 序列化选项.
+        context: 序列化上下文.
+        exclude_unset: 是否排除未显式设置的字段.
+        length_type: 长度头字节数 (1, 2 或 4).
+        inclusive_length: 长度值是否包含长度头本身.
+        little_endian_length: 长度头是否为小端序.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
+
+    Returns:
+        bytes: 所有数据包按顺序拼接后的二进制数据.
+
+    Raises:
+        TypeError: 如果对象不是同一个 Struct 类的实例.
+        ValueError: 如果 length_type 无效或数据包超出长度头可表示的范围.
+    """
+    if not objs:
+        return b""
+    config = Config.from_params(
+        option=option,
+        context=context,
+        exclude_unset=exclude_unset,
+    )
+    return core.dumps_batch_framed(
+        list(objs),
+        type(objs[0]),
+        _struct_options(config),
+        config.context if config.context is not None else {},
+        length_type,
+        inclusive_length,
+        little_endian_length,
+        max_depth,
+        string1_max,
+        force_string4,
+    )
+
+
+def _struct_options(config: Config) -> int:
+    """计算 Struct 编码使用的选项位 (含内部的 EXCLUDE_UNSET 标志)."""
+    raw_options = int(config.option)
+    if config.exclude_unset:
+        raw_options |= int(Option.EXCLUDE_UNSET)
+    return raw_options
+
+
 @overload
 def dump(
     obj: Struct,
@@ -147,6 +359,13 @@ def loads(
     option: Option = Option.NONE,
     *,
     context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
 ) -> T: ...
 
 
@@ -157,6 +376,18 @@ def loads(
     option: Option = Option.NONE,
     *,
     bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
 ) -> StructDict: ...
 
 
@@ -167,9 +398,82 @@ def loads(
     option: Option = Option.NONE,
     *,
     bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
 ) -> dict[int, Any]: ...
 
 
+@overload
+def loads(
+    data: bytes | bytearray | memoryview,
+    target: type[StructDict] | type[dict] = StructDict,
+    option: Option = Option.NONE,
+    *,
+    bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: type[Struct] | list[tuple],
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[False] = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+) -> dict[int | str, Any]: ...
+
+
+@overload
+def loads(
+    data: bytes | bytearray | memoryview,
+    target: type[T],
+    option: Option = Option.NONE,
+    *,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[True],
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+) -> tuple[T, int]: ...
+
+
+@overload
+def loads(
+    data: bytes | bytearray | memoryview,
+    target: type[StructDict] | type[dict] = StructDict,
+    option: Option = Option.NONE,
+    *,
+    bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: type[Struct] | list[tuple] | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: Literal[True],
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+) -> tuple[StructDict | dict[int | str, Any], int]: ...
+
+
 def loads(
     data: bytes | bytearray | memoryview,
     target: type[T] | type[StructDict] | type[dict] = StructDict,
@@ -177,7 +481,18 @@ def loads(
     *,
     bytes_mode: BytesMode = "auto",
     context: dict[str, Any] | None = None,
-) -> T | StructDict | dict[int, Any]:
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+    schema: type[Struct] | list[tuple] | None = None,
+    verify_length_tag: int | None = None,
+    return_consumed: bool = False,
+    map_factory: Callable[[], MutableMapping[Any, Any]] | None = None,
+    hooks: dict[int, Callable[[Any, Any], Any]] | None = None,
+) -> Any:
     """反序列化 JCE 字节为 Python 对象.
 
     Args:
@@ -186,6 +501,7 @@ def loads(
             - `Struct` 子类: 尝试解析并验证为该结构体实例.
             - `StructDict` (默认): 解析为 StructDict 实例 (Struct 语义).
             - `dict`: 解析为普通 dict（将 StructDict 递归转换为 dict）。
+            - 提供 Schema 方法的普通类: 需指定 `Option.SLOTS`, 直接解码为该类的实例.
         option: 反序列化选项 (如 `Option.LITTLE_ENDIAN`).
         bytes_mode: 字节数据的处理模式 (仅对通用解析 target=StructDict/dict 有效).
             - `'raw'`: 保持所有 bytes 类型不变.
@@ -194,58 +510,855 @@ def loads(
               1. 无损解码: 优先尝试 UTF-8 解码.
               2. JCE 探测: 尝试作为嵌套 JCE 结构解析.
               3. 回退: 保持为 bytes.
-        context: Pydantic 验证器上下文.
+            - `'view'`: 返回指向输入数据的 memoryview 切片, 不拷贝.
+              切片持有对输入对象的引用; 输入为可变缓冲区 (如 bytearray) 时,
+              修改输入会反映到切片中, 且切片存活期间输入无法调整大小.
+        context: 反序列化上下文.
+            传递给字段的自定义反序列化器 (`@field_deserializer`), `hooks` 中的钩子
+            及 Pydantic 验证器.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+        endian: 字节序 (`"big"` 或 `"little"`).
+            指定时覆盖 `option` 中的 `Option.LITTLE_ENDIAN` 位.
+        str_encoding: 字符串字段的文本编码 (如 `"gbk"`, `"utf-16le"`), 默认为 UTF-8.
+            支持 Python `codecs` 模块中的任意编码.
+        max_fields: 单个结构体允许的最大字段数, 默认不限制.
+        max_total_elements: 结构体字段, List 元素与 Map 键值对的最大总数,
+            默认不限制. 用于处理不可信输入.
+        allowed_control_bytes: `'auto'` 模式下文本中允许出现的 ASCII 控制字符,
+            默认为 `b"\t\n\r"`. 含有其他控制字符的数据保留为 bytes.
+        schema: Struct 类或 Schema 列表 (仅对通用解析有效, 其他目标指定时抛出 ValueError).
+            指定时 Schema 中已知的 Tag 以字段名为键, 未知 Tag 保留整数键,
+            结果为普通 dict. 用于仅有部分 Schema 时查看数据.
+        verify_length_tag: 声明结构体总长度的字段 Tag (某些协议内嵌冗余的长度字段).
+            指定时校验该字段的值与实际解码消耗的字节数一致, 不符或字段缺失时抛出
+            `DecodeError`.
+        return_consumed: 为 True 时返回 `(结果, 消耗的字节数)`, 用于在没有长度前缀的
+            协议中推进自己的读取位置. 解码在数据结束或遇到顶层 StructEnd 时停止,
+            消耗的字节数包含该 StructEnd.
+        map_factory: 构造 Map 容器的无参可调用对象 (如 `collections.OrderedDict`),
+            键值对按线上顺序通过 `__setitem__` 写入, 默认为 dict.
+            对 Struct 目标, 字段值随后仍会按注解经过 Pydantic 校验.
+        hooks: Tag 到 `hook(value, context)` 的映射, 用于对各层结构体中指定 Tag 的
+            字段值进行后处理 (Map/List 元素不受影响). 钩子抛出的异常原样传播.
+            未指定时使用 `context["hooks"]`.
 
     Returns:
         T: 目标类型实例 (如果 target=Struct).
         StructDict: 结构体数据 (如果 target=StructDict).
-        dict: 字典数据 (如果 target=dict).
+        dict: 字典数据 (如果 target=dict 或指定了 schema).
+        tuple: `return_consumed` 为 True 时为 `(上述结果, 消耗的字节数)`.
 
     Raises:
         DecodeError: 数据格式错误.
         PartialDataError: 数据不完整.
+        ValueError: 目标不是 StructDict 或 dict 时指定了 `schema`.
     """
     # 通用解码
     if target is StructDict or target is dict:
-        # Map BytesMode string to integer for Rust
-        mode_int = 2  # default auto
-        if bytes_mode == "raw":
-            mode_int = 0
-        elif bytes_mode == "string":
-            mode_int = 1
-
         # 使用 Rust 核心进行通用反序列化
         result = core.loads_generic(
-            bytes(data),
+            data,
             int(option),
-            mode_int,
+            _bytes_mode_to_int(bytes_mode),
+            context,
+            max_depth,
+            endian,
+            str_encoding,
+            max_fields,
+            max_total_elements,
+            allowed_control_bytes,
+            None if schema is None else _core_schema(schema),
+            verify_length_tag,
+            return_consumed=return_consumed,
+            map_factory=map_factory,
+            hooks=hooks,
         )
+        consumed = None
+        if return_consumed:
+            result, consumed = result
 
-        # 3. 如目标为 dict，则直接返回 (Rust 已经返回了纯 dict)
-        if target is dict:
-            return cast(dict[int, Any], result)
-
-        # 4. 默认目标为 StructDict，需要将顶层转换为 StructDict
-        if not isinstance(result, StructDict):
+        # 默认目标为 StructDict, 需要将顶层转换为 StructDict;
+        # 目标为 dict, 指定了 schema 或要求有序列表时 Rust 已经返回了纯 dict / 列表
+        if (
+            target is not dict
+            and schema is None
+            and not option & Option.ORDERED_PAIRS
+            and not isinstance(result, StructDict)
+        ):
             result = StructDict(result)
-        return cast(StructDict, result)
+        return result if consumed is None else (result, consumed)
+
+    if schema is not None:
+        raise ValueError("schema is only supported when target is StructDict or dict")
 
     # Schema 模式
     if issubclass(target, Struct):
         # 使用 Rust 核心进行反序列化并直接实例化
         # 注意: core.loads 现在直接返回实例
-        return target.model_validate(
-            core.loads(
-                bytes(data),
-                target,
-                int(option),
-            ),
-            context=context,
+        # SLOTS 仅适用于普通类, Struct 目标忽略该选项, 始终经过 Pydantic 验证
+        decoded = core.loads(
+            data,
+            target,
+            int(option & ~Option.SLOTS),
+            context,
+            max_depth,
+            endian,
+            str_encoding,
+            verify_length_tag,
+            return_consumed=return_consumed,
+            map_factory=map_factory,
+            hooks=hooks,
+            max_fields=max_fields,
+            max_total_elements=max_total_elements,
+        )
+        if return_consumed:
+            fields, consumed = decoded
+            return target.model_validate(fields, context=context), consumed
+        return target.model_validate(decoded, context=context)
+
+
+    # __slots__ 模式: Rust 核心直接创建并填充实例, 不经过 Pydantic
+    if option & Option.SLOTS:
+        return core.loads(
+            data,
+            target,
+            int(option),
+            context,
+            max_depth,
+            endian,
+            str_encoding,
+            verify_length_tag,
+            return_consumed=return_consumed,
+            map_factory=map_factory,
+            hooks=hooks,
+            max_fields=max_fields,
+            max_total_elements=max_total_elements,
         )
 
     raise NotImplementedError("Please use Struct or supported types.")
 
 
+def loads_partial(
+    data: bytes | bytearray | memoryview,
+    option: Option = Option.NONE,
+    *,
+    bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+) -> tuple[StructDict, dict[str, Any] | None]:
+    """通用反序列化, 出错时返回出错前已成功解码的顶层字段.
+
+    用于分析损坏或截断的数据包. 与 `loads` 不同, 遇到第一个错误时不抛出异常,
+    而是停止解码并同时返回已解码的字段与错误信息.
+    嵌套结构中的错误视为所在的顶层字段解码失败.
+
+    Args:
+        data: 输入的二进制数据.
+        option: 反序列化选项.
+        bytes_mode: 字节数据的处理模式, 同 `loads`.
+        context: 反序列化上下文.
+        max_depth: 最大嵌套深度. 默认为 100.
+        endian: 字节序 (`"big"` 或 `"little"`).
+        str_encoding: 字符串字段的文本编码, 同 `loads`.
+
+    Returns:
+        tuple[StructDict, dict[str, Any] | None]: 已解码的字段与错误信息.
+            错误信息包含 `offset` (出错字段头部的偏移量), `tag`
+            (出错字段的 Tag, 读取字段头部失败时为 None) 与 `error` (异常对象);
+            数据完整解码时为 None.
+
+    Examples:
+        >>> loads_partial(b"\x00\x01\x16\x05ab")
+        ({0: 1}, {'offset': 2, 'tag': 1, 'error': DecodeError('Buffer overflow (at offset 4)')})
+    """
+    result, error = core.loads_generic_partial(
+        data,
+        int(option),
+        _bytes_mode_to_int(bytes_mode),
+        context,
+        max_depth,
+        endian,
+        str_encoding,
+    )
+    if isinstance(result, dict) and not isinstance(result, StructDict):
+        result = StructDict(result)
+    return result, error
+
+
+def loads_value(
+    data: bytes | bytearray | memoryview,
+    option: Option = Option.NONE,
+    *,
+    bytes_mode: BytesMode = "auto",
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+    endian: Endian | None = None,
+    str_encoding: str | None = None,
+    max_fields: int | None = None,
+    max_total_elements: int | None = None,
+    allowed_control_bytes: bytes | None = None,
+) -> Any:
+    """通用反序列化单个 JCE 值.
+
+    用于数据体本身就是一个值 (如顶层的 List, Map 或 SimpleList), 而不是
+    结构体字段序列的协议. 读取一个字段头部并解码其值, 头部中的 Tag 被忽略.
+
+    Args:
+        data: 输入的二进制数据.
+        option: 反序列化选项.
+        bytes_mode: 字节数据的处理模式, 同 `loads`.
+        context: 反序列化上下文.
+        max_depth: 最大嵌套深度. 默认为 100.
+        endian: 字节序 (`"big"` 或 `"little"`).
+        str_encoding: 字符串字段的文本编码, 同 `loads`.
+        max_fields: 单个结构体允许的最大字段数, 同 `loads`.
+        max_total_elements: 结构体字段, List 元素与 Map 键值对的最大总数, 同 `loads`.
+        allowed_control_bytes: `'auto'` 模式下文本中允许出现的 ASCII 控制字符, 同 `loads`.
+
+    Returns:
+        Any: 解码得到的值. 结构体解码为 `StructDict`.
+
+    Raises:
+        DecodeError: 数据格式错误, 超出 `max_fields`/`max_total_elements` 限制,
+            或值之后还有多余的数据.
+
+    Examples:
+        >>> loads_value(b"\x09\x00\x02\x00\x01\x00\x02")
+        [1, 2]
+    """
+    return core.loads_generic_value(
+        data,
+        int(option),
+        _bytes_mode_to_int(bytes_mode),
+        context,
+        max_depth,
+        endian,
+        str_encoding,
+        max_fields,
+        max_total_elements,
+        allowed_control_bytes,
+    )
+
+
+def loads_batch(
+    frames: Sequence[bytes | bytearray | memoryview],
+    target: type[T],
+    option: Option = Option.NONE,
+    *,
+    skip_errors: bool = False,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+) -> list[T | None]:
+    """批量反序列化同一类型的数据包.
+
+    目标 Schema 只解析一次, 适合一次性处理大量数据包的场景.
+
+    Args:
+        frames: 数据包序列, 每一项都是一个完整的结构体编码.
+        target: 目标 Struct 类.
+        option: 反序列化选项.
+        skip_errors: 是否跳过损坏的数据包.
+            为 True 时解码失败 (DecodeError, ValueError) 或验证失败的数据包在结果中
+            对应 None, 其他异常 (如字段钩子抛出的 TypeError) 照常抛出;
+            为 False (默认) 时第一个错误会直接抛出.
+        context: Pydantic 验证器上下文.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        list[T | None]: 与 `frames` 一一对应的结构体实例.
+
+    Raises:
+        DecodeError: 数据格式错误 (仅 skip_errors=False).
+        ValidationError: 字段验证失败 (仅 skip_errors=False).
+    """
+    decoded = core.loads_batch(
+        list(frames),
+        target,
+        int(option),
+        skip_errors,
+        context,
+        max_depth,
+    )
+    results: list[T | None] = []
+    for item in decoded:
+        if item is None:
+            results.append(None)
+            continue
+        try:
+            results.append(target.model_validate(item, context=context))
+        except ValidationError:
+            if not skip_errors:
+                raise
+            results.append(None)
+    return results
+
+
+def loads_batch_parallel(
+    frames: Sequence[bytes | bytearray | memoryview],
+    target: type[T],
+    option: Option = Option.NONE,
+    *,
+    threads: int | None = None,
+    context: dict[str, Any] | None = None,
+    max_depth: int | None = None,
+) -> list[T]:
+    """多线程校验后批量反序列化同一类型的数据包.
+
+    结构校验在释放 GIL 后由多个线程并行完成, 全部通过后才在当前线程构建对象,
+    结果顺序与 `frames` 一致.
+
+    Args:
+        frames: 数据包序列, 每一项都是一个完整的结构体编码.
+        target: 目标 Struct 类.
+        option: 反序列化选项.
+        threads: 校验线程数, 默认使用可用的 CPU 核心数.
+        context: Pydantic 验证器上下文.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        list[T]: 与 `frames` 一一对应的结构体实例.
+
+    Raises:
+        DecodeError: 任一数据包结构非法或解码失败, 错误信息包含其索引.
+        ValidationError: 字段验证失败, Python 3.11+ 下异常注释包含数据包索引.
+        RuntimeError: 校验线程异常退出.
+    """
+    decoded = core.loads_batch_parallel(
+        list(frames),
+        target,
+        int(option),
+        threads,
+        context,
+        max_depth,
+    )
+    results: list[T] = []
+    for index, item in enumerate(decoded):
+        try:
+            results.append(target.model_validate(item, context=context))
+        except ValidationError as e:
+            # ValidationError 无法改写消息, 以异常注释 (Python 3.11+) 给出索引
+            if sys.version_info >= (3, 11):
+                e.add_note(f"Frame {index}")
+            raise
+    return results
+
+
+def loads_projection(
+    data: bytes | bytearray | memoryview,
+    target: type[Struct],
+    tags: set[int] | frozenset[int],
+    option: Option = Option.NONE,
+    *,
+    max_depth: int | None = None,
+) -> dict[str, Any]:
+    """仅反序列化指定 Tag 的字段 (投影解码).
+
+    未请求的字段会被直接跳过, 嵌套容器不会创建任何 Python 对象,
+    适用于只关心大结构体中少数字段的场景.
+
+    Args:
+        data: 输入的二进制数据.
+        target: 目标 Struct 类 (仅用于提供 Schema).
+        tags: 需要解码的 Tag 集合.
+        option: 反序列化选项.
+        max_depth: 最大嵌套深度 (同时限制跳过字段时的深度), 默认为 100.
+
+    Returns:
+        dict[str, Any]: 仅包含请求字段的字典 (字段名 -> 值).
+            线上缺失的请求字段会填充为默认值.
+
+    Raises:
+        DecodeError: 数据格式错误.
+
+    Examples:
+        >>> loads_projection(data, User, {0})
+        {'uid': 100}
+    """
+    return core.loads_projection(data, target, set(tags), int(option), max_depth)
+
+
+def get_field(
+    data: bytes | bytearray | memoryview,
+    tag: int,
+    little_endian: bool = False,
+    *,
+    bytes_mode: BytesMode = "auto",
+    max_depth: int | None = None,
+) -> Any | None:
+    """按 Tag 提取顶层结构中的单个字段.
+
+    顺序扫描顶层字段, 找到目标 Tag 后立即解码返回, 不解析剩余数据.
+    适用于按命令字等单个字段路由数据包的场景.
+
+    Args:
+        data: 输入的二进制数据.
+        tag: 目标字段的 Tag.
+        little_endian: 是否使用小端序.
+        bytes_mode: 字节数据的处理模式, 同 `loads`.
+        max_depth: 最大嵌套深度 (同时限制跳过字段时的深度), 默认为 100.
+
+    Returns:
+        Any | None: 字段值 (通用解码), 如果 Tag 不存在则返回 None.
+
+    Raises:
+        DecodeError: 数据格式错误.
+
+    Examples:
+        >>> get_field(data, 0)
+        100
+    """
+    return core.get_field(
+        data, tag, little_endian, _bytes_mode_to_int(bytes_mode), max_depth
+    )
+
+
+@overload
+def index_struct(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    duplicates: Literal["last"] = "last",
+    max_depth: int | None = None,
+) -> dict[int, tuple[int, int]]: ...
+
+
+@overload
+def index_struct(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    duplicates: Literal["list"],
+    max_depth: int | None = None,
+) -> dict[int, list[tuple[int, int]]]: ...
+
+
+def index_struct(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    duplicates: Literal["last", "list"] = "last",
+    max_depth: int | None = None,
+) -> dict[int, tuple[int, int]] | dict[int, list[tuple[int, int]]]:
+    """建立顶层字段的位置索引, 用于按需解码单个字段.
+
+    只扫描数据结构而不解码字段值, 适用于只访问少数字段的大数据包.
+    索引中的偏移量与长度均不含字段头部, 即 `data[offset:offset + length]`
+    恰好是字段值的编码.
+
+    Args:
+        data: 输入的二进制数据.
+        little_endian: 是否使用小端序.
+        duplicates: 重复 Tag 的处理方式. `"last"` 取最后一次出现的位置;
+            `"list"` 时每个 Tag 都对应按出现顺序排列的位置列表.
+        max_depth: 扫描嵌套值时的最大深度, 默认为 100.
+
+    Returns:
+        dict: Tag -> (值偏移量, 值长度), `duplicates="list"` 时值为位置列表.
+
+    Raises:
+        DecodeError: 数据格式错误.
+        ValueError: duplicates 取值无效.
+
+    Examples:
+        >>> index_struct(data)
+        {0: (1, 1), 1: (4, 5)}
+    """
+    if duplicates not in ("last", "list"):
+        raise ValueError(f"Invalid duplicates mode: {duplicates!r}")
+    return core.index_struct(data, little_endian, duplicates == "list", max_depth)
+
+
+def canonical_hash(
+    data: bytes | bytearray | memoryview,
+    option: Option = Option.NONE,
+    *,
+    max_depth: int | None = None,
+) -> bytes:
+    """计算数据包规范形式的 SHA-256 摘要.
+
+    数据会先被重编码为规范形式 (最小整数宽度, Tag 与 Map 键有序,
+    Float 提升为 Double 并归一化 `-0.0`/NaN), 因此语义相同但字节不同的
+    数据包会得到相同的摘要, 可用作去重或缓存的键.
+
+    Args:
+        data: 输入的二进制数据 (结构体字段序列).
+        option: 选项, 仅 `Option.LITTLE_ENDIAN` 生效.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        bytes: 32 字节的 SHA-256 摘要.
+
+    Raises:
+        DecodeError: 数据格式错误.
+    """
+    return core.canonical_hash(data, int(option), max_depth)
+
+
+def canonicalize(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    max_depth: int | None = None,
+) -> bytes:
+    """将 JCE 数据重编码为规范形式.
+
+    规范形式使用最小整数宽度, Tag 升序的结构体, 按键排序的 Map,
+    按长度选择的字符串类型 (String1/String4), 并将 Float 提升为 Double.
+    语义相同的数据包规范化后字节完全一致, 且对结果再次规范化不会产生变化.
+
+    Args:
+        data: 输入的二进制数据 (结构体字段序列).
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        bytes: 规范化后的二进制数据.
+
+    Raises:
+        DecodeError: 数据格式错误.
+
+    Examples:
+        >>> canonicalize(b"\x02\x00\x00\x00\x01")
+        b'\x00\x01'
+    """
+    return core.canonicalize(data, little_endian, max_depth)
+
+
+def jce_equal(
+    a: bytes | bytearray | memoryview,
+    b: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    max_depth: int | None = None,
+) -> bool:
+    """判断两段 JCE 数据在语义上是否相等.
+
+    同一数据可能有多种合法编码 (如整数 1 既可以是 Int1 也可以是 Int4),
+    直接比较字节会产生误判. 本函数将双方重编码为规范形式后比较,
+    整数宽度, 字段顺序, Map 键顺序以及 Float/Double 的差异都不会影响结果.
+
+    Args:
+        a: 第一段二进制数据 (结构体字段序列).
+        b: 第二段二进制数据 (结构体字段序列).
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        bool: 语义相等时为 True.
+
+    Raises:
+        DecodeError: 任一数据格式错误.
+
+    Examples:
+        >>> jce_equal(b"\x00\x01", b"\x02\x00\x00\x00\x01")
+        True
+    """
+    return core.jce_equal(a, b, little_endian, max_depth)
+
+
+def debug_dump(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    max_depth: int | None = None,
+) -> str:
+    """生成 JCE 数据的结构化文本转储, 用于调试与逆向未知数据包.
+
+    每个字段输出一行, 包含偏移量, Tag, 类型与值,
+    结构体, List 与 Map 的子元素按层级缩进.
+    遇到非法数据时不会抛出异常, 而是追加 `<parse error at 0xNN>` 标记并停止.
+
+    Args:
+        data: 输入的二进制数据.
+        little_endian: 是否使用小端序.
+        max_depth: 最大嵌套深度, 超出时视为非法数据. 默认为 100.
+
+    Returns:
+        str: 转储文本.
+
+    Examples:
+        >>> print(debug_dump(bytes.fromhex("010100")))
+        offset=0x00 tag=0 type=Int2 value=256
+    """
+    return core.debug_dump(data, little_endian, max_depth)
+
+
+def is_type_compatible(
+    expected: int, actual: int, *, widen_floats: bool = False
+) -> bool:
+    """判断声明为 `expected` 类型的字段能否接受线上类型为 `actual` 的数据.
+
+    与解码时使用的兼容规则一致: 各宽度的整数 (含 ZeroTag) 互通,
+    String1 与 String4 互通, Double 可接受 Float, 其余类型必须完全一致.
+    可用于在不解码的情况下离线校验抓包数据与 Schema 是否匹配.
+
+    Args:
+        expected: 声明的 JCE 类型码 (0-13).
+        actual: 线上的 JCE 类型码 (0-13).
+        widen_floats: 是否按 `Option.WIDEN_FLOATS` 的规则允许 Float 字段接受 Double.
+
+    Returns:
+        bool: 是否兼容.
+
+    Raises:
+        ValueError: 类型码无效.
+
+    Examples:
+        >>> is_type_compatible(5, 4)  # Double 字段接受 Float
+        True
+    """
+    return core.is_type_compatible(expected, actual, widen_floats)
+
+
+def set_schema_method_name(name: str | None) -> None:
+    """设置用于获取结构体 Schema 的方法名.
+
+    配置后编码器优先调用该方法获取 Schema 列表, 对象不存在该方法时
+    回退到 `__get_core_schema__`. 顶层 `dumps` 与各函数的 `schema` 参数
+    同样按此查找. 编译后的 Schema 缓存在类型上, 应在编解码任何数据之前设置.
+
+    Args:
+        name: 方法名, 为 None 时恢复默认.
+    """
+    global _schema_method_name
+    core.set_schema_method_name(name)
+    _schema_method_name = name
+
+
+def schema_compatible(
+    old: type[Struct] | list[tuple],
+    new: type[Struct] | list[tuple],
+) -> dict[str, Any]:
+    """检查两个版本的 Schema 之间的线上兼容性.
+
+    按 Tag 比较两个版本: 删除字段与不兼容的类型修改 (如 String -> Int) 属于破坏性变更;
+    新增字段与双向兼容的类型修改 (如 INT8 -> INT64) 属于非破坏性变更.
+    嵌套结构体的内部字段不会递归比较.
+
+    Args:
+        old: 旧版本的 Struct 类或 Schema 列表.
+        new: 新版本的 Struct 类或 Schema 列表.
+
+    Returns:
+        dict[str, Any]: 兼容性报告, 包含 `compatible` (bool),
+            `breaking` 与 `non_breaking` (变更列表). 每项变更包含
+            `tag`, `name`, `kind` (`"removed"`, `"added"` 或 `"type_changed"`),
+            `old_type` 与 `new_type` (类型码, 不存在时为 None).
+
+    Examples:
+        >>> schema_compatible(UserV1, UserV2)["compatible"]
+        True
+    """
+    return core.schema_compatible(_core_schema(old), _core_schema(new))
+
+
+def normalize_struct(
+    data: dict[str, Any],
+    schema: type[Struct] | list[tuple],
+) -> StructDict:
+    """将以字段名为键的字典转换为以 Tag 为键的 `StructDict`, 不经过二进制编码.
+
+    嵌套结构体字段, 元素为结构体的 List 以及值为结构体的 Map 会按各自的 Schema
+    递归转换. 结果可以直接传给 `dumps`, 按结构体语义编码. 已经是 Tag 的整数键原样保留.
+
+    Args:
+        data: 以字段名为键的字典.
+        schema: Struct 类或 Schema 列表.
+
+    Returns:
+        StructDict: 以 Tag 为键的结构体数据.
+
+    Raises:
+        ValueError: 存在 Schema 中没有的字段名.
+
+    Examples:
+        >>> normalize_struct({"id": 1, "name": "Alice"}, User)
+        {0: 1, 1: 'Alice'}
+    """
+    return core.normalize_struct(data, _core_schema(schema), StructDict)
+
+
+def denormalize_struct(
+    data: dict[int, Any],
+    schema: type[Struct] | list[tuple],
+) -> dict[str, Any]:
+    """将以 Tag 为键的字典转换为以字段名为键的字典, 是 `normalize_struct` 的逆操作.
+
+    常用于把 `loads(data)` 得到的 `StructDict` 转为可读的字典,
+    Schema 中没有的 Tag 原样保留.
+
+    Args:
+        data: 以 Tag 为键的字典.
+        schema: Struct 类或 Schema 列表.
+
+    Returns:
+        dict[str, Any]: 以字段名为键的字典.
+
+    Examples:
+        >>> denormalize_struct({0: 1, 1: "Alice"}, User)
+        {'id': 1, 'name': 'Alice'}
+    """
+    return core.denormalize_struct(data, _core_schema(schema))
+
+
+def serialize_schema(schema: type[Struct] | list[tuple]) -> bytes:
+    """将 Schema 序列化为可持久化的二进制描述.
+
+    配合 `deserialize_schema` 跨进程缓存编译结果, 每次启动的进程 (如命令行工具)
+    无需重新内省 Pydantic 模型. 字段引用的 Struct 类与枚举类以 `module:qualname`
+    记录, 加载时重新导入. 默认值支持基础类型, 容器, 枚举成员, `Decimal`,
+    `datetime`/`date`/`time` (无时区或固定偏移量), `timedelta` 与 `ipaddress` 类型.
+
+    Args:
+        schema: Struct 类或 Schema 列表.
+
+    Returns:
+        bytes: 二进制描述.
+
+    Raises:
+        TypeError: Schema 引用了无法导入的类型 (如函数内定义的类),
+            或默认值无法序列化 (如任意 Python 对象, 此时应改用 `default_factory`).
+
+    Examples:
+        >>> Path("user.schema").write_bytes(serialize_schema(User))
+    """
+    return core.serialize_schema(_core_schema(schema))
+
+
+def deserialize_schema(
+    data: bytes | bytearray | memoryview,
+    owner: type[Struct] | None = None,
+    *,
+    max_depth: int | None = None,
+) -> Any:
+    """从 `serialize_schema` 生成的描述加载编译后的 Schema.
+
+    指定 `owner` 时结果缓存到该类上, 之后的编解码不再调用其 `__get_core_schema__`.
+    描述应与类定义保持同步, 类定义变化后需要重新生成.
+
+    Warning:
+        加载描述会导入其中引用的模块并调用其中的枚举类, 与 `pickle` 一样可能执行
+        任意导入代码. 只加载本程序自身生成或来源可信的描述. 引用的对象不是
+        Struct 或枚举类时抛出 TypeError, 但对应模块的导入代码已经执行.
+
+    Args:
+        data: 二进制描述.
+        owner: 描述对应的 Struct 类.
+        max_depth: 描述中默认值的最大嵌套深度, 默认为 100.
+
+    Returns:
+        Any: 编译后的 Schema, 可作为 `loads` 等函数的 Schema 参数.
+
+    Raises:
+        DecodeError: 描述格式无效.
+        TypeError: 描述引用了 Struct 与枚举类以外的对象.
+
+    Examples:
+        >>> deserialize_schema(Path("user.schema").read_bytes(), owner=User)
+    """
+    return core.deserialize_schema(data, owner, max_depth)
+
+
+# 与 core 中配置的 Schema 方法名保持一致, 供 Python 侧解析 Schema 参数
+_schema_method_name: str | None = None
+
+
+def _schema_method(target: Any) -> Callable[[], list[tuple]] | None:
+    """查找 Schema 方法: 优先使用配置的方法名, 其次为 `__get_core_schema__`."""
+    if _schema_method_name is not None:
+        method = getattr(target, _schema_method_name, None)
+        if method is not None:
+            return method
+    return getattr(target, "__get_core_schema__", None)
+
+
+def _core_schema(target: type[Struct] | list[tuple]) -> list[tuple]:
+    if isinstance(target, list):
+        return target
+    method = _schema_method(target)
+    if method is None:
+        raise TypeError(f"type object '{target.__name__}' has no schema method")
+    return method()
+
+
+def to_json(
+    data: bytes | bytearray | memoryview,
+    little_endian: bool = False,
+    *,
+    bytes_mode: BytesMode = "auto",
+    max_depth: int | None = None,
+) -> str:
+    """将 JCE 数据通用解码为 JSON 文本, 用于日志与调试.
+
+    按 `loads` 的通用解码规则解码后转换为 JSON. 结构体输出为以 Tag 字符串为键的对象;
+    Map 输出为 `{"$map": [[键, 值], ...]}`, 键保留原始类型 (如整数 `1` 与字符串 `"1"`);
+    二进制数据输出为 `{"$bytes": "<Base64>"}`; NaN 与无穷大输出为 `null`.
+
+    Args:
+        data: 输入的二进制数据.
+        little_endian: 是否使用小端序.
+        bytes_mode: 字节数据的处理模式, 同 `loads` (`'view'` 按 `'raw'` 处理).
+        max_depth: 最大嵌套深度, 超出时抛出异常. 默认为 100.
+
+    Returns:
+        str: JSON 文本.
+
+    Raises:
+        DecodeError: 数据格式错误.
+
+    Examples:
+        >>> to_json(dumps(StructDict({0: 1, 1: b"\\x00"})))
+        '{"0":1,"1":{"$bytes":"AA=="}}'
+    """
+    return core.to_json(
+        data, little_endian, _bytes_mode_to_int(bytes_mode), max_depth
+    )
+
+
+def from_json(
+    text: str,
+    little_endian: bool = False,
+    *,
+    max_depth: int | None = None,
+    string1_max: int = 255,
+    force_string4: bool = False,
+) -> bytes:
+    """将 `to_json` 生成的 JSON 文本编码回 JCE 数据.
+
+    嵌套对象按以下规则编码: 仅含 `$bytes` 键的对象编码为二进制数据;
+    仅含 `$map` 键的对象编码为 Map; 其余对象 (包括空对象) 编码为结构体,
+    其键必须全部为 Tag (0-255 的十进制字符串).
+
+    Args:
+        text: JSON 文本, 顶层必须是以 Tag 为键的对象.
+        little_endian: 是否使用小端序.
+        max_depth: JSON 的最大嵌套深度, 超出时抛出异常. 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
+
+    Returns:
+        bytes: JCE 二进制数据.
+
+    Raises:
+        TypeError: 顶层不是 JSON 对象.
+        ValueError: 键不是合法 Tag, 存在重复的 Tag 或 Map 键, 或包含无法编码的 `null`.
+    """
+    return core.from_json(text, little_endian, max_depth, string1_max, force_string4)
+
+
+def _bytes_mode_to_int(bytes_mode: BytesMode) -> int:
+    """将 BytesMode 字符串映射为 Rust 核心使用的整数."""
+    if bytes_mode == "raw":
+        return 0
+    if bytes_mode == "string":
+        return 1
+    if bytes_mode == "view":
+        return 3
+    return 2
+
+
 @overload
 def load(
     fp: IO[bytes],
@@ -71,6 +71,11 @@ class Config:
         """是否序列化 None 值."""
         return bool(self.flags & Option.SERIALIZE_NONE)
 
+    @property
+    def include_none(self) -> bool:
+        """是否在遇到值为 None 的字段时报错 (而不是跳过)."""
+        return bool(self.flags & Option.INCLUDE_NONE)
+
     @property
     def zero_copy(self) -> bool:
         """是否使用零复制模式."""
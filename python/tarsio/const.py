@@ -3,18 +3,49 @@
 该模块定义了Tarsio协议中使用的类型ID和其他常量.
 """
 
+from enum import IntEnum
+
+from ._core import JCE_TYPES
+
+_CODES = dict(JCE_TYPES)
+
+
+class JceType(IntEnum):
+    """JCE 线上类型码, 即字段头部低 4 位的取值.
+
+    取值由 Rust 核心的类型定义导出, 与编解码器保持一致.
+    字段头部的高 4 位为 Tag; Tag 大于等于 15 时高 4 位固定为 15,
+    实际的 Tag 写在紧随其后的 1 个字节中 (扩展 Tag).
+    """
+
+    INT1 = _CODES["INT1"]
+    INT2 = _CODES["INT2"]
+    INT4 = _CODES["INT4"]
+    INT8 = _CODES["INT8"]
+    FLOAT = _CODES["FLOAT"]
+    DOUBLE = _CODES["DOUBLE"]
+    STRING1 = _CODES["STRING1"]
+    STRING4 = _CODES["STRING4"]
+    MAP = _CODES["MAP"]
+    LIST = _CODES["LIST"]
+    STRUCT_BEGIN = _CODES["STRUCT_BEGIN"]
+    STRUCT_END = _CODES["STRUCT_END"]
+    ZERO_TAG = _CODES["ZERO_TAG"]
+    SIMPLE_LIST = _CODES["SIMPLE_LIST"]
+
+
 # JCE数据类型
-INT1 = 0
-INT2 = 1
-INT4 = 2
-INT8 = 3
-FLOAT = 4
-DOUBLE = 5
-STRING1 = 6
-STRING4 = 7
-MAP = 8
-LIST = 9
-STRUCT_BEGIN = 10
-STRUCT_END = 11
-ZERO_TAG = 12
-SIMPLE_LIST = 13
+INT1 = JceType.INT1
+INT2 = JceType.INT2
+INT4 = JceType.INT4
+INT8 = JceType.INT8
+FLOAT = JceType.FLOAT
+DOUBLE = JceType.DOUBLE
+STRING1 = JceType.STRING1
+STRING4 = JceType.STRING4
+MAP = JceType.MAP
+LIST = JceType.LIST
+STRUCT_BEGIN = JceType.STRUCT_BEGIN
+STRUCT_END = JceType.STRUCT_END
+ZERO_TAG = JceType.ZERO_TAG
+SIMPLE_LIST = JceType.SIMPLE_LIST
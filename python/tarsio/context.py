@@ -54,3 +54,26 @@ def field_serializer(field_name: str):
         return func
 
     return decorator
+
+
+def field_deserializer(field_name: str):
+    """装饰器: 注册字段的自定义 Tarsio 反序列化方法.
+
+    被装饰的函数会自动转换为 classmethod, 在解码得到原始值之后、Pydantic 验证之前调用.
+
+    Args:
+        field_name: 要自定义反序列化的字段名称.
+
+    Usage:
+        ```python
+        @field_deserializer("password")
+        def deserialize_password(cls, raw: Any, info: SerializationInfo) -> Any:
+            return decrypt(raw)
+        ```
+    """
+
+    def decorator(func: Callable[..., Any]) -> Any:
+        cast(Any, func).__tars_deserializer_target__ = field_name
+        return classmethod(func)
+
+    return decorator
@@ -63,6 +63,70 @@ class PartialDataError(DecodeError):
     pass
 
 
+class JceFrameDecodeError(DecodeError):
+    """分帧正确但数据体解码失败时抛出 (`LengthPrefixedReader(skip_bad_frames=True)`).
+
+    该数据包已从缓冲区移除, 可以记录错误后继续读取后续数据包.
+    原始解码异常保存在 `__cause__` 中.
+    """
+
+    def __init__(
+        self,
+        msg: str,
+        frame: bytes,
+        loc: list[str | int] | None = None,
+    ) -> None:
+        """初始化数据包解码错误.
+
+        Args:
+            msg: 错误描述信息.
+            frame: 解码失败的数据体 (不含长度前缀).
+            loc: 错误发生的位置路径 (Tag ID 或 索引).
+        """
+        super().__init__(msg, loc)
+        self.frame = frame
+
+
+class FrameError(DecodeError, ValueError):
+    """长度前缀分帧失败时抛出 (`JceFramer`, `LengthPrefixedReader`).
+
+    长度头本身非法, 无法确定数据包边界, 后续数据无法继续读取.
+    同时是 `ValueError` 的子类.
+    """
+
+    def __init__(
+        self,
+        msg: str,
+        length: int,
+        limit: int,
+        index: int | None = None,
+    ) -> None:
+        """初始化分帧错误.
+
+        Args:
+            msg: 错误描述信息.
+            length: 长度头声明的数据包总长度 (含长度头).
+            limit: 违反的限制 (最大数据包大小或长度头字节数).
+            index: 出错数据包的序号 (从 0 开始), 仅 `split_frames` 提供.
+        """
+        super().__init__(msg)
+        self.length = length
+        self.limit = limit
+        self.index = index
+
+
+class FrameTooLarge(FrameError):
+    """数据包长度超过 `max_buffer_size` 时抛出."""
+
+    pass
+
+
+class InvalidLength(FrameError):
+    """包含长度头的长度值小于长度头本身时抛出."""
+
+    pass
+
+
 class TarsTypeError(EncodeError, TypeError):
     """类型不匹配时抛出."""
 
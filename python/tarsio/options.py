@@ -33,3 +33,86 @@ class Option(IntFlag):
 
     # 排除未设置的字段 (仅 Struct)
     EXCLUDE_UNSET = 0x0040
+
+    # 通用解码时先在释放 GIL 的情况下校验数据结构 (适用于多线程服务)
+    RELEASE_GIL = 0x0080
+
+    # 编码时拒绝 NaN/Infinity 浮点数, 解码时将其转换为 None
+    REJECT_NONFINITE = 0x0100
+
+    # 解码 Struct 时不为缺失字段回填默认值, 结果仅包含数据中实际存在的字段
+    NO_DEFAULT_FILL = 0x0200
+
+    # 解码时未知的枚举值保留为原始整数, 而不是抛出异常
+    ENUM_PASSTHROUGH = 0x0400
+
+    # 通用解码时同一层出现重复 Tag 则抛出异常 (默认后者覆盖前者)
+    DUP_TAG_ERROR = 0x0800
+
+    # 通用解码时将同一层重复出现的 Tag 收集为列表
+    DUP_TAG_LIST = 0x1000
+
+    # 通用解码时将结构体返回为按线上顺序排列的 (tag, value) 列表 (保留重复 Tag);
+    # 通用编码时按给定顺序写入此类列表, 不再按 Tag 排序
+    ORDERED_PAIRS = 0x2000
+
+    # 编码 Map 时按键的编码字节排序 (递归作用于嵌套 Map), 使相等的字典得到相同的输出
+    SORT_MAP_KEYS = 0x4000
+
+    # 解码时通过线程局部的有界缓存复用短字符串对象, 减少重复字符串的内存占用
+    INTERN_STRINGS = 0x8000
+
+    # 通用解码时将 List 返回为不可变的 tuple (Map 仍为 dict)
+    LIST_AS_TUPLE = 0x10000
+
+    # 通用解码时将 Int1/Int2/Int4/Int8 还原为 Fixed8/Fixed16/Fixed32/Fixed64,
+    # 重新编码时保持原宽度 (ZeroTag 仍为 int 0)
+    KEEP_INT_WIDTH = 0x20000
+
+    # Schema 解码时允许 Float 字段读取线上的 Double 并截断为单精度
+    WIDEN_FLOATS = 0x40000
+
+    # 通用解码时将字符串返回为指向输入缓冲区的 JceStr 视图, 不拷贝内容
+    STR_VIEW = 0x80000
+
+    # Schema 中的 Tag 未按升序声明时, 编码仍按 Tag 升序写出字段
+    SORT_TAGS = 0x100000
+
+    # 将顶层结构体直接解码为目标类的实例 (不调用 __init__, 逐个 setattr),
+    # 适用于提供 Schema 方法的 __slots__ 类; Struct 目标忽略此选项, 仍经过 Pydantic 验证
+    SLOTS = 0x200000
+
+    # SimpleList (bytes) 的元素个数固定以 Int4 写入, 而非最小宽度,
+    # 用于兼容按固定宽度读取长度的旧版实现
+    FIXED_SIMPLE_LIST_LEN = 0x400000
+
+    # datetime/date 的 Unix 时间戳使用毫秒 (默认为秒), 作用于通用编码与未指定单位的字段
+    DATETIME_MILLIS = 0x800000
+
+    # 将无时区的 datetime 视为 UTC 编码 (默认抛出 ValueError)
+    NAIVE_DATETIME_UTC = 0x1000000
+
+    # 解码 Struct 时不回填默认值为 None 的缺失字段 (含使用 default_factory 的字段),
+    # 由模型自身的默认值与验证器处理
+    OMIT_NONE_DEFAULTS = 0x2000000
+
+    # 解码声明了元素类型的 List 字段时, 元素的线上类型必须与声明兼容,
+    # 否则抛出 DecodeError (而不是回退到通用解码)
+    STRICT_LIST = 0x4000000
+
+    # 编码 Struct 与以 Tag 为键的通用结构体时省略空字符串, 空 bytes 与空容器字段,
+    # 与声明的默认值无关
+    OMIT_EMPTY = 0x8000000
+
+    # 解码时顶层出现没有配对 StructBegin 的 StructEnd 视为数据损坏并抛出 DecodeError,
+    # 默认在此处静默停止解码
+    STRICT_STRUCT_END = 0x10000000
+
+    # 解码前先对整个数据包做零分配的结构扫描, 结构非法时在创建任何 Python 对象之前报错
+    VALIDATE_FIRST = 0x20000000
+
+    # 编码声明为 bytes (SimpleList) 的字段时拒绝 str 值, 而不是按 UTF-8 写入
+    STRICT_BYTES = 0x40000000
+
+    # 不再自动跳过值为 None 的字段: JCE 没有空值, 遇到 None 时抛出异常而不是静默丢弃
+    INCLUDE_NONE = 0x80000000
@@ -4,7 +4,8 @@
 支持增量编码和解码.
 """
 
-from typing import Any
+from collections.abc import Awaitable, Callable, Iterator, Sequence
+from typing import IO, Any
 
 from ._core import (
     LengthPrefixedReader as _RustLengthPrefixedReader,
@@ -12,6 +13,8 @@ from ._core import (
 from ._core import (
     LengthPrefixedWriter as _RustLengthPrefixedWriter,
 )
+from ._core import split_frames as _split_frames
+from .exceptions import PartialDataError
 from .options import Option
 
 
@@ -34,6 +37,9 @@ class LengthPrefixedWriter(_RustLengthPrefixedWriter):
             - True: TotalSize (Header + Body)
             - False: BodySize
         little_endian_length: 长度字段是否使用小端序.
+        max_depth: 最大嵌套深度, 默认为 100.
+        string1_max: 使用 String1 的最大字符串字节长度 (0-255), 超出时使用 String4.
+        force_string4: 是否所有字符串都使用 String4, 优先于 `string1_max`.
     """
 
     def __new__(
@@ -43,6 +49,9 @@ class LengthPrefixedWriter(_RustLengthPrefixedWriter):
         length_type: int = 4,
         inclusive_length: bool = True,
         little_endian_length: bool = False,
+        max_depth: int | None = None,
+        string1_max: int = 255,
+        force_string4: bool = False,
     ):
         """创建 LengthPrefixedWriter 实例."""
         return super().__new__(  # type: ignore
@@ -52,6 +61,9 @@ class LengthPrefixedWriter(_RustLengthPrefixedWriter):
             little_endian_length=little_endian_length,
             options=int(option),
             context=context if context is not None else {},
+            max_depth=max_depth,
+            string1_max=string1_max,
+            force_string4=force_string4,
         )
 
     def __init__(
@@ -61,6 +73,9 @@ class LengthPrefixedWriter(_RustLengthPrefixedWriter):
         length_type: int = 4,  # 1, 2, 或 4 字节
         inclusive_length: bool = True,  # 长度包含头部本身
         little_endian_length: bool = False,  # 长度字段字节序
+        max_depth: int | None = None,
+        string1_max: int = 255,
+        force_string4: bool = False,
     ):
         """初始化带长度前缀的写入器.
 
@@ -70,6 +85,9 @@ class LengthPrefixedWriter(_RustLengthPrefixedWriter):
             length_type: 长度字段的字节数 (1, 2, 或 4).
             inclusive_length: 长度值是否包含头部本身的长度.
             little_endian_length: 长度字段是否使用小端序.
+            max_depth: 最大嵌套深度.
+            string1_max: 使用 String1 的最大字符串字节长度.
+            force_string4: 是否所有字符串都使用 String4.
         """
         pass
 
@@ -124,12 +142,23 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
             - False: BodySize
         little_endian_length: 长度字段是否使用小端序.
         bytes_mode: 字节数据处理模式 ('raw', 'string', 'auto').
+        skip_bad_frames: 数据体解码失败时抛出携带原始数据的 `JceFrameDecodeError`,
+            该数据包已被移除, 捕获异常后可以继续读取后续数据包.
+        max_fields: 通用解码时单个结构体允许的最大字段数, 默认不限制.
+        max_total_elements: 通用解码时每个数据包中结构体字段, List 元素与
+            Map 键值对的最大总数, 默认不限制. 用于防止恶意数据包耗尽内存.
+        allowed_control_bytes: `'auto'` 模式下文本中允许出现的 ASCII 控制字符,
+            默认为 `b"\t\n\r"`.
+        verify_length_tag: 数据体内声明总长度的字段 Tag, 指定时校验其值与
+            数据体实际解码的字节数一致, 不符时抛出 `DecodeError`.
+        max_depth: 最大嵌套深度, 默认为 100.
     """
 
     _target: Any
     _context: dict[str, Any] | None
     _option: Option
     _bytes_mode: str
+    _source: Callable[[], Awaitable[bytes]] | None
 
     def __new__(
         cls,
@@ -141,6 +170,12 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
         inclusive_length: bool = True,
         little_endian_length: bool = False,
         bytes_mode: str = "auto",
+        skip_bad_frames: bool = False,
+        max_fields: int | None = None,
+        max_total_elements: int | None = None,
+        allowed_control_bytes: bytes | None = None,
+        verify_length_tag: int | None = None,
+        max_depth: int | None = None,
     ):
         """创建 LengthPrefixedReader 实例."""
         # 映射 BytesMode 字符串为 Rust 需要的整数
@@ -162,6 +197,12 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
             inclusive_length=inclusive_length,
             little_endian_length=little_endian_length,
             bytes_mode=mode_int,
+            skip_bad_frames=skip_bad_frames,
+            max_fields=max_fields,
+            max_total_elements=max_total_elements,
+            allowed_control_bytes=allowed_control_bytes,
+            verify_length_tag=verify_length_tag,
+            max_depth=max_depth,
         )
 
     def __init__(
@@ -174,6 +215,12 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
         inclusive_length: bool = True,
         little_endian_length: bool = False,
         bytes_mode: str = "auto",
+        skip_bad_frames: bool = False,
+        max_fields: int | None = None,
+        max_total_elements: int | None = None,
+        allowed_control_bytes: bytes | None = None,
+        verify_length_tag: int | None = None,
+        max_depth: int | None = None,
     ):
         """初始化带长度前缀的读取器.
 
@@ -186,10 +233,16 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
             inclusive_length: 长度是否包含头部.
             little_endian_length: 长度字段是否小端序.
             bytes_mode: 字节处理模式.
+            skip_bad_frames: 解码失败时是否抛出可恢复的 `JceFrameDecodeError`.
+            max_fields: 单个结构体允许的最大字段数.
+            max_total_elements: 每个数据包的最大元素总数.
+            allowed_control_bytes: 文本中允许出现的控制字符.
+            verify_length_tag: 声明数据体总长度的字段 Tag.
+            max_depth: 最大嵌套深度, 默认为 100.
         """
         # 注意：基类初始化已在 __new__ 中由 Rust 核心完成
         # Rust 侧已经负责了验证逻辑，Python 侧不再需要 _target 等状态
-        pass
+        self._source = None
 
     def __next__(self) -> Any:
         """获取下一个解析出的对象.
@@ -199,7 +252,32 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
         """
         return super().__next__()
 
-    def feed(self, data: bytes) -> None:
+    def next_raw(self) -> bytes | None:
+        """获取下一个完整数据包的原始数据体, 不进行解码.
+
+        数据包同样会从缓冲区中移除. 适用于只检查路由字段 (如配合 `get_field`)
+        并原样转发其余数据的代理场景.
+
+        Returns:
+            bytes | None: 去除长度前缀后的数据体, 数据不足时返回 None.
+
+        Raises:
+            ValueError: 如果数据包格式错误.
+        """
+        return super().next_raw()
+
+    def peek_frame_length(self) -> int | None:
+        """预览下一个完整数据包的总长度, 不取出任何数据.
+
+        Returns:
+            int | None: 数据包的总字节数 (含长度前缀), 数据不足时返回 None.
+
+        Raises:
+            ValueError: 如果数据包格式错误 (`FrameTooLarge` 或 `InvalidLength`).
+        """
+        return super().peek_frame_length()
+
+    def feed(self, data: bytes | bytearray | memoryview) -> None:
         """将数据追加到内部缓冲区.
 
         Args:
@@ -210,6 +288,23 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
         """
         super().feed(data)
 
+    def feed_many(self, chunks: Sequence[bytes | bytearray | memoryview]) -> None:
+        """一次追加多个数据块.
+
+        按总大小一次性检查 `max_buffer_size` 并预留空间, 超出限制时不追加任何数据.
+
+        Args:
+            chunks: 要追加的数据块.
+
+        Raises:
+            BufferError: 如果缓冲区超过 max_buffer_size.
+        """
+        super().feed_many(chunks)
+
+    def compact(self) -> None:
+        """压缩缓冲区, 释放已取出数据包占用的空间."""
+        super().compact()
+
     def __iter__(self) -> "LengthPrefixedReader":
         """获取迭代器.
 
@@ -217,3 +312,147 @@ class LengthPrefixedReader(_RustLengthPrefixedReader):
             LengthPrefixedReader: 迭代器本身.
         """
         return self
+
+    def set_source(self, source: Callable[[], Awaitable[bytes]] | None) -> None:
+        """设置异步迭代使用的数据源.
+
+        Args:
+            source: 无参的异步函数, 每次调用返回新到达的数据, 返回空字节表示数据源结束.
+                例如 `lambda: stream.read(4096)` (`asyncio.StreamReader`).
+                传入 None 取消数据源.
+        """
+        self._source = source
+
+    def __aiter__(self) -> "LengthPrefixedReader":
+        """获取异步迭代器.
+
+        Returns:
+            LengthPrefixedReader: 迭代器本身.
+        """
+        return self
+
+    async def __anext__(self) -> Any:
+        """异步获取下一个解析出的对象.
+
+        缓冲区中没有完整数据包时, 等待数据源 (`set_source`) 提供更多数据后重试.
+        未设置数据源时行为与同步迭代相同, 数据不足即结束迭代.
+
+        Returns:
+            Any: 解析出的对象实例.
+
+        Raises:
+            StopAsyncIteration: 数据不足且没有数据源, 或数据源已结束.
+        """
+        while True:
+            try:
+                return super().__next__()
+            except StopIteration:
+                pass
+            if self._source is None:
+                raise StopAsyncIteration
+            data = await self._source()
+            if not data:
+                raise StopAsyncIteration
+            self.feed(data)
+
+
+def load_stream(
+    fp: IO[bytes],
+    target: Any,
+    option: Option = Option.NONE,
+    *,
+    context: dict[str, Any] | None = None,
+    length_type: int = 4,
+    inclusive_length: bool = True,
+    little_endian_length: bool = False,
+    bytes_mode: str = "auto",
+    chunk_size: int = 64 * 1024,
+    max_buffer_size: int = 10 * 1024 * 1024,
+) -> Iterator[Any]:
+    """从文件对象中逐个读取带长度前缀的数据包并反序列化.
+
+    通过 `fp.read(chunk_size)` 主动拉取数据, 读取不足一个数据包时继续读取,
+    仅产出完整的数据包. 适合读取由多个数据包拼接而成的抓包文件.
+
+    Args:
+        fp: 打开的二进制文件对象 (需支持 `read(n)`).
+        target: 目标类型, 同 `LengthPrefixedReader`.
+        option: JCE 选项.
+        context: 反序列化上下文.
+        length_type: 长度字段的字节数 (1, 2, 或 4).
+        inclusive_length: 长度值是否包含头部本身的长度.
+        little_endian_length: 长度字段是否使用小端序.
+        bytes_mode: 字节数据处理模式 ('raw', 'string', 'auto').
+        chunk_size: 每次调用 `read` 读取的字节数.
+        max_buffer_size: 内部缓冲区的最大大小.
+
+    Yields:
+        Any: 解析出的对象实例.
+
+    Raises:
+        PartialDataError: 数据在数据包中途结束.
+        ValueError: 数据包格式错误.
+
+    Examples:
+        >>> with open("capture.bin", "rb") as f:
+        ...     for packet in load_stream(f, User):
+        ...         print(packet)
+    """
+    reader = LengthPrefixedReader(
+        target,
+        option,
+        max_buffer_size=max_buffer_size,
+        context=context,
+        length_type=length_type,
+        inclusive_length=inclusive_length,
+        little_endian_length=little_endian_length,
+        bytes_mode=bytes_mode,
+    )
+    while chunk := fp.read(chunk_size):
+        reader.feed(bytes(chunk))
+        yield from reader
+    if reader.pending:
+        raise PartialDataError(
+            f"Stream ended with {reader.pending} bytes of incomplete frame"
+        )
+
+
+def split_frames(
+    buffer: bytes | bytearray | memoryview,
+    length_type: int = 4,
+    inclusive_length: bool = True,
+    little_endian_length: bool = False,
+    max_buffer_size: int = 10 * 1024 * 1024,
+) -> tuple[list[bytes], int]:
+    """将多个带长度前缀的数据包拼接而成的数据切分为独立的数据体.
+
+    分帧规则与 `LengthPrefixedReader` 一致, 只识别数据包边界而不解码数据体.
+    返回的数据体不含长度前缀, 与 `LengthPrefixedReader.next_raw()` 的结果相同.
+
+    Args:
+        buffer: 拼接的数据.
+        length_type: 长度字段的字节数 (1, 2, 或 4).
+        inclusive_length: 长度值是否包含头部本身的长度.
+        little_endian_length: 长度字段是否使用小端序.
+        max_buffer_size: 允许的最大数据包大小 (含长度头).
+
+    Returns:
+        tuple[list[bytes], int]: 完整数据包的数据体列表,
+            以及末尾不完整数据包的剩余字节数 (数据完整时为 0).
+
+    Raises:
+        FrameTooLarge: 某个数据包超过 `max_buffer_size`.
+        InvalidLength: 某个数据包的长度值小于长度头本身.
+            两者的 `index` 属性为出错数据包的序号 (从 0 开始).
+
+    Examples:
+        >>> split_frames(b"\x00\x00\x00\x06\x00\x01\x00\x00")
+        ([b'\x00\x01'], 2)
+    """
+    return _split_frames(
+        buffer,
+        length_type,
+        inclusive_length,
+        little_endian_length,
+        max_buffer_size,
+    )
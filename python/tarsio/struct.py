@@ -1,8 +1,12 @@
 """JCE 结构体定义模块."""
 
+import enum
 import re
 import types as stdlib_types
 from collections.abc import Callable
+from datetime import date, datetime
+from decimal import Decimal
+from ipaddress import IPv4Address, IPv6Address
 from typing import (
     Any,
     ClassVar,
@@ -21,6 +25,7 @@ from pydantic_core import PydanticUndefined, core_schema
 from typing_extensions import Self, dataclass_transform
 
 from . import types
+from .context import SerializationInfo
 from .options import Option
 
 S = TypeVar("S", bound="Struct")
@@ -69,6 +74,12 @@ def Field(
     *,
     id: int,
     tars_type: type[types.Type] | None = None,
+    codec: str | None = None,
+    decimal_mode: Literal["string", "scaled_int"] | None = None,
+    decimal_scale: int | None = None,
+    datetime_unit: Literal["s", "ms"] | None = None,
+    endian: Literal["big", "little"] | None = None,
+    tars_alias: str | None = None,
     default_factory: Callable[[], Any] | Callable[[dict[str, Any]], Any] | None = None,
     alias: str | None = None,
     alias_priority: int | None = None,
@@ -119,6 +130,20 @@ def Field(
         tars_type: [可选] 显式指定 JCE 类型，用于覆盖默认的类型推断。
             *   指定 `types.INT1` 可强制将 int 编码为单字节。
             *   指定 `types.BYTES` 可强制将复杂对象（如 Struct/StructDict）**先序列化为二进制**再作为 SimpleList 存储 (Binary Blob 模式)。
+        codec: [可选] 将 `str` 字段以指定编码 (如 `"gbk"`) 存储为 SimpleList。
+            解码时直接按该编码还原为 `str`，不经过 `bytes_mode` 的自动探测。
+        decimal_mode: [可选] `Decimal` 字段的存储方式。
+            *   `"string"` (默认): 以十进制字符串存储，无损。
+            *   `"scaled_int"`: 乘以 `10 ** decimal_scale` 后以整数存储，
+                小数位数超过 `decimal_scale` 时编码失败。
+        decimal_scale: [可选] `"scaled_int"` 模式下的小数位数。
+        datetime_unit: [可选] `datetime`/`date` 字段的时间戳单位 (`"s"` 或 `"ms"`)。
+            未指定时按 `Option.DATETIME_MILLIS` 选择，默认为秒。
+        endian: [可选] 嵌套 `Struct` 字段的字节序 (`"big"` 或 `"little"`)。
+            用于外层与嵌套结构体字节序不同的混合协议，未指定时与外层一致。
+        tars_alias: [可选] 字段的旧名称或协议原始名称 (如 `"stFoo"`)。
+            以字段名为键的字典在编码、`normalize_struct` 与模型验证时可用它代替字段名，
+            解码结果始终使用字段名。与 Pydantic 的 `alias` 无关。
         default_factory: 用于生成默认值的无参可调用对象。
             对于可变类型（如 `list`, `dict`），**必须**使用此参数而不是 `default`。
         alias: 字段别名 (Pydantic).
@@ -162,6 +187,7 @@ def Field(
 
     Raises:
         ValueError: 如果 `id` 小于 0。
+        TypeError: 如果 `decimal_mode` 与 `decimal_scale` 不匹配。
 
     Examples:
         >>> from tarsio import Struct, Field, types
@@ -184,12 +210,29 @@ def Field(
     """
     if id < 0:
         raise ValueError(f"Invalid JCE ID: {id}")
+    if decimal_mode == "scaled_int":
+        if decimal_scale is None or decimal_scale < 0:
+            raise TypeError("decimal_mode='scaled_int' requires decimal_scale >= 0")
+    elif decimal_scale is not None:
+        raise TypeError("decimal_scale requires decimal_mode='scaled_int'")
 
     # 构造 JCE 元数据
     final_extra = {
         "id": id,
         "tars_type": tars_type,
     }
+    if codec is not None:
+        final_extra["codec"] = codec
+    if datetime_unit is not None:
+        final_extra["datetime_unit"] = datetime_unit
+    if endian is not None:
+        final_extra["endian"] = endian
+    if tars_alias is not None:
+        final_extra["tars_alias"] = tars_alias
+    if decimal_mode is not None:
+        final_extra["decimal"] = (
+            decimal_scale if decimal_mode == "scaled_int" else decimal_mode
+        )
 
     # 合并显式传入的 json_schema_extra
     if json_schema_extra is not None:
@@ -260,11 +303,51 @@ class ModelField:
     存储了解析后的 JCE ID 和 JCE 类型信息。
     """
 
-    __slots__ = ("id", "tars_type")
-
-    def __init__(self, id: int, tars_type: type[types.Type] | Any):
+    __slots__ = (
+        "alias",
+        "codec",
+        "datetime",
+        "decimal",
+        "elem",
+        "endian",
+        "enum_type",
+        "id",
+        "ip",
+        "key",
+        "struct_type",
+        "tars_type",
+        "value",
+    )
+
+    def __init__(
+        self,
+        id: int,
+        tars_type: type[types.Type] | Any,
+        codec: str | None = None,
+        elem: type[types.Type] | None = None,
+        key: type[types.Type] | None = None,
+        value: type[types.Type] | None = None,
+        struct_type: type["Struct"] | None = None,
+        enum_type: type[enum.Enum] | None = None,
+        decimal: str | int | None = None,
+        datetime: tuple[str, str | None] | None = None,
+        ip: int | None = None,
+        endian: str | None = None,
+        alias: str | None = None,
+    ):
         self.id = id
         self.tars_type = tars_type
+        self.codec = codec
+        self.elem = elem
+        self.key = key
+        self.value = value
+        self.struct_type = struct_type
+        self.enum_type = enum_type
+        self.decimal = decimal
+        self.datetime = datetime
+        self.ip = ip
+        self.endian = endian
+        self.alias = alias
 
     @classmethod
     def from_field_info(cls, field_info: FieldInfo, annotation: Any) -> Self:
@@ -278,9 +361,37 @@ class ModelField:
             type[types.Type] | None, extra.get("tars_type")
         )
 
+        codec: str | None = cast(str | None, extra.get("codec"))
+        decimal = cls._declared_decimal(
+            annotation, cast(str | int | None, extra.get("decimal"))
+        )
+        datetime_spec = cls._declared_datetime(
+            annotation, cast(str | None, extra.get("datetime_unit"))
+        )
+        ip = cls._declared_ip(annotation)
+        endian: str | None = cast(str | None, extra.get("endian"))
+        alias: str | None = cast(str | None, extra.get("tars_alias"))
+
         if id is None:
             raise ValueError("id is missing")
 
+        # Decimal 字段按存储方式使用字符串或整数
+        if decimal is not None and tars_type is None:
+            tars_type = types.STRING if decimal == "string" else types.INT
+        # datetime/date 字段按 Int8 时间戳存储
+        if datetime_spec is not None and tars_type is None:
+            tars_type = types.INT64
+        # IP 地址字段: IPv4 按 Int4 存储, IPv6 按 16 字节 SimpleList 存储
+        if ip is not None and tars_type is None:
+            tars_type = types.INT32 if ip == 4 else types.BYTES
+
+        # 指定了 codec 的字段固定以 SimpleList 存储
+        if codec is not None:
+            if tars_type is None:
+                tars_type = types.BYTES
+            elif tars_type is not types.BYTES:
+                raise TypeError(f"codec requires BYTES tars_type, got {tars_type}")
+
         # 如果未显式指定 tars_type，则尝试推断
         if tars_type is None:
             tars_type = cls._infer_tars_type_from_annotation(annotation)
@@ -298,7 +409,170 @@ class ModelField:
             if not (isinstance(tars_type, type) and issubclass(tars_type, types.Type)):
                 raise TypeError(f"Invalid tars_type: {tars_type}")
 
-        return cls(cast(int, id), tars_type)
+        # 容器字段记录元素/键值类型, 以便按其 Schema 编解码
+        elem = key = value = None
+        if tars_type is types.LIST:
+            (elem,) = cls._infer_arg_types_from_annotation(annotation, list, 1)
+        elif tars_type is types.MAP:
+            key, value = cls._infer_arg_types_from_annotation(annotation, dict, 2)
+
+        struct_type = cls._declared_struct_type(tars_type, annotation)
+        enum_type = cls._declared_enum_type(tars_type, annotation)
+        if endian is not None and (struct_type is None or tars_type is types.BYTES):
+            raise TypeError(f"endian requires a Struct field, got {annotation}")
+
+        return cls(
+            cast(int, id),
+            tars_type,
+            codec,
+            elem,
+            key,
+            value,
+            struct_type,
+            enum_type,
+            decimal,
+            datetime_spec,
+            ip,
+            endian,
+            alias,
+        )
+
+    @staticmethod
+    def _declared_datetime(
+        annotation: Any, unit: str | None
+    ) -> tuple[str, str | None] | None:
+        """获取 `datetime`/`date` 字段的 `(kind, unit)`.
+
+        为其他类型的字段指定 `datetime_unit` 时抛出 TypeError.
+        """
+        origin = get_origin(annotation)
+        if origin is Union or origin is stdlib_types.UnionType:
+            non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+            if len(non_none_args) == 1:
+                annotation = non_none_args[0]
+        if isinstance(annotation, type):
+            # datetime 是 date 的子类, 需先判断
+            if issubclass(annotation, datetime):
+                return ("datetime", unit)
+            if issubclass(annotation, date):
+                return ("date", unit)
+        if unit is not None:
+            raise TypeError(
+                f"datetime_unit requires a datetime or date field, got {annotation}"
+            )
+        return None
+
+    @staticmethod
+    def _declared_ip(annotation: Any) -> int | None:
+        """获取 IP 地址字段的版本 (4 或 6), 非 IP 地址字段返回 None."""
+        origin = get_origin(annotation)
+        if origin is Union or origin is stdlib_types.UnionType:
+            non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+            if len(non_none_args) == 1:
+                annotation = non_none_args[0]
+        if isinstance(annotation, type):
+            if issubclass(annotation, IPv4Address):
+                return 4
+            if issubclass(annotation, IPv6Address):
+                return 6
+        return None
+
+    @staticmethod
+    def _declared_decimal(annotation: Any, mode: str | int | None) -> str | int | None:
+        """获取 `Decimal` 字段的存储方式 (`"string"` 或整数 scale).
+
+        注解为 `Decimal` (含 `Decimal | None`) 的字段默认以字符串存储;
+        为其他类型的字段指定 `decimal_mode` 时抛出 TypeError.
+        """
+        origin = get_origin(annotation)
+        if origin is Union or origin is stdlib_types.UnionType:
+            non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+            if len(non_none_args) == 1:
+                annotation = non_none_args[0]
+        if isinstance(annotation, type) and issubclass(annotation, Decimal):
+            return "string" if mode is None else mode
+        if mode is not None:
+            raise TypeError(f"decimal_mode requires a Decimal field, got {annotation}")
+        return None
+
+    @staticmethod
+    def _declared_enum_type(tars_type: Any, annotation: Any) -> type[enum.Enum] | None:
+        """获取整数字段声明的枚举类型.
+
+        core 据此在解码时将整数转换为枚举成员, 编码时写入成员的 `.value`.
+        """
+        if tars_type is not types.INT:
+            return None
+        origin = get_origin(annotation)
+        if origin is Union or origin is stdlib_types.UnionType:
+            non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+            if len(non_none_args) != 1:
+                return ModelField._enum_in_union(non_none_args)
+            annotation = non_none_args[0]
+        if isinstance(annotation, type) and issubclass(annotation, enum.Enum):
+            return annotation
+        return None
+
+    @staticmethod
+    def _enum_in_union(args: list[Any]) -> type[enum.Enum] | None:
+        """识别 `Enum | int` 形式的 Union, 返回其中的枚举类型."""
+        enums = [a for a in args if isinstance(a, type) and issubclass(a, enum.Enum)]
+        if len(enums) == 1 and len(args) == 2 and int in args:
+            if all(isinstance(m.value, int) for m in enums[0]):
+                return enums[0]
+        return None
+
+    @staticmethod
+    def _declared_struct_type(tars_type: Any, annotation: Any) -> type["Struct"] | None:
+        """获取 Struct 字段或二进制块字段声明的具体结构体类型.
+
+        core 据此预编译嵌套 Schema, 编码时无需对每个值查询 `__get_core_schema__`.
+        """
+        if tars_type is types.BYTES:
+            origin = get_origin(annotation)
+            if origin is Union or origin is stdlib_types.UnionType:
+                non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+                if len(non_none_args) != 1:
+                    return None
+                annotation = non_none_args[0]
+            tars_type = annotation
+        if (
+            isinstance(tars_type, type)
+            and issubclass(tars_type, Struct)
+            and tars_type is not Struct
+        ):
+            return tars_type
+        return None
+
+    @staticmethod
+    def _infer_arg_types_from_annotation(
+        annotation: Any,
+        container: type,
+        count: int,
+    ) -> tuple[type[types.Type] | None, ...]:
+        """推断 `list[X]` / `dict[K, V]` 注解中类型参数的 JCE 类型.
+
+        无法确定 (未参数化, Any, TypeVar 或不支持的类型) 的参数返回 None,
+        此时对应的元素按运行时推断编解码.
+        """
+        unknown = (None,) * count
+        origin = get_origin(annotation)
+        if origin is Union or origin is stdlib_types.UnionType:
+            non_none_args = [a for a in get_args(annotation) if a is not type(None)]
+            if len(non_none_args) != 1:
+                return unknown
+            annotation = non_none_args[0]
+            origin = get_origin(annotation)
+
+        args = get_args(annotation)
+        if origin is not container or len(args) != count:
+            return unknown
+        return tuple(
+            None
+            if isinstance(arg, TypeVar)
+            else ModelField._infer_tars_type_from_annotation(arg)
+            for arg in args
+        )
 
     @staticmethod
     def _infer_tars_type_from_annotation(
@@ -324,14 +598,24 @@ class ModelField:
             non_none_args = [a for a in args if a is not type(None)]
             if len(non_none_args) == 1:
                 return ModelField._infer_tars_type_from_annotation(non_none_args[0])
+            if ModelField._enum_in_union(non_none_args) is not None:
+                return types.INT  # `Enum | int`: 允许保留未知的枚举值
             return None  # 多重 Union 不支持
 
         # 4. 检查具体类映射
         is_class = isinstance(annotation, type)
 
         if is_class:
+            # 整数值枚举按整数存储
+            if issubclass(annotation, enum.Enum):
+                if all(isinstance(m.value, int) for m in annotation):
+                    return types.INT
+                return None
+
             # 基础类型映射
-            if issubclass(annotation, (bool, int)):
+            if issubclass(annotation, bool):
+                return types.BOOL
+            if issubclass(annotation, int):
                 return types.INT
             if issubclass(annotation, float):
                 return types.DOUBLE
@@ -401,9 +685,16 @@ class StructMeta(type(BaseModel)):
             cls.__tars_tag_map__ = {
                 f.id: field_name for field_name, f in cls.__tars_fields__.items()
             }
+            # 别名到字段名的映射
+            cls.__tars_alias_map__ = {
+                f.alias: field_name
+                for field_name, f in cls.__tars_fields__.items()
+                if f.alias is not None
+            }
 
             # 收集自定义序列化器/反序列化器
             cls.__tars_serializers__ = {}
+            cls.__tars_deserializers__ = {}
             for attr_name, attr_value in namespace.items():
                 func = attr_value
                 if isinstance(func, classmethod | staticmethod):
@@ -412,6 +703,9 @@ class StructMeta(type(BaseModel)):
                 target = getattr(func, "__tars_serializer_target__", None)
                 if target:
                     cls.__tars_serializers__[target] = attr_name
+                target = getattr(func, "__tars_deserializer_target__", None)
+                if target:
+                    cls.__tars_deserializers__[target] = attr_name
 
         return cls
 
@@ -493,13 +787,63 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
 
     __tars_fields__: ClassVar[dict[str, "ModelField"]] = {}
     __tars_tag_map__: ClassVar[dict[int, str]] = {}
+    __tars_alias_map__: ClassVar[dict[str, str]] = {}
     __tars_serializers__: ClassVar[dict[str, str]] = {}
+    __tars_deserializers__: ClassVar[dict[str, str]] = {}
     __core_schema_cache__: ClassVar[list[tuple] | None] = None
 
     def __bytes__(self) -> bytes:
         """支持 bytes(obj) 语法."""
         return self.model_dump_tars()
 
+    def __tars_serialize_field__(
+        self, name: str, value: Any, context: dict[str, Any]
+    ) -> Any:
+        """编码钩子: 对注册了 `@field_serializer` 的字段转换待编码的值.
+
+        由 Rust 核心在编码声明了钩子的字段时调用. 返回 None 时跳过该字段,
+        指定 `Option.INCLUDE_NONE` 时则抛出 ValueError. 以 Schema 编码字典时不调用.
+
+        Args:
+            name: 字段名称.
+            value: 字段的当前值.
+            context: 传递给 `dumps` 的上下文.
+
+        Returns:
+            Any: 实际写入的值.
+        """
+        attr = self.__tars_serializers__.get(name)
+        if attr is None:
+            return value
+        info = SerializationInfo(
+            context=context, field_name=name, id=self.__tars_fields__[name].id
+        )
+        return getattr(self, attr)(value, info)
+
+    @classmethod
+    def __tars_deserialize_field__(
+        cls, name: str, raw: Any, context: dict[str, Any]
+    ) -> Any:
+        """解码钩子: 对注册了 `@field_deserializer` 的字段转换解码得到的原始值.
+
+        由 Rust 核心在解码声明了钩子的字段时调用, 结果随后交给 Pydantic 验证.
+
+        Args:
+            name: 字段名称.
+            raw: 解码得到的原始值.
+            context: 传递给 `loads` 的上下文.
+
+        Returns:
+            Any: 转换后的值.
+        """
+        attr = cls.__tars_deserializers__.get(name)
+        if attr is None:
+            return raw
+        info = SerializationInfo(
+            context=context, field_name=name, id=cls.__tars_fields__[name].id
+        )
+        return getattr(cls, attr)(raw, info)
+
     @classmethod
     def __get_core_schema__(cls) -> list[tuple]:
         """获取用于 core (Rust) 的结构体 Schema.
@@ -507,9 +851,17 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
         Returns:
             list[tuple]: Schema 列表, 每个元素为:
                 (field_name, tag_id, tars_type_code, default_value, has_serializer)
+                需要额外描述的字段会追加第 6 个元素 (dict),
+                如 `{"codec": "gbk"}`, `{"elem": User}`, `{"enum": Color}`,
+                `{"decimal": 2}`, `{"required": True}`, `{"not_none": True}`,
+                `{"deserializer": True}`
+                或 `{"key": 6, "value": User}`. has_serializer 仅表示声明了编码钩子,
+                解码钩子由额外描述中的 `deserializer` 标记.
         """
-        if cls.__core_schema_cache__ is not None:
-            return cls.__core_schema_cache__
+        # 仅使用类型自身的缓存, 子类不能复用父类的 Schema
+        cached = cls.__dict__.get("__core_schema_cache__")
+        if cached is not None:
+            return cached
 
         from . import types
 
@@ -528,6 +880,7 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
             types.MAP: 8,
             types.LIST: 9,
             types.BYTES: 13,  # SimpleList (Blob)
+            types.BOOL: 254,  # 按整数编码, 解码为 bool
         }
 
         schema = []
@@ -542,12 +895,7 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
             field_info = cls.model_fields[name]
 
             # 3. 确定类型码
-            if isinstance(tars_type_cls, type) and issubclass(tars_type_cls, Struct):
-                type_code = 10  # StructBegin
-            elif tars_type_cls is None:
-                type_code = 255  # 运行时推断 (Any)
-            else:
-                type_code = type_map.get(tars_type_cls, 0)
+            type_code = cls._type_code(tars_type_cls, type_map)
 
             # 4. 确定默认值 (用于 OMIT_DEFAULT)
             if (
@@ -558,23 +906,80 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
             else:
                 default_val = field_info.default
 
-            # 5. 检查自定义序列化器
+            # 5. 检查自定义序列化器 (反序列化器记录在额外描述中)
             has_serializer = name in cls.__tars_serializers__
 
-            # 6. 构建 Tuple
-            schema.append(
-                (
-                    name,
-                    tag,
-                    type_code,
-                    default_val,
-                    has_serializer,
-                )
-            )
+            # 6. 收集额外描述
+            extras: dict[str, Any] = {}
+            if jce_info.codec is not None:
+                extras["codec"] = jce_info.codec
+            if jce_info.struct_type is not None:
+                extras["schema"] = jce_info.struct_type
+            if jce_info.enum_type is not None:
+                extras["enum"] = jce_info.enum_type
+            if jce_info.decimal is not None:
+                extras["decimal"] = jce_info.decimal
+            if jce_info.datetime is not None:
+                extras["datetime"] = jce_info.datetime
+            if jce_info.ip is not None:
+                extras["ip"] = jce_info.ip
+            if jce_info.endian is not None:
+                extras["endian"] = jce_info.endian
+            if jce_info.alias is not None:
+                extras["alias"] = jce_info.alias
+            if field_info.is_required():
+                extras["required"] = True
+            if field_info.default is not None and cls._rejects_none(
+                field_info.annotation
+            ):
+                extras["not_none"] = True
+            if name in cls.__tars_deserializers__:
+                extras["deserializer"] = True
+            for kind in ("elem", "key", "value"):
+                arg_type = getattr(jce_info, kind)
+                if arg_type is not None:
+                    extras[kind] = cls._type_desc(arg_type, type_map)
+
+            # 7. 构建 Tuple
+            item: tuple = (name, tag, type_code, default_val, has_serializer)
+            if extras:
+                item += (extras,)
+            schema.append(item)
 
         cls.__core_schema_cache__ = schema
         return schema
 
+    @staticmethod
+    def _rejects_none(annotation: Any) -> bool:
+        """类型注解是否不接受 None.
+
+        仅具体类 (`int`, `str`, 结构体等) 与 `list[...]`/`dict[...]` 视为不接受;
+        `Optional`, `Union`, `Any`, `TypeVar` 等一律视为可能为 None.
+        """
+        if isinstance(annotation, type):
+            return annotation is not type(None) and annotation is not object
+        return get_origin(annotation) in (list, dict)
+
+    @staticmethod
+    def _type_code(tars_type_cls: Any, type_map: dict[Any, int]) -> int:
+        """将 JCE 类型类映射为 core 使用的类型码."""
+        if isinstance(tars_type_cls, type) and issubclass(tars_type_cls, Struct):
+            return 10  # StructBegin
+        if tars_type_cls is None:
+            return 255  # 运行时推断 (Any)
+        return type_map.get(tars_type_cls, 0)
+
+    @classmethod
+    def _type_desc(cls, tars_type_cls: Any, type_map: dict[Any, int]) -> Any:
+        """构建容器元素的类型描述.
+
+        具体的 Struct 子类携带自身类型, 以便按其 Schema 编解码; 其余使用类型码.
+        """
+        is_model = isinstance(tars_type_cls, type) and issubclass(tars_type_cls, Struct)
+        if is_model and tars_type_cls is not Struct:
+            return tars_type_cls
+        return cls._type_code(tars_type_cls, type_map)
+
     @model_validator(mode="before")
     @classmethod
     def _tars_pre_validate(cls, value: Any, info: ValidationInfo) -> Any:
@@ -582,7 +987,8 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
 
         1. Bytes -> 调用 Rust 解码 -> Dict
         2. Tag Dict -> Python 循环映射 -> Name Dict (含 Blob 自动解包)
-        3. Name Dict -> 直接放行
+        3. 以 `tars_alias` 为键的项 -> 映射为字段名
+        4. Name Dict -> 直接放行
         """
         if isinstance(value, bytes | bytearray):
             try:
@@ -606,7 +1012,8 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
                     f"Failed to decode JCE bytes for {cls.__name__}: {e}"
                 ) from e
         if isinstance(value, dict):
-            if any(isinstance(k, int) for k in value):
+            alias_map = cls.__tars_alias_map__
+            if any(isinstance(k, int) or k in alias_map for k in value):
                 tag_map = cls.__tars_tag_map__
                 new_value = {}
 
@@ -615,6 +1022,10 @@ class Struct(BaseModel, types.Type, metaclass=StructMeta):
                         field_name = tag_map[k]
                         new_value[field_name] = v
 
+                    elif k in alias_map and alias_map[k] not in value:
+                        # 字段名优先于别名
+                        new_value[alias_map[k]] = v
+
                     else:
                         new_value[k] = v
 
@@ -85,8 +85,9 @@ class BYTE(Type):
 class BOOL(Type):
     """JCE 布尔类型.
 
-    Tarsio 协议原生不支持 bool，此类型在序列化时映射为 `INT8` (Type ID 0/1)。
-    True -> 1, False -> 0.
+    Tarsio 协议原生不支持 bool，此类型在序列化时映射为最小宽度的整数 (Type ID 0 或 12)。
+    True -> 1, False -> 0. 反序列化时整数按是否非零还原为 `True`/`False`。
+    注解为 `bool` 的字段会自动推断为此类型。
     """
 
 
@@ -1,24 +1,63 @@
 """测试 JCE API 层."""
 
+import base64
 import io
+import json
+import sys
+from array import array
+from collections import OrderedDict
 from collections.abc import Callable
+from enum import IntEnum
+from pathlib import Path
 from typing import TYPE_CHECKING, Any
 
 if TYPE_CHECKING:
     from tarsio.api import BytesMode
 
 import pytest
+from pydantic import ValidationError
 from tarsio import (
     DecodeError,
     Field,
+    Fixed8,
+    Fixed16,
+    Fixed32,
+    Fixed64,
+    JceReader,
+    JceStr,
+    JceType,
+    JceWriter,
+    LengthPrefixedWriter,
     Option,
     Struct,
     StructDict,
+    canonical_hash,
+    canonicalize,
+    debug_dump,
+    denormalize_struct,
     dump,
     dumps,
+    dumps_batch,
+    dumps_batch_framed,
+    dumps_into,
+    from_json,
+    get_field,
+    index_struct,
+    jce_equal,
     load,
     loads,
+    loads_batch,
+    loads_batch_parallel,
+    loads_partial,
+    loads_value,
+    loads_projection,
+    normalize_struct,
+    schema_compatible,
+    serialize_schema,
+    set_schema_method_name,
+    to_json,
 )
+from tarsio import _core as core
 
 
 class SimpleUser(Struct):
@@ -59,6 +98,25 @@ def test_loads_map_behavior() -> None:
     assert isinstance(decoded[0][1], str)
 
 
+def test_loads_map_factory() -> None:
+    """map_factory 应用于构造每个 Map 容器, 并保持线上顺序."""
+    encoded = dumps({"b": 1, "a": {"y": 2, "x": 3}})
+
+    decoded = loads(encoded, map_factory=OrderedDict)
+
+    assert type(decoded[0]) is OrderedDict
+    assert list(decoded[0]) == ["b", "a"]
+    assert type(decoded[0]["a"]) is OrderedDict
+    assert list(decoded[0]["a"]) == ["y", "x"]
+    assert type(loads(encoded)[0]) is dict
+
+    # 钩子中调用的其他解码入口不继承外层的 map_factory
+    nested = loads(
+        encoded, map_factory=OrderedDict, hooks={0: lambda v, _: get_field(encoded, 0)}
+    )
+    assert type(nested[0]) is dict
+
+
 def test_jce_dict_struct_behavior() -> None:
     """StructDict 对象应被序列化为 Struct 格式而非 Map."""
     data = StructDict({0: 100})
@@ -276,6 +334,231 @@ def test_dumps_with_option() -> None:
     assert isinstance(encoded_be, bytes)
 
 
+def test_dumps_little_endian_repeated() -> None:
+    """小端序重复编码 (含嵌套结构体) 结果应保持一致."""
+    data = {0: 70000, 1: StructDict({0: 70000})}
+
+    first = dumps(StructDict(data), option=Option.LITTLE_ENDIAN)
+    for _ in range(3):
+        assert dumps(StructDict(data), option=Option.LITTLE_ENDIAN) == first
+    assert loads(first, option=Option.LITTLE_ENDIAN) == data
+
+
+def test_endian_param_overrides_option() -> None:
+    """endian 参数应等价于并覆盖 LITTLE_ENDIAN 选项位."""
+    data = StructDict({0: 70000})
+    le = dumps(data, option=Option.LITTLE_ENDIAN)
+
+    assert dumps(data, endian="little") == le
+    assert dumps(data, option=Option.LITTLE_ENDIAN, endian="big") == dumps(data)
+    assert loads(le, endian="little") == {0: 70000}
+    assert loads(dumps(data), option=Option.LITTLE_ENDIAN, endian="big") == {0: 70000}
+
+    user = SimpleUser(uid=70000)
+    assert loads(dumps(user, endian="little"), SimpleUser, endian="little") == user
+    with pytest.raises(ValueError, match="endian must be 'big' or 'little'"):
+        dumps(data, endian="middle")
+
+
+def test_string_length_config() -> None:
+    """string1_max 与 force_string4 应控制字符串使用 String1 还是 String4."""
+    data = StructDict({0: "abc"})
+
+    assert dumps(data) == b"\x06\x03abc"
+    assert dumps(data, string1_max=2) == b"\x07\x00\x00\x00\x03abc"
+    assert dumps(data, string1_max=3) == b"\x06\x03abc"
+    assert dumps(data, force_string4=True) == b"\x07\x00\x00\x00\x03abc"
+    assert loads(dumps(data, force_string4=True)) == {0: "abc"}
+    # 嵌套在 Map 内的字符串同样生效
+    nested = dumps({"k": "v"}, force_string4=True, option=Option.SORT_MAP_KEYS)
+    assert nested == b"\x08\x00\x01\x07\x00\x00\x00\x01k\x17\x00\x00\x00\x01v"
+
+    user = SimpleUser(uid=1, name="a")
+    encoded = dumps(user, force_string4=True)
+    assert encoded.endswith(b"\x17\x00\x00\x00\x01a")
+    assert loads(encoded, SimpleUser) == user
+    # 配置仅作用于单次调用
+    assert dumps(data) == b"\x06\x03abc"
+
+
+def test_string_length_config_on_other_encoders() -> None:
+    """其余编码入口同样应用字符串长度配置, 超出范围的 string1_max 抛出 ValueError."""
+    user = SimpleUser(uid=1, name="a")
+    expected = dumps(user, force_string4=True)
+
+    out = bytearray()
+    dumps_into(user, out, force_string4=True)
+    assert bytes(out) == expected
+    assert dumps_batch([user], force_string4=True) == [expected]
+    framed = dumps_batch_framed([user], force_string4=True, inclusive_length=False)
+    assert framed[4:] == expected
+    writer = LengthPrefixedWriter(inclusive_length=False, force_string4=True)
+    writer.pack(user)
+    assert writer.get_buffer()[4:] == expected
+    assert from_json('{"0":"a"}', force_string4=True) == b"\x07\x00\x00\x00\x01a"
+    for bad in (256, -1):
+        with pytest.raises(ValueError, match="string1_max must be between 0 and 255"):
+            dumps(user, string1_max=bad)
+        with pytest.raises(ValueError, match="string1_max"):
+            LengthPrefixedWriter(string1_max=bad)
+
+
+def test_fixed_simple_list_len() -> None:
+    """FIXED_SIMPLE_LIST_LEN 下 SimpleList 长度应固定以 Int4 写入."""
+    data = StructDict({0: b"abc"})
+
+    assert dumps(data) == b"\x0d\x00\x00\x03abc"
+    fixed = dumps(data, option=Option.FIXED_SIMPLE_LIST_LEN)
+    assert fixed == b"\x0d\x00\x02\x00\x00\x00\x03abc"
+    assert loads(fixed, bytes_mode="raw") == {0: b"abc"}
+
+
+def test_str_encoding() -> None:
+    """str_encoding 应按指定编码转码字符串字段."""
+    gbk = "中文".encode("gbk")
+    data = StructDict({0: "中文"})
+
+    encoded = dumps(data, str_encoding="gbk")
+    assert encoded == b"\x06" + bytes([len(gbk)]) + gbk
+    assert loads(encoded, str_encoding="gbk") == {0: "中文"}
+    assert loads(dumps(data, str_encoding="utf-16le"), str_encoding="utf-16le") == data
+    assert dumps(data, str_encoding="UTF8") == dumps(data)
+
+    user = SimpleUser(uid=1, name="张三")
+    encoded_user = dumps(user, str_encoding="gbk")
+    assert "张三".encode("gbk") in encoded_user
+    assert loads(encoded_user, SimpleUser, str_encoding="gbk") == user
+    with pytest.raises(DecodeError, match="Invalid UTF-8"):
+        loads(encoded_user, SimpleUser)
+    with pytest.raises(ValueError, match="Unknown str_encoding"):
+        dumps(data, str_encoding="no-such-codec")
+    with pytest.raises(DecodeError, match="Invalid ascii string"):
+        loads(dumps(data), str_encoding="ascii")
+    with pytest.raises(DecodeError, match="Invalid ascii string"):
+        loads(encoded_user, SimpleUser, str_encoding="ascii")
+
+
+def test_nested_calls_do_not_inherit_str_encoding() -> None:
+    """钩子中调用的其他编解码入口不应继承外层调用的 str_encoding."""
+    user = SimpleUser(uid=1, name="张三")
+    utf8 = dumps(user)
+    seen: list[Any] = []
+
+    def probe(value: Any, ctx: Any) -> Any:
+        seen.append(get_field(utf8, 1))
+        seen.append(loads_projection(utf8, SimpleUser, {1})["name"])
+        seen.append(loads_batch([utf8], SimpleUser)[0].name)
+        seen.append(dumps_batch([user])[0])
+        out = bytearray()
+        dumps_into(user, out)
+        seen.append(bytes(out))
+        return value
+
+    outer = dumps(StructDict({0: "中文"}), str_encoding="gbk")
+    assert loads(outer, str_encoding="gbk", hooks={0: probe}) == {0: "中文"}
+    assert seen == ["张三", "张三", "张三", utf8, utf8]
+
+
+def test_loads_partial() -> None:
+    """loads_partial 应返回出错前已解码的字段与出错位置."""
+    data = dumps(StructDict({0: 1, 1: "ab", 2: StructDict({0: 3})}))
+
+    assert loads_partial(data) == ({0: 1, 1: "ab", 2: {0: 3}}, None)
+
+    fields, error = loads_partial(data[:-2])
+    assert isinstance(fields, StructDict)
+    assert fields == {0: 1, 1: "ab"}
+    assert error is not None
+    assert (error["offset"], error["tag"]) == (data.index(b"\x2a"), 2)
+    assert isinstance(error["error"], DecodeError)
+    # 字段头部本身不完整
+    _, error = loads_partial(b"\x00\x01\xf0")
+    assert error is not None
+    assert (error["offset"], error["tag"]) == (2, None)
+    assert "Truncated extended tag header" in str(error["error"])
+    # 字节序与文本编码同 loads
+    little = dumps(StructDict({0: 300, 1: "中"}), endian="little", str_encoding="gbk")
+    fields, error = loads_partial(little[:-1], endian="little", str_encoding="gbk")
+    assert fields == {0: 300}
+    assert error is not None
+    assert loads_partial(little, endian="little", str_encoding="gbk") == ({0: 300, 1: "中"}, None)
+
+
+def test_loads_value() -> None:
+    """loads_value 应解码顶层单个 List/Map/SimpleList 值并拒绝多余数据."""
+    assert loads_value(dumps([1, 2])) == [1, 2]
+    assert loads_value(dumps({"a": 1})) == {"a": 1}
+    # 头部 Tag 被忽略
+    assert loads_value(b"\x59\x00\x01\x00\x07") == [7]
+    assert loads_value(dumps(b"\xff")) == b"\xff"
+    assert loads_value(dumps(b"ab"), bytes_mode="raw") == b"ab"
+
+    with pytest.raises(DecodeError, match="2 trailing bytes"):
+        loads_value(dumps([1]) + b"\x10\x01")
+    with pytest.raises(DecodeError, match="StructEnd"):
+        loads_value(b"\x0b")
+
+
+def test_loads_value_limits_and_text_policy() -> None:
+    """loads_value 与 loads 一样接受编码, 规模限制与控制字符配置."""
+    assert loads_value(dumps(["中"], str_encoding="gbk"), str_encoding="gbk") == ["中"]
+    with pytest.raises(DecodeError, match="Total element count exceeds limit 2"):
+        loads_value(dumps([1, 2, 3]), max_total_elements=2)
+    nested = dumps(StructDict({0: StructDict({i: i for i in range(3)})}))
+    with pytest.raises(DecodeError, match="Struct field count exceeds limit 2"):
+        loads_value(nested, max_fields=2)
+    assert loads_value(dumps(b"a\x01"), allowed_control_bytes=b"\x01") == "a\x01"
+
+
+def test_generic_decode_limits() -> None:
+    """max_fields 与 max_total_elements 应限制通用解码的字段与元素数量."""
+    wide = dumps(StructDict({i: i for i in range(5)}))
+    assert loads(wide, max_fields=5) == {i: i for i in range(5)}
+    with pytest.raises(DecodeError, match="Struct field count exceeds limit 4"):
+        loads(wide, max_fields=4)
+
+    # 顶层字段 1 个 + List 元素 5 个
+    data = dumps(StructDict({0: [1, 2, 3, 4, 5]}))
+    assert loads(data, max_total_elements=6) == {0: [1, 2, 3, 4, 5]}
+    with pytest.raises(DecodeError, match="Total element count exceeds limit 5"):
+        loads(data, max_total_elements=5)
+    # 计数在每次调用时重置
+    assert loads(data, max_total_elements=6) == {0: [1, 2, 3, 4, 5]}
+    # auto 模式试探嵌套结构体失败时, 其登记的元素不计入总数
+    nested = dumps(StructDict({0: 1, 1: 2, 2: 3}))
+    probed = dumps(StructDict({0: nested, 1: 5}))
+    assert loads(probed, max_total_elements=3) == {0: nested, 1: 5}
+
+
+def test_struct_decode_limits() -> None:
+    """max_fields 与 max_total_elements 同样限制 Struct 目标的解码."""
+
+    class Bag(Struct):
+        items: list[int] = Field(id=0)
+        count: int = Field(id=1, default=0)
+
+    data = dumps(Bag(items=[1, 2, 3, 4, 5], count=1))
+    assert loads(data, Bag, max_fields=2, max_total_elements=7).items == [1, 2, 3, 4, 5]
+    with pytest.raises(DecodeError, match="Struct field count exceeds limit 1"):
+        loads(data, Bag, max_fields=1)
+    with pytest.raises(DecodeError, match="Total element count exceeds limit 6"):
+        loads(data, Bag, max_total_elements=6)
+
+
+def test_allowed_control_bytes() -> None:
+    """allowed_control_bytes 应调整 auto 模式下文本与 bytes 的判断."""
+    data = dumps(StructDict({0: b"page\x0c\tend"}))
+
+    assert loads(data) == {0: b"page\x0c\tend"}
+    assert loads(data, allowed_control_bytes=b"\t\x0c") == {0: "page\x0c\tend"}
+    # 显式指定时替换默认集合
+    assert loads(data, allowed_control_bytes=b"\x0c") == {0: b"page\x0c\tend"}
+    # 设置仅对当前调用生效
+    assert loads(data) == {0: b"page\x0c\tend"}
+    with pytest.raises(ValueError, match="ASCII control characters"):
+        loads(data, allowed_control_bytes=b"a")
+
+
 def test_dumps_with_exclude_unset() -> None:
     """dumps(exclude_unset=True) 应排除未设置的字段."""
     user = SimpleUser(uid=100)
@@ -345,6 +628,27 @@ def test_load_with_bytes_mode() -> None:
     assert isinstance(loaded_str[0][1], str)
 
 
+def test_loads_bytes_mode_view() -> None:
+    """bytes_mode='view' 应返回指向输入数据的 memoryview 切片."""
+    data = dumps({0: b"\x00\x01\x02", 1: [b"\xff"]})
+
+    result = loads(data, dict, bytes_mode="view")[0]
+    view = result[0]
+    assert isinstance(view, memoryview)
+    assert view.obj is data
+    assert bytes(view) == b"\x00\x01\x02"
+    assert isinstance(result[1][0], memoryview)
+
+    buf = bytearray(data)
+    view = loads(buf, dict, bytes_mode="view")[0][0]
+    buf[data.index(b"\x00\x01\x02")] = 0x09
+    assert view[0] == 0x09
+
+    # 非连续输入需要先拷贝, 回退为 bytes
+    strided = memoryview(bytes(b for c in data for b in (c, 0)))[::2]
+    assert isinstance(loads(strided, dict, bytes_mode="view")[0][0], bytes)
+
+
 def test_load_with_context() -> None:
     """load() 应能将 context 传递给反序列化过程."""
 
@@ -386,6 +690,18 @@ def test_loads_with_different_input_types(
     assert loaded.name == "test", f"失败: {desc}"
 
 
+def test_loads_memoryview_slice_of_buffer() -> None:
+    """loads() 应能直接解码接收缓冲区的 memoryview 切片."""
+    packet = dumps(StructDict({0: 1, 1: "abc"}))
+    buffer = bytearray(b"\xaa\xbb" + packet + b"\xcc")
+    view = memoryview(buffer)[2 : 2 + len(packet)]
+
+    assert loads(view) == {0: 1, 1: "abc"}
+    assert get_field(view, 1) == "abc"
+    assert canonical_hash(view) == canonical_hash(packet)
+    view.release()
+
+
 def test_convert_bytes_nested_jcedict() -> None:
     """loads(bytes_mode='auto') 应递归转换嵌套 StructDict 中的字节."""
     data = StructDict({0: StructDict({1: b"nested_text"})})
@@ -413,3 +729,915 @@ def test_error_handling_invalid_data() -> None:
 
     with pytest.raises(DecodeError):
         loads(invalid_data)
+
+
+class WideStruct(Struct):
+    """用于测试投影解码的结构体."""
+
+    uid: int = Field(id=0)
+    name: str = Field(id=1, default="")
+    tags: list[str] = Field(id=2, default_factory=list)
+    extra: dict[str, int] = Field(id=3, default_factory=dict)
+
+
+def test_loads_projection_returns_only_requested_fields() -> None:
+    """loads_projection() 应只返回请求的字段."""
+    data = dumps(WideStruct(uid=7, name="n", tags=["a", "b"], extra={"k": 1}))
+
+    result = loads_projection(data, WideStruct, {0, 1})
+
+    assert result == {"uid": 7, "name": "n"}
+
+
+def test_loads_projection_fills_missing_requested_default() -> None:
+    """loads_projection() 对线上缺失的请求字段应填充默认值."""
+    data = dumps(WideStruct(uid=7), option=Option.OMIT_DEFAULT)
+
+    result = loads_projection(data, WideStruct, {1})
+
+    assert result == {"name": ""}
+
+
+def test_dumps_with_context_field_order() -> None:
+    """context["field_order"] 应覆盖顶层字段的写出顺序."""
+    u = SimpleUser(uid=100, name="test")
+
+    data = dumps(u, context={"field_order": [1]})
+
+    assert data == bytes.fromhex("1604746573740064")
+
+
+def test_get_field_returns_matching_tag() -> None:
+    """get_field() 应返回指定 Tag 的字段值."""
+    data = dumps(WideStruct(uid=7, name="n", tags=["a", "b"]))
+
+    assert get_field(data, 1) == "n"
+    assert get_field(data, 2) == ["a", "b"]
+
+
+def test_get_field_returns_none_when_absent() -> None:
+    """get_field() 对不存在的 Tag 应返回 None."""
+    data = dumps(SimpleUser(uid=1, name="a"))
+
+    assert get_field(data, 9) is None
+
+
+def test_get_field_stops_after_match() -> None:
+    """get_field() 命中 Tag 后不应继续解析后续数据."""
+    # Tag 0 = 100, 随后是无效的类型 ID 15
+    data = bytes.fromhex("0064") + b"\xff\xff"
+
+    assert get_field(data, 0) == 100
+
+
+def test_index_struct() -> None:
+    """index_struct() 应返回顶层字段值的偏移量与长度 (不含头部)."""
+    data = dumps(StructDict({0: 7, 3: StructDict({0: "x"}), 20: "ab"}))
+    index = index_struct(data)
+
+    assert index == {0: (1, 1), 3: (3, 4), 20: (9, 3)}
+    offset, length = index[3]
+    assert loads(data[offset : offset + length]) == {0: "x"}
+    assert index_struct(data, duplicates="list")[0] == [(1, 1)]
+
+
+def test_index_struct_duplicates() -> None:
+    """index_struct() 对重复 Tag 默认取最后一次出现, 可收集为列表."""
+    data = b"\x00\x01\x10\x05\x00\x02"
+
+    assert index_struct(data) == {0: (5, 1), 1: (3, 1)}
+    assert index_struct(data, duplicates="list") == {0: [(1, 1), (5, 1)], 1: [(3, 1)]}
+    with pytest.raises(ValueError, match="Invalid duplicates mode"):
+        index_struct(data, duplicates="first")  # type: ignore[call-overload]
+    with pytest.raises(DecodeError):
+        index_struct(data + b"\x1a\x0c")
+
+
+def test_canonical_hash_ignores_encoding_variation() -> None:
+    """语义相同但编码不同的数据包应得到相同的 canonical_hash."""
+    # Tag 1 = "a", Tag 0 = 1 (Int4 非最小宽度, 且 Tag 乱序)
+    loose = bytes.fromhex("1601610200000001")
+    tight = dumps(StructDict({0: 1, 1: "a"}))
+
+    assert loose != tight
+    assert canonical_hash(loose) == canonical_hash(tight)
+    assert len(canonical_hash(tight)) == 32
+
+
+def test_canonical_hash_differs_for_different_values() -> None:
+    """语义不同的数据包应得到不同的 canonical_hash."""
+    a = dumps(StructDict({0: 1}))
+    b = dumps(StructDict({0: 2}))
+
+    assert canonical_hash(a) != canonical_hash(b)
+
+
+def test_loads_release_gil() -> None:
+    """RELEASE_GIL 选项不应改变通用解码结果, 非法数据应在校验阶段报错."""
+    data = dumps(StructDict({0: 1, 1: "a", 2: {3: b"x"}}))
+
+    assert loads(data, option=Option.RELEASE_GIL) == loads(data)
+    view = memoryview(bytearray(data)).toreadonly()
+    assert loads(view, option=Option.RELEASE_GIL) == loads(data)
+    open_struct = b"\x1a\x00\x01"
+    assert loads(open_struct, option=Option.RELEASE_GIL) == loads(open_struct)
+    with pytest.raises(DecodeError):
+        loads(b"\x1a\x00", option=Option.RELEASE_GIL)
+
+
+def test_dumps_batch_matches_dumps() -> None:
+    """dumps_batch() 的每一项应与单独调用 dumps() 一致."""
+    users = [SimpleUser(uid=i, name=f"u{i}") for i in range(5)]
+
+    assert dumps_batch(users) == [dumps(u) for u in users]
+    assert dumps_batch(users, option=Option.LITTLE_ENDIAN) == [
+        dumps(u, option=Option.LITTLE_ENDIAN) for u in users
+    ]
+    assert dumps_batch([]) == []
+
+
+def test_dumps_batch_rejects_mixed_types() -> None:
+    """dumps_batch() 遇到不同类型的对象应报错."""
+    with pytest.raises(TypeError):
+        dumps_batch([SimpleUser(uid=1), WideStruct(uid=2)])
+
+
+def test_dumps_batch_framed_matches_writer() -> None:
+    """dumps_batch_framed() 的输出应与 LengthPrefixedWriter 一致."""
+    users = [SimpleUser(uid=i) for i in range(3)]
+    writer = LengthPrefixedWriter(length_type=2, inclusive_length=False)
+    for u in users:
+        writer.write(u)
+
+    framed = dumps_batch_framed(users, length_type=2, inclusive_length=False)
+
+    assert framed == writer.get_buffer()
+
+
+def test_loads_batch_round_trip() -> None:
+    """loads_batch() 应与逐个调用 loads() 结果一致."""
+    users = [SimpleUser(uid=i, name=f"u{i}") for i in range(3)]
+
+    assert loads_batch(dumps_batch(users), SimpleUser) == users
+
+
+def test_loads_batch_skip_errors() -> None:
+    """loads_batch(skip_errors=True) 应对损坏的数据包返回 None."""
+    frames = [dumps(SimpleUser(uid=1)), b"\x16\x05ab", dumps(SimpleUser(uid=2))]
+
+    result = loads_batch(frames, SimpleUser, skip_errors=True)
+
+    assert result == [SimpleUser(uid=1), None, SimpleUser(uid=2)]
+    with pytest.raises(DecodeError):
+        loads_batch(frames, SimpleUser)
+    # 非数据错误 (此处为不支持缓冲区协议的对象) 不应被吞掉
+    bad_frames: list[Any] = [*frames, 5]
+    with pytest.raises(TypeError):
+        loads_batch(bad_frames, SimpleUser, skip_errors=True)
+
+
+def test_loads_batch_parallel_preserves_order() -> None:
+    """loads_batch_parallel() 应保持输入顺序, 并在错误信息中给出非法数据包的索引."""
+    users = [SimpleUser(uid=i, name=f"u{i}") for i in range(10)]
+    frames: list[bytes | bytearray | memoryview] = list(dumps_batch(users))
+    frames[3] = bytearray(frames[3])
+    # 只读视图的底层 bytearray 仍可被修改, 同样按拷贝处理
+    frames[5] = memoryview(bytearray(frames[5])).toreadonly()
+
+    assert loads_batch_parallel(frames, SimpleUser, threads=3) == users
+
+    frames[7] = b"\x16\x05ab"
+    with pytest.raises(DecodeError, match="Frame 7"):
+        loads_batch_parallel(frames, SimpleUser, threads=3)
+
+    # 结构合法但字段类型不匹配, 在校验之后的解码阶段失败
+    frames[7] = b"\x06\x01x"
+    with pytest.raises(ValidationError) as excinfo:
+        loads_batch_parallel(frames, SimpleUser, threads=3)
+    if sys.version_info >= (3, 11):
+        assert "Frame 7" in excinfo.value.__notes__
+
+
+def test_loads_batch_parallel_decode_error_has_index() -> None:
+    """loads_batch_parallel() 在校验通过后的解码错误也应给出数据包索引."""
+
+    class Color(IntEnum):
+        RED = 1
+
+    class Paint(Struct):
+        color: Color = Field(id=0)
+
+    frames = [dumps(Paint(color=Color.RED)), dumps(StructDict({0: 5}))]
+    with pytest.raises(ValueError, match="Frame 1: Unknown Color value 5"):
+        loads_batch_parallel(frames, Paint)
+
+
+def test_debug_dump_annotates_fields() -> None:
+    """debug_dump() 应输出带偏移量与缩进的字段信息."""
+    data = dumps(StructDict({0: 256, 1: [1]}))
+
+    assert debug_dump(data) == (
+        "offset=0x00 tag=0 type=Int2 value=256\n"
+        "offset=0x03 tag=1 type=List size=1\n"
+        "  offset=0x06 tag=0 type=Int1 value=1\n"
+    )
+
+
+def test_debug_dump_marks_parse_error() -> None:
+    """debug_dump() 遇到非法数据时应追加错误标记而不是抛出异常."""
+    assert debug_dump(b"\x00\x01\x16\x05ab").endswith("<parse error at 0x04>\n")
+
+
+def test_dumps_into_appends() -> None:
+    """dumps_into() 应将编码结果追加到 bytearray 末尾并返回写入的字节数."""
+    user = SimpleUser(uid=1, name="a")
+    expected = dumps(user)
+
+    buf = bytearray(b"\xff")
+    assert dumps_into(user, buf) == len(expected)
+    assert dumps_into(user, buf, Option.LITTLE_ENDIAN) == len(expected)
+    assert buf == b"\xff" + expected + dumps(user, Option.LITTLE_ENDIAN)
+
+    # 存在导出的 memoryview 时无法调整大小
+    view = memoryview(buf)
+    with pytest.raises(BufferError):
+        dumps_into(user, buf)
+    view.release()
+
+
+def test_ordered_pairs_roundtrip() -> None:
+    """ORDERED_PAIRS 应按线上顺序返回 (tag, value) 列表并逐字节写回."""
+    # Tag 1 = 2, Tag 0 = Struct { Tag 2 = "a", Tag 1 = 5 }, Tag 1 = 3
+    data = b"\x10\x02\x0a\x26\x01a\x10\x05\x0b\x10\x03"
+
+    pairs = loads(data, option=Option.ORDERED_PAIRS)
+    assert pairs == [(1, 2), (0, [(2, "a"), (1, 5)]), (1, 3)]
+    assert dumps(pairs, option=Option.ORDERED_PAIRS) == data
+
+    # 未指定选项时仍然解码为 dict
+    assert loads(data, dict) == {1: 3, 0: {2: "a", 1: 5}}
+
+
+def test_sort_map_keys_is_deterministic() -> None:
+    """SORT_MAP_KEYS 应使插入顺序不同的相等字典 (包括嵌套 Map) 编码结果一致."""
+    a = {0: {"b": {2: 1, 1: 2}, "a": [1]}}
+    b = {0: {"a": [1], "b": {1: 2, 2: 1}}}
+
+    assert dumps(a) != dumps(b)
+    assert dumps(a, option=Option.SORT_MAP_KEYS) == dumps(
+        b, option=Option.SORT_MAP_KEYS
+    )
+    assert loads(dumps(a, option=Option.SORT_MAP_KEYS), dict)[0] == a
+
+
+def test_intern_strings_reuses_objects() -> None:
+    """INTERN_STRINGS 应使不同数据包中内容相同的字符串共享同一对象."""
+    data = dumps(StructDict({0: "command.name", 1: ["command.name"]}))
+
+    a = loads(data, option=Option.INTERN_STRINGS)
+    b = loads(data, option=Option.INTERN_STRINGS)
+    assert a[0] is b[0]
+    assert a[0] is a[1][0]
+
+    c = loads(data)
+    d = loads(data)
+    assert c[0] == d[0]
+    assert c[0] is not d[0]
+
+
+def test_to_json_and_back() -> None:
+    """to_json() 应输出以 Tag 为键的 JSON, from_json() 应还原为相同的字节."""
+    data = dumps(
+        StructDict(
+            {
+                0: 256,
+                1: StructDict({0: "a\n"}),
+                2: b"\xff\xfe",
+                3: {"k": [1.5, -2]},
+            }
+        )
+    )
+
+    text = to_json(data)
+    assert json.loads(text) == {
+        "0": 256,
+        "1": {"0": "a\n"},
+        "2": {"$bytes": "//4="},
+        "3": {"$map": [["k", [1.5, -2]]]},
+    }
+    assert from_json(text) == data
+    assert to_json(data, bytes_mode="raw") == text
+
+    with pytest.raises(ValueError, match="null"):
+        from_json('{"0": null}')
+    with pytest.raises(ValueError, match="Invalid tag key"):
+        from_json('{"x": 1}')
+
+
+def test_to_json_keeps_map_keys_and_empty_structs() -> None:
+    """to_json() 应保留 Map 键的类型并区分空结构体与空 Map, 重复的键应报错."""
+    data = dumps(StructDict({0: {1: "int", "1": "str"}, 1: StructDict(), 2: {}}))
+
+    text = to_json(data)
+    assert json.loads(text) == {
+        "0": {"$map": [[1, "int"], ["1", "str"]]},
+        "1": {},
+        "2": {"$map": []},
+    }
+    assert from_json(text) == data
+
+    with pytest.raises(ValueError, match="Duplicate tag key"):
+        from_json('{"1": 1, "1": 2}')
+    with pytest.raises(ValueError, match="Duplicate map key"):
+        from_json('{"0": {"$map": [[1, "a"], [1, "b"]]}}')
+
+
+def _vector_value(node: Any) -> Any:
+    """将黄金向量的 JSON 表示转换为通用编解码使用的 Python 值."""
+    if isinstance(node, list):
+        return [_vector_value(item) for item in node]
+    if isinstance(node, dict):
+        if "$map" in node:
+            return {_vector_value(k): _vector_value(v) for k, v in node["$map"]}
+        if "$bytes" in node:
+            return base64.b64decode(node["$bytes"])
+        return StructDict({int(k): _vector_value(v) for k, v in node.items()})
+    return node
+
+
+def test_json_golden_vectors() -> None:
+    """from_json()/to_json() 与 dumps()/loads() 应与 tests/vectors 中的黄金向量一致."""
+    vectors = Path(__file__).resolve().parents[2] / "tests" / "vectors"
+    names = sorted(path.stem for path in vectors.glob("*.json"))
+    assert names
+
+    for name in names:
+        text = (vectors / f"{name}.json").read_text(encoding="utf-8")
+        value = _vector_value(json.loads(text))
+        for suffix, little_endian in (("be", False), ("le", True)):
+            data = (vectors / f"{name}.{suffix}.bin").read_bytes()
+            assert json.loads(to_json(data, little_endian, bytes_mode="raw")) == (
+                json.loads(text)
+            )
+            assert from_json(text, little_endian) == data
+            endian = "little" if little_endian else "big"
+            assert dumps(value, endian=endian) == data
+            assert loads(data, bytes_mode="raw", endian=endian) == value
+
+
+def test_jce_equal_ignores_encoding_variation() -> None:
+    """jce_equal() 应忽略整数宽度, 字段顺序与 Map 键顺序的差异."""
+    # Tag 0 = 1 (Int1), Tag 1 = {1: 0, 2: 0}
+    tight = b"\x00\x01\x18\x00\x02\x00\x01\x1c\x00\x02\x1c"
+    # Tag 1 = {2: 0, 1: 0}, Tag 0 = 1 (Int4)
+    loose = b"\x18\x00\x02\x00\x02\x1c\x00\x01\x1c\x02\x00\x00\x00\x01"
+
+    assert jce_equal(tight, loose)
+    assert not jce_equal(tight, b"\x00\x02")
+
+
+def test_canonicalize_is_minimal_and_idempotent() -> None:
+    """canonicalize() 应输出最小编码, 且重复规范化结果不变."""
+    loose = b"\x16\x01a\x02\x00\x00\x00\x01"
+
+    once = canonicalize(loose)
+
+    assert once == b"\x00\x01\x16\x01a"
+    assert canonicalize(once) == once
+    assert jce_equal(once, loose)
+
+
+def test_loads_typed_simple_list() -> None:
+    """元素类型为 Int2/Int4/Int8 的 SimpleList 应解码为整数列表."""
+    # Tag 0 = SimpleList<Int4> [1, -2], Tag 1 = SimpleList<Int1> b"ab"
+    data = (
+        b"\x0d\x02\x00\x02\x00\x00\x00\x01\xff\xff\xff\xfe"
+        b"\x1d\x00\x00\x02ab"
+    )
+
+    assert loads(data, bytes_mode="raw") == {0: [1, -2], 1: b"ab"}
+    assert loads(data, option=Option.RELEASE_GIL, bytes_mode="raw")[0] == [1, -2]
+    assert get_field(data, 1, bytes_mode="raw") == b"ab"
+
+
+def test_dumps_int_array_as_simple_list() -> None:
+    """整数 array.array 应编码为定宽整数 SimpleList 并解码为整数列表."""
+    data = dumps(StructDict({0: array("h", [1, -1]), 1: array("I", [4294967295])}))
+
+    assert data.startswith(b"\x0d\x01\x00\x02\x00\x01\xff\xff")
+    assert loads(data) == {0: [1, -1], 1: [4294967295]}
+    with pytest.raises(TypeError):
+        dumps(StructDict({0: array("d", [1.0])}))
+    with pytest.raises(OverflowError):
+        dumps(StructDict({0: array("Q", [2**64 - 1])}))
+
+
+def test_reject_nonfinite_option() -> None:
+    """REJECT_NONFINITE 应在编码时拒绝 NaN/Inf, 在解码时将其转换为 None."""
+    data = dumps(StructDict({0: float("inf"), 1: 1.5}))
+
+    assert loads(data)[0] == float("inf")
+    assert loads(data, option=Option.REJECT_NONFINITE) == {0: None, 1: 1.5}
+    with pytest.raises(ValueError, match="tag 3"):
+        dumps(StructDict({3: float("nan")}), option=Option.REJECT_NONFINITE)
+
+
+def test_max_depth_per_call() -> None:
+    """max_depth 应覆盖默认的 100 层嵌套限制."""
+
+    def nested(levels: int) -> StructDict:
+        obj = StructDict({0: 1})
+        for _ in range(levels):
+            obj = StructDict({0: obj})
+        return obj
+
+    with pytest.raises(ValueError, match="Depth exceeded"):
+        dumps(nested(150))
+    data = dumps(nested(150), max_depth=400)
+    with pytest.raises(ValueError, match="Depth exceeded"):
+        loads(data)
+    assert loads(data, max_depth=400) == nested(150)
+
+    shallow = dumps(nested(5))
+    assert loads(shallow) == nested(5)
+    with pytest.raises(ValueError, match="Depth exceeded"):
+        loads(shallow, max_depth=2)
+    with pytest.raises(ValueError, match="Depth exceeded"):
+        dumps(nested(5), max_depth=2)
+    # 限制仅对当前调用生效
+    assert dumps(nested(5)) == shallow
+
+
+def test_max_depth_on_walkers() -> None:
+    """通用遍历与扫描入口应遵循 max_depth 参数."""
+    data = dumps(StructDict({0: StructDict({0: StructDict({0: 1})})}))
+
+    assert get_field(data, 0) == {0: {0: 1}}
+    with pytest.raises((ValueError, DecodeError), match="[Dd]epth"):
+        get_field(data, 0, max_depth=1)
+    for walk in (canonicalize, to_json, lambda d, **kw: jce_equal(d, d, **kw)):
+        walk(data)
+        with pytest.raises((ValueError, DecodeError), match="[Dd]epth"):
+            walk(data, max_depth=1)
+    with pytest.raises((ValueError, DecodeError), match="[Dd]epth"):
+        index_struct(data, max_depth=1)
+    assert "<parse error" in debug_dump(data, max_depth=1)
+
+
+def test_no_default_fill_option() -> None:
+    """NO_DEFAULT_FILL 应使 model_fields_set 仅包含数据中实际存在的字段."""
+    data = dumps(StructDict({0: 7}))
+
+    assert loads(data, SimpleUser).model_fields_set == {"uid", "name"}
+    user = loads(data, SimpleUser, option=Option.NO_DEFAULT_FILL)
+    assert user.model_fields_set == {"uid"}
+    assert user.name == "unknown"
+    assert dumps(user, exclude_unset=True) == data
+
+
+class _FactoryDefaults(Struct):
+    """默认值由 default_factory 或 None 提供的字段."""
+
+    uid: int = Field(id=0, default=0)
+    tags: list[str] = Field(id=1, default_factory=list)
+    nick: str | None = Field(id=2, default=None)
+
+
+def test_omit_none_defaults_option() -> None:
+    """OMIT_NONE_DEFAULTS 应只跳过默认值为 None 的缺失字段的回填."""
+    data = dumps(StructDict({2: "n"}))
+
+    with pytest.raises(ValidationError):
+        loads(data, _FactoryDefaults)
+    obj = loads(data, _FactoryDefaults, option=Option.OMIT_NONE_DEFAULTS)
+    assert obj == _FactoryDefaults(uid=0, tags=[], nick="n")
+    assert obj.model_fields_set == {"uid", "nick"}
+
+    empty = loads(b"", _FactoryDefaults, option=Option.OMIT_NONE_DEFAULTS)
+    assert empty.model_fields_set == {"uid"}
+    assert empty.nick is None
+    projected = loads_projection(
+        b"", _FactoryDefaults, {0, 1}, option=Option.OMIT_NONE_DEFAULTS
+    )
+    assert projected == {"uid": 0}
+
+
+class _SizedPacket(Struct):
+    """Tag 0 冗余记录自身总长度的结构体."""
+
+    length: int = Field(id=0)
+    body: str = Field(id=1)
+
+
+def test_verify_length_tag() -> None:
+    """verify_length_tag 应校验声明的长度与实际消耗的字节数一致."""
+    data = dumps(_SizedPacket(length=9, body="hello"))
+
+    assert loads(data, _SizedPacket, verify_length_tag=0).body == "hello"
+    assert loads(data, verify_length_tag=0) == {0: 9, 1: "hello"}
+
+    bad = dumps(_SizedPacket(length=8, body="hello"))
+    with pytest.raises(DecodeError, match="Declared length 8 at tag 0"):
+        loads(bad, _SizedPacket, verify_length_tag=0)
+    with pytest.raises(DecodeError, match="Declared length 8 at tag 0"):
+        loads(bad, verify_length_tag=0)
+    with pytest.raises(DecodeError, match="Length tag 3 not found"):
+        loads(data, verify_length_tag=3)
+    with pytest.raises(DecodeError, match="expected integer"):
+        loads(data, _SizedPacket, verify_length_tag=1)
+
+
+class Point:
+    """通过 __tars_encode__ 提供编码值的自定义对象."""
+
+    def __init__(self, x: int, y: int) -> None:
+        self.x, self.y = x, y
+
+    def __tars_encode__(self, context: dict[str, Any]) -> list[int]:
+        return [self.x * context.get("scale", 1), self.y * context.get("scale", 1)]
+
+
+def test_context_reaches_custom_encoder() -> None:
+    """通用编码应调用对象的 __tars_encode__ 并传入 context."""
+    assert loads(dumps(StructDict({0: Point(1, 2)}))) == {0: [1, 2]}
+    data = dumps(StructDict({0: Point(1, 2)}), context={"scale": 10})
+    assert loads(data) == {0: [10, 20]}
+
+
+def test_context_decode_hooks_intern_strings() -> None:
+    """context["hooks"] 应按 Tag 后处理各层结构体中的字段值, hooks 参数优先."""
+    data = dumps(
+        StructDict(
+            {
+                0: [StructDict({1: "alice"}), StructDict({1: "alice"})],
+                1: "alice",
+            }
+        )
+    )
+    cache: dict[str, str] = {}
+    seen: list[str] = []
+
+    def intern(value: str, ctx: dict[str, Any]) -> str:
+        seen.append(value)
+        return ctx["cache"].setdefault(value, value)
+
+    result = loads(data, context={"cache": cache, "hooks": {1: intern}})
+
+    assert result == {0: [{1: "alice"}, {1: "alice"}], 1: "alice"}
+    assert len(seen) == 3
+    assert result[0][0][1] is result[0][1][1] is result[1]
+
+    user = loads(
+        dumps(SimpleUser(uid=1, name="bob")),
+        SimpleUser,
+        context={"hooks": {1: lambda v, _: v.upper()}},
+    )
+    assert user.name == "BOB"
+    # hooks 参数优先于 context["hooks"]
+    overridden = loads(
+        data,
+        hooks={1: lambda v, _: v.title()},
+        context={"hooks": {1: lambda v, _: v.upper()}},
+    )
+    assert overridden[1] == "Alice"
+    with pytest.raises(TypeError, match=r"context\['hooks'\] must be a dict"):
+        loads(data, context={"hooks": "not a dict"})
+
+    # 钩子中调用的其他解码入口不继承外层的钩子
+    user_data = dumps(SimpleUser(uid=1, name="bob"))
+
+    def nested(value: str, ctx: Any) -> str:
+        return loads_batch([user_data], SimpleUser)[0].name
+
+    assert loads(data, hooks={1: nested})[1] == "bob"
+
+
+def test_decode_hook_errors_propagate_from_auto_probe() -> None:
+    """Auto 模式试探解析嵌套数据时, 钩子抛出的异常应原样传播而非回退为 bytes."""
+    data = dumps(StructDict({0: dumps(StructDict({1: 5}))}))
+
+    def reject(value: int, ctx: Any) -> int:
+        raise RuntimeError(f"bad value {value}")
+
+    assert loads(data) == {0: {1: 5}}
+    with pytest.raises(RuntimeError, match="bad value 5"):
+        loads(data, hooks={1: reject})
+
+
+class OptionalUser(Struct):
+    """包含可选字段的结构体."""
+
+    uid: int = Field(id=0)
+    nick: str | None = Field(id=1, default=None)
+
+
+def test_include_none_option() -> None:
+    """INCLUDE_NONE 应对值为 None 的字段报错, 而不是静默跳过."""
+    user = OptionalUser(uid=1)
+
+    assert dumps(user) == b"\x00\x01"
+    # SERIALIZE_NONE 保持原有含义, 不会因 None 报错
+    assert dumps(user, option=Option.SERIALIZE_NONE) == b"\x00\x01"
+    with pytest.raises(ValueError, match=r"Field 'nick' \(tag 1\) is None"):
+        dumps(user, option=Option.INCLUDE_NONE)
+    assert dumps(OptionalUser(uid=1, nick="a"), option=Option.INCLUDE_NONE)
+
+
+def test_duplicate_tag_policy() -> None:
+    """重复 Tag 默认后者覆盖, 可选择报错或收集为列表."""
+    data = b"\x00\x01\x10\x05\x00\x02\x00\x03"
+
+    assert loads(data) == {0: 3, 1: 5}
+    assert loads(data, option=Option.DUP_TAG_LIST) == {0: [1, 2, 3], 1: 5}
+    with pytest.raises(DecodeError, match="Duplicate tag 0"):
+        loads(data, option=Option.DUP_TAG_ERROR)
+
+
+def test_jce_type_constants() -> None:
+    """JceType 应与 Rust 核心导出的类型码一致, 并可直接与读取到的类型码比较."""
+    assert [(t.name, t.value) for t in JceType] == list(core.JCE_TYPES)
+    assert JceType.SIMPLE_LIST == 13
+    assert JceType.ZERO_TAG == 12
+
+    reader = JceReader(b"\x0c\x16\x01a")
+    assert reader.read_head() == (0, JceType.ZERO_TAG)
+    assert reader.read_head() == (1, JceType.STRING1)
+    assert reader.read_string(JceType.STRING1) == "a"
+
+
+def test_reader_peek_head() -> None:
+    """peek_head 应返回下一个头部且不移动偏移量 (包括扩展 Tag)."""
+    reader = JceReader(b"\xf0\x14\x07\x16\x01a")
+
+    assert reader.peek_head() == (20, 0)
+    assert reader.position() == 0
+    assert reader.read_head() == (20, 0)
+    assert reader.read_int(0) == 7
+    assert reader.peek_head() == (1, 6)
+    assert reader.read_head() == (1, 6)
+    assert reader.read_string(6) == "a"
+    assert reader.is_end()
+    with pytest.raises(DecodeError):
+        reader.peek_head()
+    assert reader.position() == 6
+
+
+def test_reader_seek_to_tag() -> None:
+    """seek_to_tag 应只扫描当前层级, 未找到时保持偏移量不变."""
+    data = dumps(StructDict({0: StructDict({3: 1}), 1: [1, 2], 3: "x"}))
+    reader = JceReader(data)
+
+    assert not reader.seek_to_tag(7)
+    assert reader.position() == 0
+    assert reader.seek_to_tag(3)
+    tag, type_id = reader.read_head()
+    assert tag == 3
+    assert reader.read_string(type_id) == "x"
+
+
+def test_reader_skip_field() -> None:
+    """skip_field 应整体跳过容器, 嵌套过深时抛出 DecodeError."""
+    data = dumps(StructDict({0: {"a": [1, 2]}, 1: StructDict({0: 1}), 2: "x"}))
+    reader = JceReader(data)
+
+    for _ in range(2):
+        _, type_id = reader.read_head()
+        reader.skip_field(type_id)
+    assert reader.read_head() == (2, 6)
+    assert reader.read_string(6) == "x"
+
+    nested = b"\x0a" * 5 + b"\x0b" * 5
+    shallow = JceReader(nested, max_depth=2)
+    _, type_id = shallow.read_head()
+    with pytest.raises(DecodeError, match="Max recursion depth"):
+        shallow.skip_field(type_id)
+    assert shallow.position() == 1
+    deep = JceReader(nested)
+    deep.read_head()
+    deep.skip_field(type_id)
+    assert deep.is_end()
+
+
+def test_reader_set_position_and_remaining() -> None:
+    """set_position 应支持回溯并拒绝超出数据长度的偏移量."""
+    reader = JceReader(b"\x00\x07\x16\x01a")
+
+    assert reader.remaining() == 5
+    reader.read_head()
+    start = reader.position()
+    assert reader.read_int(0) == 7
+    assert reader.remaining() == 3
+    reader.set_position(start)
+    assert reader.read_int(0) == 7
+    reader.set_position(5)
+    assert reader.is_end()
+    assert reader.remaining() == 0
+    with pytest.raises(ValueError, match="out of range"):
+        reader.set_position(6)
+    assert reader.position() == 5
+
+
+def test_reader_measure_field() -> None:
+    """measure_field 应返回完整字段长度 (含扩展 Tag 头部) 且不移动偏移量."""
+    data = dumps(StructDict({0: StructDict({3: [1, 2]}), 20: "ab"}))
+    reader = JceReader(data)
+
+    tag, type_id, length = reader.measure_field()
+    assert (tag, type_id) == (0, 10)
+    assert reader.position() == 0
+    reader.read_head()
+    reader.skip_field(type_id)
+    assert reader.position() == length
+    assert reader.measure_field() == (20, 6, 5)
+    assert reader.position() == length
+    reader.set_position(length + 5)
+    with pytest.raises(DecodeError):
+        reader.measure_field()
+    with pytest.raises(DecodeError):
+        JceReader(data[: length - 1]).measure_field()
+
+
+def test_writer_write_raw_field_passthrough() -> None:
+    """write_raw_field 应原样追加值的编码, 实现逐字节透传."""
+    # Tag 0 以 Int4 宽度编码的 1, 重新编码会被压缩为 Int1
+    data = b"\x02\x00\x00\x00\x01\x16\x02ab"
+    reader = JceReader(data)
+    writer = JceWriter()
+    while not reader.is_end():
+        start = reader.position()
+        tag, type_id, length = reader.measure_field()
+        reader.read_head()
+        body = data[reader.position() : start + length]
+        reader.set_position(start + length)
+        if tag == 1:
+            writer.write_string(1, "xyz")
+        else:
+            writer.write_raw_field(tag, type_id, body)
+
+    assert writer.getvalue() == b"\x02\x00\x00\x00\x01\x16\x03xyz"
+    assert len(writer) == 10
+    writer.clear()
+    writer.write_raw_field(20, JceType.STRING1, memoryview(b"\x01a"))
+    writer.write_bytes(1, b"\xff")
+    assert writer.getvalue() == b"\xf6\x14\x01a\x1d\x00\x00\x01\xff"
+    with pytest.raises(ValueError, match="Invalid type ID"):
+        writer.write_raw_field(0, 14, b"")
+
+    little = JceWriter(little_endian=True)
+    little.write_int(0, 256)
+    assert little.getvalue() == b"\x01\x00\x01"
+    assert loads(little.getvalue(), endian="little") == {0: 256}
+
+
+def test_zero_tag_container_size() -> None:
+    """以 ZeroTag 编码大小的空 Map/List 应解码为空容器, 且 Auto 探测结果一致."""
+    assert loads(b"\x08\x0c\x19\x0c", dict) == {0: {}, 1: []}
+
+    # SimpleList 内容为同样的数据时应被探测为嵌套结构体
+    blob = b"\x08\x0c\x19\x0c"
+    data = b"\x0d\x00\x00" + bytes([len(blob)]) + blob
+    assert loads(data, dict) == {0: {0: {}, 1: []}}
+
+
+def test_invalid_container_size_rejected() -> None:
+    """负数或超过剩余字节数的容器大小应抛出 DecodeError."""
+    with pytest.raises(DecodeError, match="Invalid container size -1"):
+        loads(b"\x09\x02\xff\xff\xff\xff", dict)
+    with pytest.raises(DecodeError, match="Container size 1000 exceeds"):
+        loads(b"\x08\x01\x03\xe8\x00\x01", dict)
+
+
+class _Point:
+    """通过自定义方法名提供 Schema 的非 Struct 对象."""
+
+    def __init__(self, x: int, y: int) -> None:
+        self.x = x
+        self.y = y
+
+    @classmethod
+    def tars_schema(cls) -> list[tuple]:
+        return [("x", 0, 0, 0, False), ("y", 1, 0, 0, False)]
+
+
+def test_set_schema_method_name() -> None:
+    """配置 Schema 方法名后, 编码器应使用该方法将对象编码为结构体."""
+    with pytest.raises(TypeError):
+        dumps(StructDict({0: _Point(1, 2)}))
+
+    set_schema_method_name("tars_schema")
+    try:
+        data = dumps(StructDict({0: _Point(1, 2)}))
+        assert loads(data, dict) == {0: {0: 1, 1: 2}}
+        # Struct 仍通过 __get_core_schema__ 编码
+        assert dumps(StructDict({0: SimpleUser(uid=1)})) == dumps(
+            StructDict({0: StructDict({0: 1, 1: "unknown"})})
+        )
+    finally:
+        set_schema_method_name(None)
+
+
+def test_schema_method_name_in_python_api() -> None:
+    """只提供自定义 Schema 方法的普通类应可用于顶层 dumps 与各函数的 schema 参数."""
+    set_schema_method_name("tars_schema")
+    try:
+        body = b"\x00\x01\x10\x02"
+        assert dumps(_Point(1, 2)) == body
+        assert dumps({"x": 1, "y": 2}, schema=_Point) == body
+        assert normalize_struct({"x": 1, "y": 2}, _Point) == {0: 1, 1: 2}
+        assert denormalize_struct({0: 1, 1: 2}, _Point) == {"x": 1, "y": 2}
+        assert serialize_schema(_Point) == serialize_schema(_Point.tars_schema())
+        assert schema_compatible(_Point, _Point)["compatible"]
+    finally:
+        set_schema_method_name(None)
+
+    # 恢复默认后普通对象重新按通用值编码
+    with pytest.raises(TypeError):
+        dumps(_Point(1, 2))
+
+
+def test_generic_encode_tuple_and_set() -> None:
+    """tuple 与 set 应按 List 编码, set 的元素排序后写入."""
+    expected = dumps(StructDict({0: [1, 2, 3]}))
+
+    assert dumps(StructDict({0: (1, 2, 3)})) == expected
+    assert dumps(StructDict({0: {3, 1, 2}})) == expected
+    assert dumps(StructDict({0: frozenset({2, 3, 1})})) == expected
+    assert dumps(StructDict({0: ("a", b"b")})) == dumps(StructDict({0: ["a", b"b"]}))
+
+
+def test_list_as_tuple() -> None:
+    """LIST_AS_TUPLE 应将通用解码中的 List (包括嵌套) 返回为 tuple."""
+    data = dumps(StructDict({0: [1, [2, 3]], 1: {"k": ["v"]}}))
+
+    result = loads(data, option=Option.LIST_AS_TUPLE)
+    assert result == {0: (1, (2, 3)), 1: {"k": ("v",)}}
+    assert hash(result[0])
+    assert loads(data) == {0: [1, [2, 3]], 1: {"k": ["v"]}}
+
+
+def test_fixed_width_ints() -> None:
+    """Fixed16/32/64 应固定宽度编码, KEEP_INT_WIDTH 下解码还原为包装类型."""
+    data = dumps(StructDict({0: Fixed64(0), 1: Fixed32(1), 2: Fixed16(-1)}))
+
+    assert data == (
+        b"\x03\x00\x00\x00\x00\x00\x00\x00\x00"
+        b"\x12\x00\x00\x00\x01"
+        b"\x21\xff\xff"
+    )
+    assert loads(data) == {0: 0, 1: 1, 2: -1}
+    restored = loads(data, option=Option.KEEP_INT_WIDTH)
+    assert restored == {0: Fixed64(0), 1: Fixed32(1), 2: Fixed16(-1)}
+    assert dumps(restored) == data
+    assert repr(Fixed32(1)) == "Fixed32(1)"
+    with pytest.raises(ValueError, match="Fixed16 value 70000 out of range"):
+        Fixed16(70000)
+
+    # 值为 0 的 Int1 与 ZeroTag 同样应原样往返
+    narrow = b"\x00\x00\x1c\x20\x05"
+    restored = loads(narrow, option=Option.KEEP_INT_WIDTH)
+    assert restored == {0: Fixed8(0), 1: 0, 2: Fixed8(5)}
+    assert dumps(restored) == narrow
+    assert dumps(StructDict({0: Fixed8(0)})) == b"\x00\x00"
+
+
+def test_str_view_option() -> None:
+    """STR_VIEW 下字符串应返回指向输入的 JceStr, 并可按原样重新编码."""
+    data = dumps(StructDict({0: "hello", 1: {"k": "v"}, 2: 7}))
+    result = loads(data, option=Option.STR_VIEW)
+
+    assert isinstance(result[0], JceStr)
+    assert result[0] == "hello"
+    assert str(result[0]) == "hello"
+    assert bytes(result[0]) == b"hello"
+    assert repr(result[0]) == "JceStr('hello')"
+    assert result[0].view.obj is data
+    assert hash(result[0]) == hash("hello")
+    assert result[1] == {"k": "v"}
+    assert dumps(StructDict(result)) == data
+    # 非连续输入无法借用, 回退为 str
+    padded = bytes(b for x in data for b in (x, 0))
+    copied = loads(memoryview(padded)[::2], option=Option.STR_VIEW)
+    assert copied[0] == "hello"
+    assert not isinstance(copied[0], JceStr)
+
+
+def test_str_view_over_mutable_buffer_is_unhashable() -> None:
+    """指向可变缓冲区的 JceStr 不可哈希, 但仍可比较."""
+    data = bytearray(dumps(StructDict({0: "hello"})))
+    view = loads(data, option=Option.STR_VIEW)[0]
+
+    assert isinstance(view, JceStr)
+    assert view == "hello"
+    with pytest.raises(TypeError, match="unhashable"):
+        hash(view)
+    data[-1:] = b"O"
+    assert view == "hellO"
+
+
+def test_truncated_extended_tag_header() -> None:
+    """扩展 Tag 头部的第二个字节缺失时应报告专门的错误."""
+    for option in (Option.NONE, Option.RELEASE_GIL):
+        with pytest.raises(
+            DecodeError, match=r"Truncated extended tag header \(at offset 0\)"
+        ):
+            loads(b"\xf0", option=option)
@@ -8,7 +8,7 @@
 """
 
 import pytest
-from tarsio import Field, Option, Struct, dumps
+from tarsio import Field, Option, Struct, StructDict, dumps
 
 # --- 辅助结构体定义 ---
 
@@ -128,6 +128,29 @@ def test_protocol_omit_default():
     assert dumps(obj2, option=Option.OMIT_DEFAULT).hex().upper() == "1003"
 
 
+def test_protocol_omit_empty():
+    """开启 OMIT_EMPTY 选项时应省略空字符串, 空 bytes 与空容器字段."""
+
+    class Sparse(Struct):
+        a: int = Field(id=0, default=0)
+        b: str = Field(id=1, default="x")
+        c: bytes = Field(id=2, default=b"x")
+        d: list[int] = Field(id=3, default=[1])
+        e: dict[str, int] = Field(id=4, default_factory=dict)
+
+    # 数值 0 不是空值, 仍然写出 -> 0C (Tag 0, ZeroTag)
+    obj = Sparse(a=0, b="", c=b"", d=[], e={})
+    assert dumps(obj, option=Option.OMIT_EMPTY).hex().upper() == "0C"
+
+    # 非空字段正常写出 -> 0C 16 01 79 (Tag 1, String1 "y")
+    obj = Sparse(b="y", c=b"", d=[])
+    assert dumps(obj, option=Option.OMIT_EMPTY).hex().upper() == "0C160179"
+
+    # 以 Tag 为键的通用结构体 (含嵌套) 同样省略空值 -> 0C 2A 0B (Tag 2, 空结构体)
+    generic = StructDict({0: 0, 1: "", 2: StructDict({0: [], 1: b""})})
+    assert dumps(generic, option=Option.OMIT_EMPTY).hex().upper() == "0C2A0B"
+
+
 def test_protocol_nested_map():
     """Map 的序列化结构 (Key-Value Pairs) 应符合协议标准."""
     # 输入: {10: [1]}
@@ -5,16 +5,32 @@
 2. 长度前缀协议 (LengthPrefixedWriter/Reader)
 3. 网络场景模拟 (粘包、拆包)
 4. 边界条件 (Max buffer size, Length limits)
+5. 独立分帧 (JceFramer)
 """
 
+import asyncio
+import io
 import struct
-from typing import cast
+from typing import Any, cast
 
 import pytest
-from tarsio import Field, Struct, StructDict
+from tarsio import (
+    DecodeError,
+    Field,
+    FrameTooLarge,
+    InvalidLength,
+    JceFrameDecodeError,
+    JceFramer,
+    PartialDataError,
+    Struct,
+    StructDict,
+    dumps,
+)
 from tarsio.stream import (
     LengthPrefixedReader,
     LengthPrefixedWriter,
+    load_stream,
+    split_frames,
 )
 
 
@@ -153,6 +169,78 @@ def test_reader_partial_header() -> None:
     assert packets[0] == {0: 1}
 
 
+def test_reader_feed_many() -> None:
+    """feed_many 应按顺序追加所有数据块, 超出上限时不追加任何数据."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+    buffer = writer.get_buffer()
+    chunks = [buffer[i : i + 3] for i in range(0, len(buffer), 3)]
+
+    reader = LengthPrefixedReader(target=StreamMsg, max_buffer_size=len(buffer))
+    reader.feed_many(chunks)
+    assert next(reader) == StreamMsg(id=1, data="a")
+    reader.compact()
+    assert reader.pending == len(buffer) // 2
+    assert list(reader) == [StreamMsg(id=2, data="b")]
+
+    reader.feed(buffer[:2])
+    with pytest.raises(BufferError):
+        reader.feed_many(chunks)
+    assert reader.pending == 2
+
+
+def test_reader_feed_buffer_protocol() -> None:
+    """feed 与 feed_many 应接受 bytearray, memoryview 等任意缓冲区对象."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+    buffer = writer.get_buffer()
+    half = len(buffer) // 2
+
+    reader = LengthPrefixedReader(target=StreamMsg)
+    reader.feed(bytearray(buffer[:half]))
+    reader.feed_many([memoryview(buffer)[half:-1], bytearray(buffer[-1:])])
+    assert list(reader) == [StreamMsg(id=1, data="a"), StreamMsg(id=2, data="b")]
+
+
+def test_reader_verify_length_tag() -> None:
+    """verify_length_tag 应对每个数据包的数据体校验声明的长度."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StructDict({0: 4, 1: 1}))
+    writer.pack(StructDict({0: 5, 1: 1}))
+
+    reader = LengthPrefixedReader(target=dict, verify_length_tag=0)
+    reader.feed(writer.get_buffer())
+
+    assert next(reader) == {0: 4, 1: 1}
+    with pytest.raises(DecodeError, match="does not match body length 4"):
+        next(reader)
+
+
+def test_reader_peek_frame_length() -> None:
+    """peek_frame_length 应返回下一个完整数据包的总长度且不取出数据."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    buffer = writer.get_buffer()
+
+    reader = LengthPrefixedReader(target=StreamMsg)
+    assert reader.peek_frame_length() is None
+    reader.feed(buffer[:-1])
+    assert reader.peek_frame_length() is None
+    reader.feed(buffer[-1:])
+    assert reader.peek_frame_length() == len(buffer)
+    assert reader.peek_frame_length() == len(buffer)
+    assert reader.pending == len(buffer)
+    assert next(reader) == StreamMsg(id=1, data="a")
+    assert reader.peek_frame_length() is None
+
+    reader.feed(struct.pack(">I", 2))
+    with pytest.raises(InvalidLength):
+        reader.peek_frame_length()
+    assert reader.pending == 4
+
+
 def test_length_prefixed_reader_with_jcedict() -> None:
     """LengthPrefixedReader 应支持 StructDict 作为 target."""
     writer = LengthPrefixedWriter()
@@ -169,6 +257,163 @@ def test_length_prefixed_reader_with_jcedict() -> None:
     assert packets[0][1] == "test"
 
 
+def test_reader_next_raw() -> None:
+    """next_raw() 应返回去除长度前缀的数据体并将其移出缓冲区."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+
+    reader = LengthPrefixedReader(target=StreamMsg)
+    reader.feed(writer.get_buffer())
+
+    body = reader.next_raw()
+    assert body == dumps(StreamMsg(id=1, data="a"))
+    assert next(reader) == StreamMsg(id=2, data="b")
+    assert reader.next_raw() is None
+
+
+def test_reader_async_iteration() -> None:
+    """异步迭代应在数据不足时等待数据源, 数据源结束时停止."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+    buffer = writer.get_buffer()
+    # 分片到达, 第一个分片不足一个完整数据包
+    chunks = [buffer[:3], buffer[3:10], buffer[10:], b""]
+
+    async def source() -> bytes:
+        return chunks.pop(0)
+
+    async def collect(reader: LengthPrefixedReader) -> list[Any]:
+        return [msg async for msg in reader]
+
+    reader = LengthPrefixedReader(target=StreamMsg)
+    reader.set_source(source)
+    assert asyncio.run(collect(reader)) == [
+        StreamMsg(id=1, data="a"),
+        StreamMsg(id=2, data="b"),
+    ]
+
+    # 未设置数据源时与同步迭代一致
+    reader = LengthPrefixedReader(target=StreamMsg)
+    reader.feed(buffer[:3])
+    assert asyncio.run(collect(reader)) == []
+
+
+def test_reader_skip_bad_frames() -> None:
+    """skip_bad_frames 应在坏包处抛出可恢复的异常, 随后继续读取后续数据包."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    # 分帧正确, 但数据体是截断的 String1
+    writer.write_bytes(b"\x16\x05ab")
+    writer.pack(StreamMsg(id=2, data="b"))
+
+    reader = LengthPrefixedReader(target=StreamMsg, skip_bad_frames=True)
+    reader.feed(writer.get_buffer())
+
+    assert next(reader) == StreamMsg(id=1, data="a")
+    with pytest.raises(JceFrameDecodeError) as exc_info:
+        next(reader)
+    assert exc_info.value.frame == b"\x16\x05ab"
+    assert isinstance(exc_info.value.__cause__, DecodeError)
+    assert next(reader) == StreamMsg(id=2, data="b")
+    assert list(reader) == []
+
+
+def test_reader_decode_limits() -> None:
+    """读取器的元素总数限制应按数据包分别计数."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StructDict({0: [1, 2]}))
+    writer.pack(StructDict({0: [1, 2]}))
+    writer.pack(StructDict({0: [1, 2, 3]}))
+
+    reader = LengthPrefixedReader(target=StructDict, max_total_elements=3)
+    reader.feed(writer.get_buffer())
+
+    assert next(reader) == {0: [1, 2]}
+    assert next(reader) == {0: [1, 2]}
+    with pytest.raises(DecodeError, match="Total element count exceeds limit 3"):
+        next(reader)
+
+
+def test_reader_allowed_control_bytes() -> None:
+    """读取器的 allowed_control_bytes 应作用于 auto 模式的文本判断."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StructDict({0: b"a\x0bb"}))
+
+    reader = LengthPrefixedReader(target=StructDict, allowed_control_bytes=b"\x0b")
+    reader.feed(writer.get_buffer())
+
+    assert next(reader) == {0: "a\x0bb"}
+
+
+def test_load_stream_reads_file() -> None:
+    """load_stream() 应处理短读并逐个产出完整数据包."""
+
+    class ShortReadIO(io.BytesIO):
+        def read(self, size: int | None = -1) -> bytes:
+            return super().read(min(size or 3, 3))
+
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+    buffer = writer.get_buffer()
+
+    packets = list(load_stream(ShortReadIO(buffer), StreamMsg))
+    assert packets == [StreamMsg(id=1, data="a"), StreamMsg(id=2, data="b")]
+
+    with pytest.raises(PartialDataError, match="incomplete frame"):
+        list(load_stream(io.BytesIO(buffer[:-1]), StreamMsg))
+
+
+def test_framer_check_frame() -> None:
+    """JceFramer 应只识别数据包边界, 分帧错误映射为对应的异常类型."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    buffer = writer.get_buffer()
+    framer = JceFramer()
+
+    assert framer.check_frame(buffer) == len(buffer)
+    assert framer.check_frame(bytearray(buffer) + b"\x00") == len(buffer)
+    assert framer.check_frame(buffer[:-1]) is None
+    assert framer.check_frame(b"\x00") is None
+    assert framer.length_type == 4
+
+    with pytest.raises(InvalidLength) as invalid:
+        framer.check_frame(b"\x00\x00\x00\x02")
+    assert (invalid.value.length, invalid.value.limit) == (2, 4)
+    with pytest.raises(FrameTooLarge):
+        JceFramer(max_buffer_size=8).check_frame(b"\x00\x00\x00\x09")
+    # 读取器的分帧错误同样为 ValueError 子类, 保持兼容
+    reader = LengthPrefixedReader(target=dict, max_buffer_size=8)
+    reader.feed(b"\x00\x00\x00\x09")
+    with pytest.raises(FrameTooLarge):
+        next(reader)
+    assert issubclass(FrameTooLarge, ValueError)
+
+
+def test_split_frames() -> None:
+    """split_frames 应切分出所有完整数据体并报告剩余字节数与坏包序号."""
+    writer = LengthPrefixedWriter()
+    writer.pack(StreamMsg(id=1, data="a"))
+    writer.pack(StreamMsg(id=2, data="b"))
+    buffer = writer.get_buffer()
+
+    bodies, leftover = split_frames(buffer + b"\x00\x00")
+    assert [StreamMsg.model_validate_tars(body) for body in bodies] == [
+        StreamMsg(id=1, data="a"),
+        StreamMsg(id=2, data="b"),
+    ]
+    assert leftover == 2
+    assert split_frames(b"") == ([], 0)
+    assert split_frames(b"\x03ab\x05", length_type=1) == ([b"ab"], 1)
+
+    with pytest.raises(InvalidLength) as exc_info:
+        split_frames(buffer + b"\x00\x00\x00\x01")
+    assert exc_info.value.index == 2
+    assert "(frame 2)" in str(exc_info.value)
+
+
 # --- 异常边界测试 ---
 
 
@@ -7,13 +7,58 @@
 4. 对象方法 (encode, decode)
 5. 字段编码模式 (Nested/Blob/Any)
 6. Union 类型支持 (Union[T, None], T | None)
+7. SimpleList 文本字段 (codec)
+8. 容器元素类型 (list[Struct], dict[K, Struct])
+9. 嵌套结构体 Schema (自引用, 子类实例)
+10. 字段钩子 (field_serializer, field_deserializer)
+11. 枚举字段
+12. 布尔字段
+13. Schema 兼容性检查
+14. 字段名与 Tag 互转 (normalize_struct)
+15. Decimal 字段
+16. datetime/date 字段
+17. IP 地址字段
+18. Schema 持久化 (serialize_schema, deserialize_schema)
 """
 
-from typing import Any
+import struct
+import warnings
+from datetime import date, datetime, timedelta, timezone
+from decimal import Decimal
+from enum import Enum
+from fractions import Fraction
+from ipaddress import IPv4Address, IPv6Address, IPv6Network
+from types import SimpleNamespace
+from typing import Any, ClassVar
 
 import pytest
 from pydantic import ValidationError
-from tarsio import BYTES, Field, Struct, StructDict, dumps
+from tarsio import (
+    BYTES,
+    DOUBLE,
+    FLOAT,
+    INT64,
+    STRING,
+    DecodeError,
+    Field,
+    Option,
+    SerializationInfo,
+    Struct,
+    StructDict,
+    denormalize_struct,
+    deserialize_schema,
+    dumps,
+    field_deserializer,
+    field_serializer,
+    get_field,
+    is_type_compatible,
+    loads,
+    loads_projection,
+    normalize_struct,
+    schema_compatible,
+    serialize_schema,
+)
+from tarsio import _core as core
 
 # --- 辅助模型 ---
 
@@ -169,6 +214,15 @@ def test_pattern_binary_blob(inner_data: StructDict) -> None:
     assert b"\x00\x02\x00\x64" in encoded  # 长度 + 数据载荷
 
 
+def test_large_binary_blob_matches_bytes() -> None:
+    """大体积二进制块的编码结果应与先编码内容再写入 bytes 一致."""
+    inner = StructDict({0: "x" * 70000, 1: list(range(1000))})
+    encoded = dumps(BlobPattern(param=inner))
+
+    assert encoded == dumps(SafeAnyPattern(param=dumps(inner)))
+    assert encoded.endswith(dumps(inner))
+
+
 def test_pattern_any_with_jcedict(inner_data: StructDict) -> None:
     """模式 C 传入 StructDict 时应推断为 STRUCT."""
     obj = AnyPattern(param=inner_data)
@@ -338,3 +392,924 @@ def test_union_multiple_error() -> None:
 
         class Model(Struct):
             f1: int | str | None = Field(id=0)
+
+
+class GbkText(Struct):
+    """以 GBK 编码存储为 SimpleList 的文本字段."""
+
+    title: str = Field(id=0, codec="gbk")
+    uid: int = Field(id=1, default=0)
+
+
+def test_codec_field_gbk_round_trip() -> None:
+    """声明 codec 的 str 字段应以该编码写为 SimpleList 并还原."""
+    data = dumps(GbkText(title="中文标题", uid=1))
+
+    payload = "中文标题".encode("gbk")
+    assert data.startswith(bytes([0x0D, 0x00, 0x00, len(payload)]) + payload)
+    assert loads(data, GbkText) == GbkText(title="中文标题", uid=1)
+
+
+def test_codec_requires_bytes_type() -> None:
+    """codec 与非 BYTES 的 tars_type 组合应抛出 TypeError."""
+    with pytest.raises(TypeError, match="codec requires BYTES"):
+
+        class Model(Struct):
+            f1: str = Field(id=0, tars_type=STRING, codec="gbk")
+
+
+class Team(Struct):
+    """List 字段元素为 Struct 的结构体."""
+
+    members: list[SimpleUser] = Field(id=0, default_factory=list)
+    ids: list[int] = Field(id=1, default_factory=list)
+
+
+def test_typed_list_struct_round_trip() -> None:
+    """list[Struct] 字段应按元素 Schema 编解码."""
+    team = Team(members=[SimpleUser(uid=1, name="a"), SimpleUser(uid=2)], ids=[3])
+
+    restored = loads(dumps(team), Team)
+
+    assert restored == team
+    assert isinstance(restored.members[0], SimpleUser)
+
+
+def test_list_field_accepts_tuple() -> None:
+    """List 字段的值为 tuple 时应与 list 编码一致, 其他类型报 TypeError."""
+    members = (SimpleUser(uid=1, name="a"), SimpleUser(uid=2))
+    expected = dumps(Team(members=list(members), ids=[3]))
+
+    assert dumps({"members": members, "ids": (3,)}, schema=Team) == expected
+    schema = [("xs", 0, 9, None, False)]
+    assert core.dumps({"xs": (1, "a")}, schema) == dumps(StructDict({0: [1, "a"]}))
+    with pytest.raises(TypeError):
+        dumps({"members": [], "ids": {3}}, schema=Team)
+
+
+def test_strict_list_rejects_mixed_elements() -> None:
+    """STRICT_LIST 下与声明的元素类型不符的元素应报告其索引与偏移量."""
+    data = dumps(StructDict({1: [1, "x"]}))
+
+    assert core.loads(data, Team)["ids"] == [1, "x"]
+    with pytest.raises(DecodeError, match="List element 1 has wire type String1") as e:
+        loads(data, Team, option=Option.STRICT_LIST)
+    assert "offset 5" in str(e.value)
+
+    mixed = dumps(StructDict({0: [StructDict({0: 1}), 5]}))
+    with pytest.raises(DecodeError, match="expected StructBegin"):
+        loads(mixed, Team, option=Option.STRICT_LIST)
+    valid = dumps(Team(members=[SimpleUser(uid=1)], ids=[1, 300]))
+    assert loads(valid, Team, option=Option.STRICT_LIST) == loads(valid, Team)
+
+
+def test_strict_struct_end_rejects_top_level_end() -> None:
+    """STRICT_STRUCT_END 下顶层的 StructEnd 应抛出 DecodeError, 默认静默停止."""
+    data = b"\x00\x01\x0b\x16\x01a"
+
+    assert loads(data, SimpleUser) == SimpleUser(uid=1)
+    assert loads(data) == {0: 1}
+    for decode in (
+        lambda: loads(data, SimpleUser, option=Option.STRICT_STRUCT_END),
+        lambda: loads(data, option=Option.STRICT_STRUCT_END),
+        lambda: loads_projection(
+            data, SimpleUser, {0}, option=Option.STRICT_STRUCT_END
+        ),
+    ):
+        with pytest.raises(DecodeError, match="Unexpected StructEnd at top level") as e:
+            decode()
+        assert "offset 2" in str(e.value)
+
+    nested = dumps(StructDict({0: StructDict({0: 1}), 1: "a"}))
+    assert loads(nested, option=Option.STRICT_STRUCT_END) == {0: {0: 1}, 1: "a"}
+
+
+def test_typed_list_decodes_elements_by_field_name() -> None:
+    """list[Struct] 的元素在 core 层应直接解码为字段名字典."""
+    data = dumps(Team(members=[SimpleUser(uid=1, name="a")]))
+
+    result = loads_projection(data, Team, {0})
+
+    assert result == {"members": [{"uid": 1, "name": "a"}]}
+
+
+class Directory(Struct):
+    """Map 字段带键/值类型的结构体."""
+
+    users: dict[str, SimpleUser] = Field(id=0, default_factory=dict)
+
+
+def test_typed_map_struct_value_round_trip() -> None:
+    """dict[K, Struct] 字段的值应按声明的 Schema 解码."""
+    directory = Directory(users={"a": SimpleUser(uid=1, name="a")})
+    data = dumps(directory)
+
+    assert loads(data, Directory) == directory
+    assert loads_projection(data, Directory, {0}) == {
+        "users": {"a": {"uid": 1, "name": "a"}}
+    }
+
+
+def test_typed_map_coerces_keys() -> None:
+    """线上为 Int 的键应被转换为声明的 str 类型."""
+    data = dumps(StructDict({0: {1: StructDict({0: 7})}}))
+
+    result = loads_projection(data, Directory, {0})
+
+    assert result == {"users": {"1": {"uid": 7, "name": "unknown"}}}
+
+
+class TreeNode(Struct):
+    """自引用的结构体."""
+
+    value: int = Field(id=0)
+    child: "TreeNode | None" = Field(id=1, default=None)
+    kids: "list[TreeNode]" = Field(id=2, default_factory=list)
+
+
+def test_recursive_struct_round_trip() -> None:
+    """自引用结构体应能编解码 (嵌套 Schema 惰性编译, 不会无限递归)."""
+    tree = TreeNode(
+        value=1,
+        child=TreeNode(value=2, child=TreeNode(value=3)),
+        kids=[TreeNode(value=4)],
+    )
+
+    assert loads(dumps(tree), TreeNode) == tree
+
+
+class ExtendedUser(SimpleUser):
+    """在 SimpleUser 基础上增加字段的子类."""
+
+    level: int = Field(id=2, default=0)
+
+
+def test_nested_subclass_uses_own_schema() -> None:
+    """嵌套字段的值为子类实例时应按子类自身的 Schema 编码."""
+    SimpleUser.__get_core_schema__()
+    holder = NestedUser(info=ExtendedUser(uid=1, name="a", level=5))
+
+    data = dumps(holder)
+
+    assert get_field(data, 0) == {0: 1, 1: "a", 2: 5}
+
+
+class Temperature(Struct):
+    """以 0.1 度为单位传输的温度."""
+
+    celsius: float = Field(id=0)
+    note: str = Field(id=1, default="")
+
+    @field_serializer("celsius")
+    def _to_wire(self, value: float, info: SerializationInfo) -> int:
+        return round(value * info.context.get("scale", 10))
+
+    @field_deserializer("celsius")
+    def _from_wire(cls, raw: int, info: SerializationInfo) -> float:
+        return raw / info.context.get("scale", 10)
+
+
+class Station(Struct):
+    """包含带钩子的嵌套结构体."""
+
+    temps: list[Temperature] = Field(id=0)
+
+
+def test_field_hooks_round_trip() -> None:
+    """字段钩子应在编解码时被调用并接收 context."""
+    data = dumps(Temperature(celsius=23.5, note="x"))
+
+    assert get_field(data, 0) == 235
+    assert loads(data, Temperature) == Temperature(celsius=23.5, note="x")
+
+    scaled = dumps(Temperature(celsius=1.5), context={"scale": 100})
+    assert get_field(scaled, 0) == 150
+    assert loads(scaled, Temperature, context={"scale": 100}).celsius == 1.5
+
+
+class _DecodeOnly(Struct):
+    """只声明了解码钩子的结构体, 编码钩子被调用时报错."""
+
+    value: int = Field(id=0)
+
+    @field_deserializer("value")
+    def _from_wire(cls, raw: int, info: SerializationInfo) -> int:
+        return raw * 2
+
+    def __tars_serialize_field__(
+        self, name: str, value: Any, context: dict[str, Any]
+    ) -> Any:
+        raise AssertionError(f"serializer hook called for {name}")
+
+
+class _Nullable(Struct):
+    """编码钩子对负数返回 None 的结构体."""
+
+    value: int = Field(id=0)
+    note: str = Field(id=1, default="")
+
+    @field_serializer("value")
+    def _to_wire(self, value: int, info: SerializationInfo) -> int | None:
+        return None if value < 0 else value
+
+
+def test_field_hooks_run_in_their_direction() -> None:
+    """编码钩子只在编码对象时调用, 解码钩子只在解码时调用."""
+    data = dumps(_DecodeOnly(value=3))
+    assert data == dumps(StructDict({0: 3}))
+    assert loads(data, _DecodeOnly) == _DecodeOnly(value=6)
+
+    schema = Temperature.__get_core_schema__()
+    assert get_field(core.dumps({"celsius": 7, "note": ""}, schema), 0) == 7
+    plain = SimpleNamespace(celsius=7, note="")
+    assert get_field(core.dumps(plain, schema), 0) == 7
+
+
+def test_field_serializer_returning_none() -> None:
+    """编码钩子返回 None 时跳过该字段, INCLUDE_NONE 下报错."""
+    assert dumps(_Nullable(value=-1)) == dumps(StructDict({1: ""}))
+    assert loads(dumps(_Nullable(value=-1)), dict) == {1: ""}
+    with pytest.raises(ValueError, match="Serializer of field 'value' \\(tag 0\\)"):
+        dumps(_Nullable(value=-1), option=Option.INCLUDE_NONE)
+
+
+def test_bytes_field_accepts_str() -> None:
+    """bytes 字段的 str 值应按 UTF-8 写入, STRICT_BYTES 下拒绝."""
+    schema = [("payload", 0, 13, None, False)]
+    expected = dumps({"payload": "hé".encode()}, schema=schema)
+
+    assert dumps({"payload": "hé"}, schema=schema) == expected
+    assert loads(expected, dict, bytes_mode="raw") == {0: "hé".encode()}
+    with pytest.raises(TypeError, match="Field tag 0 expects bytes, got str"):
+        dumps({"payload": "hé"}, schema=schema, option=Option.STRICT_BYTES)
+
+    class Blob(Struct):
+        payload: Any = Field(id=0, tars_type=BYTES)
+
+    assert dumps(Blob(payload="hé")) == expected
+
+
+def test_loads_return_consumed() -> None:
+    """return_consumed 应返回结果与消耗的字节数, 含顶层 StructEnd."""
+    first = dumps(Temperature(celsius=1.0, note="a")) + b"\x0b"
+    second = dumps(Temperature(celsius=2.0))
+    buffer = first + second
+
+    obj, consumed = loads(buffer, Temperature, return_consumed=True)
+    assert (obj, consumed) == (Temperature(celsius=1.0, note="a"), len(first))
+    obj, consumed = loads(buffer[consumed:], Temperature, return_consumed=True)
+    assert (obj, consumed) == (Temperature(celsius=2.0), len(second))
+
+    raw, consumed = loads(buffer, return_consumed=True)
+    assert isinstance(raw, StructDict)
+    assert (raw, consumed) == ({0: 10, 1: "a"}, len(first))
+    assert loads(buffer, dict, return_consumed=True) == ({0: 10, 1: "a"}, len(first))
+    assert loads(buffer, Temperature) == Temperature(celsius=1.0, note="a")
+
+
+class _RecordedTemperature(Struct):
+    """反序列化钩子会记录收到的原始值的温度."""
+
+    calls: ClassVar[list[int]] = []
+
+    celsius: float = Field(id=0)
+    note: str = Field(id=1, default="")
+
+    @field_deserializer("celsius")
+    def _from_wire(cls, raw: int, info: SerializationInfo) -> float:
+        cls.calls.append(raw)
+        return raw / 10
+
+
+def test_validate_first_fails_before_materializing() -> None:
+    """VALIDATE_FIRST 下结构非法的数据应在解码任何字段之前报错."""
+    data = dumps(Temperature(celsius=1.0, note="abc"))
+    truncated = data[:-1]
+
+    _RecordedTemperature.calls.clear()
+    with pytest.raises(DecodeError):
+        loads(truncated, _RecordedTemperature)
+    assert _RecordedTemperature.calls == [10]
+
+    _RecordedTemperature.calls.clear()
+    with pytest.raises(DecodeError, match="Buffer overflow"):
+        loads(truncated, _RecordedTemperature, option=Option.VALIDATE_FIRST)
+    assert _RecordedTemperature.calls == []
+    with pytest.raises(DecodeError):
+        loads(truncated, option=Option.VALIDATE_FIRST)
+
+    restored = loads(data, Temperature, option=Option.VALIDATE_FIRST)
+    assert restored == Temperature(celsius=1.0, note="abc")
+    assert loads(data, option=Option.VALIDATE_FIRST) == loads(data)
+
+
+def test_validate_first_accepts_decodable_data() -> None:
+    """VALIDATE_FIRST 不应拒绝解码器本身接受的数据."""
+    trailing = dumps(Temperature(celsius=1.0)) + b"\x0b\xff"
+    open_struct = b"\x1a\x00\x01"
+
+    assert loads(trailing, Temperature, option=Option.VALIDATE_FIRST) == loads(
+        trailing, Temperature
+    )
+    assert loads(open_struct, option=Option.VALIDATE_FIRST) == {1: {0: 1}}
+
+
+def test_field_hooks_in_nested_list() -> None:
+    """嵌套在容器中的结构体同样应调用字段钩子."""
+    station = Station(temps=[Temperature(celsius=-2.0), Temperature(celsius=0.5)])
+
+    data = dumps(station)
+
+    assert loads(data, Station) == station
+
+
+class Color(Enum):
+    """整数值枚举."""
+
+    RED = 1
+    GREEN = 2
+
+
+class Pixel(Struct):
+    """包含枚举字段的结构体."""
+
+    color: Color = Field(id=0)
+    fallback: Color | int = Field(id=1, default=Color.RED)
+
+
+def test_enum_field_round_trip() -> None:
+    """枚举字段应按整数编码, 解码时转换为枚举成员."""
+    data = dumps(Pixel(color=Color.GREEN))
+
+    assert data == b"\x00\x02\x10\x01"
+    assert get_field(data, 0) == 2
+    assert loads(data, Pixel).color is Color.GREEN
+
+
+def test_enum_field_unknown_value() -> None:
+    """未知的枚举值默认抛出异常, ENUM_PASSTHROUGH 下保留原始整数."""
+    data = dumps(StructDict({0: 1, 1: 9}))
+
+    with pytest.raises(ValueError, match="Unknown Color value 9 at tag 1"):
+        loads(data, Pixel)
+    pixel = loads(data, Pixel, option=Option.ENUM_PASSTHROUGH)
+    assert pixel.color is Color.RED
+    assert pixel.fallback == 9
+
+
+# --- 布尔字段 ---
+
+
+class Flags(Struct):
+    """包含布尔字段的结构体."""
+
+    enabled: bool = Field(id=0)
+    bits: list[bool] = Field(id=1, default_factory=list)
+
+
+def test_bool_field_round_trip() -> None:
+    """布尔字段应按最小整数编码, core 解码时直接返回 True/False."""
+    data = dumps(Flags(enabled=True, bits=[False, True]))
+
+    assert data == b"\x00\x01\x19\x00\x02\x0c\x00\x01"
+    decoded = core.loads(data, Flags)
+    assert decoded["enabled"] is True
+    assert [type(b) for b in decoded["bits"]] == [bool, bool]
+    assert loads(data, Flags) == Flags(enabled=True, bits=[False, True])
+    assert loads(data, dict) == {0: 1, 1: [0, 1]}
+
+
+# --- Decimal 字段 ---
+
+
+class Order(Struct):
+    """包含 Decimal 字段的结构体."""
+
+    price: Decimal = Field(id=0)
+    amount: Decimal = Field(id=1, decimal_mode="scaled_int", decimal_scale=2)
+
+
+def test_decimal_field_round_trip() -> None:
+    """Decimal 默认按字符串存储, scaled_int 按缩放后的整数存储, 解码均还原为 Decimal."""
+    order = Order(price=Decimal("12.30"), amount=Decimal("1.5"))
+    data = dumps(order)
+
+    assert loads(data, dict) == {0: "12.30", 1: 150}
+    decoded = loads(data, Order)
+    assert decoded == order
+    assert str(decoded.price) == "12.30"
+    assert isinstance(core.loads(data, Order)["amount"], Decimal)
+
+    with pytest.raises(ValueError, match="more than 2 decimal places"):
+        dumps(Order(price=Decimal(1), amount=Decimal("0.001")))
+    # 通用编码同样以字符串写入, 不经过 Double
+    assert loads(dumps(StructDict({0: Decimal("0.1")}))) == {0: "0.1"}
+    # 其他实现了 __float__ 的对象仍以 Double 写入
+    assert loads(dumps(StructDict({0: Fraction(1, 2)}))) == {0: 0.5}
+
+
+def test_decimal_mode_validation() -> None:
+    """decimal_mode 与 decimal_scale 不匹配或用于非 Decimal 字段时应抛出 TypeError."""
+    with pytest.raises(TypeError, match="requires decimal_scale"):
+        Field(id=0, decimal_mode="scaled_int")
+    with pytest.raises(TypeError, match="requires a Decimal field"):
+
+        class Model(Struct):
+            f1: int = Field(id=0, decimal_mode="string")
+
+
+# --- datetime 字段 ---
+
+
+class Event(Struct):
+    """包含 datetime/date 字段的结构体."""
+
+    created: datetime = Field(id=0)
+    updated: datetime = Field(id=1, datetime_unit="ms")
+    day: date | None = Field(id=2, default=None)
+
+
+def test_datetime_field_round_trip() -> None:
+    """datetime 按 Int8 时间戳编码, 解码为 UTC 时区的 datetime/date."""
+    tz = timezone(timedelta(hours=8))
+    event = Event(
+        created=datetime(2024, 1, 2, 8, 0, 0, tzinfo=tz),
+        updated=datetime(2024, 1, 2, 0, 0, 0, 123000, tzinfo=timezone.utc),
+        day=date(1969, 12, 31),
+    )
+    data = dumps(event)
+
+    assert data[:9] == b"\x03" + struct.pack(">q", 1704153600)
+    assert loads(data, dict) == {0: 1704153600, 1: 1704153600123, 2: -86400}
+    decoded = loads(data, Event)
+    assert decoded.created == event.created
+    assert decoded.created.tzinfo is timezone.utc
+    assert decoded.updated == event.updated
+    assert decoded.day == date(1969, 12, 31)
+
+    # Option.DATETIME_MILLIS 作用于通用编码与未指定单位的字段
+    generic = dumps(StructDict({0: event.created}), option=Option.DATETIME_MILLIS)
+    assert loads(generic) == {0: 1704153600000}
+    millis = dumps(event, option=Option.DATETIME_MILLIS)
+    assert loads(millis, dict)[0] == 1704153600000
+    assert loads(millis, Event, option=Option.DATETIME_MILLIS) == event
+
+
+def test_naive_datetime_rejected() -> None:
+    """无时区的 datetime 默认拒绝编码, NAIVE_DATETIME_UTC 下视为 UTC."""
+    naive = datetime(1970, 1, 1, 0, 1)
+    with pytest.raises(ValueError, match="Naive datetime at tag 0"):
+        dumps(StructDict({0: naive}))
+    data = dumps(StructDict({0: naive}), option=Option.NAIVE_DATETIME_UTC)
+    assert loads(data) == {0: 60}
+
+
+# --- IP 地址字段 ---
+
+
+class Peer(Struct):
+    """IPv4 与 IPv6 地址字段."""
+
+    v4: IPv4Address = Field(id=0)
+    v6: IPv6Address | None = Field(id=1, default=None)
+
+
+def test_ip_field_round_trip() -> None:
+    """IP 地址字段往返一致, IPv6 以 16 字节存储."""
+    peer = Peer(v4=IPv4Address("192.168.1.1"), v6=IPv6Address("2001:db8::1"))
+    for option in (Option.NONE, Option.LITTLE_ENDIAN):
+        data = peer.model_dump_tars(option=option)
+        assert Peer.model_validate_tars(data, option=option) == peer
+    assert loads(peer.model_dump_tars())[1] == IPv6Address("2001:db8::1").packed
+    assert Peer.model_validate_tars(Peer(v4="10.0.0.1").model_dump_tars()).v6 is None
+
+
+def test_ipv4_network_byte_order() -> None:
+    """IPv4 地址总是以大端 Int4 写入, 与数据包字节序无关."""
+    peer = Peer(v4=IPv4Address("1.2.3.4"))
+    assert peer.model_dump_tars() == b"\x02\x01\x02\x03\x04"
+    assert peer.model_dump_tars(option=Option.LITTLE_ENDIAN) == b"\x02\x01\x02\x03\x04"
+    # 高位为 1 的地址不会被当作负数
+    peer = Peer(v4=IPv4Address("255.0.0.1"))
+    assert Peer.model_validate_tars(peer.model_dump_tars()) == peer
+
+
+class _LittleInner(Struct):
+    """以小端序嵌入的结构体."""
+
+    value: int = Field(id=0)
+
+
+class _MixedEndian(Struct):
+    """外层大端, 嵌套结构体小端的混合结构体."""
+
+    a: int = Field(id=0)
+    inner: _LittleInner = Field(id=1, endian="little")
+
+
+class _RenamedUser(Struct):
+    """字段改名后通过 tars_alias 保留旧名称的结构体."""
+
+    uid: int = Field(id=0)
+    nickname: str = Field(id=1, default="", tars_alias="name")
+
+
+def test_field_tars_alias() -> None:
+    """tars_alias 应在按名称编码, normalize_struct 与验证时代替字段名."""
+    expected = dumps(_RenamedUser(uid=1, nickname="a"))
+
+    assert dumps({"uid": 1, "name": "a"}, schema=_RenamedUser) == expected
+    assert normalize_struct({"uid": 1, "name": "a"}, _RenamedUser) == {0: 1, 1: "a"}
+    assert _RenamedUser.model_validate({"uid": 1, "name": "a"}).nickname == "a"
+    assert loads(expected, _RenamedUser).nickname == "a"
+    assert core.loads(expected, _RenamedUser) == {"uid": 1, "nickname": "a"}
+    # 字段名优先于别名
+    both = {"uid": 1, "nickname": "b", "name": "a"}
+    assert normalize_struct(both, _RenamedUser) == {0: 1, 1: "b"}
+    assert dumps(both, schema=_RenamedUser) == dumps(_RenamedUser(uid=1, nickname="b"))
+    assert _RenamedUser.model_validate(both).nickname == "b"
+
+    schema = [
+        ("uid", 0, 0, None, False),
+        ("nickname", 1, 6, "", False, {"alias": "uid"}),
+    ]
+    with pytest.raises(ValueError, match="Alias 'uid' of field 'nickname' conflicts"):
+        dumps({}, schema=schema)
+
+
+def test_nested_struct_endian_override() -> None:
+    """声明了 endian 的嵌套结构体字段按该字节序编解码, 外层不受影响."""
+    obj = _MixedEndian(a=0x0102, inner=_LittleInner(value=0x0102))
+
+    data = dumps(obj)
+    # Tag 0: Int2 大端 01 02; Tag 1: StructBegin, 内部 Int2 小端 02 01
+    assert data.hex() == "0101021a0102010b"
+    assert loads(data, _MixedEndian) == obj
+    little = dumps(obj, Option.LITTLE_ENDIAN)
+    assert little.hex() == "0102011a0102010b"
+    assert loads(little, _MixedEndian, Option.LITTLE_ENDIAN) == obj
+
+    with pytest.raises(TypeError, match="endian requires a Struct field"):
+
+        class _Invalid(Struct):
+            value: int = Field(id=0, endian="little")
+
+
+# --- Schema 持久化 ---
+
+
+class Catalog(Struct):
+    """引用嵌套结构体, 枚举与逻辑类型的结构体."""
+
+    pixel: Pixel = Field(id=0)
+    price: Decimal = Field(id=1, decimal_mode="scaled_int", decimal_scale=2)
+    created: datetime = Field(id=2, datetime_unit="ms")
+    fallback: Color = Field(id=3, default=Color.GREEN)
+
+
+def test_serialize_schema_round_trip() -> None:
+    """加载后的 Schema 应与原 Schema 的解码结果一致."""
+    catalog = Catalog(
+        pixel=Pixel(color=Color.GREEN),
+        price=Decimal("1.25"),
+        created=datetime(2024, 1, 1, tzinfo=timezone.utc),
+    )
+    data = dumps(catalog)
+    blob = serialize_schema(Catalog)
+
+    assert serialize_schema(Catalog.__get_core_schema__()) == blob
+    compiled = deserialize_schema(blob)
+    assert core.loads(data, compiled) == core.loads(data, Catalog)
+    assert core.loads(data[:-2], compiled)["fallback"] is Color.GREEN
+
+
+def test_deserialize_schema_owner() -> None:
+    """指定 owner 时加载结果缓存到该类上, 字段钩子照常生效."""
+    station = Station(temps=[Temperature(celsius=1.5, note="n")])
+    data = dumps(station)
+
+    compiled = deserialize_schema(serialize_schema(Temperature), owner=Temperature)
+    assert Temperature.__dict__["__tars_compiled_schema__"] is compiled
+    assert loads(data, Station) == station
+
+
+def test_serialize_schema_logical_defaults() -> None:
+    """Decimal, datetime 与 ipaddress 默认值应原样还原."""
+    defaults = {
+        "price": Decimal("9.90"),
+        "at": datetime(2024, 1, 1, 8, 30, tzinfo=timezone(timedelta(hours=8))),
+        "day": date(2024, 1, 1),
+        "ttl": timedelta(seconds=90),
+        "addr": IPv4Address("10.0.0.1"),
+        "net": IPv6Network("fe80::/64"),
+    }
+    schema = [
+        (name, tag, 6, value, False) for tag, (name, value) in enumerate(defaults.items())
+    ]
+
+    blob = serialize_schema(schema)
+    assert core.loads(b"", deserialize_schema(blob)) == defaults
+
+
+def test_serialize_schema_unsupported_values() -> None:
+    """无法序列化的默认值与函数内定义的类型应抛出 TypeError."""
+    with pytest.raises(TypeError, match="Cannot serialize"):
+        serialize_schema([("a", 0, 0, object(), False)])
+    blob = serialize_schema([("b", 1, 6, "x", False)])
+
+    class LocalColor(Enum):
+        A = 1
+
+    with pytest.raises(TypeError, match="Cannot serialize"):
+        serialize_schema([("c", 0, 0, 1, False, {"enum": LocalColor})])
+    with pytest.raises(ValueError, match="header"):
+        deserialize_schema(b"TSCH")
+    with pytest.raises(DecodeError):
+        deserialize_schema(blob[:-1])
+
+
+# --- Schema 兼容性 ---
+
+
+class UserV2(Struct):
+    """SimpleUser 的兼容版本: 扩宽 uid 并新增字段."""
+
+    uid: int = Field(id=0, tars_type=INT64)
+    name: str = Field(id=1, default="unknown")
+    email: str = Field(id=2, default="")
+
+
+class UserV3(Struct):
+    """SimpleUser 的不兼容版本: uid 改为字符串, 删除 name."""
+
+    uid: str = Field(id=0)
+
+
+def test_schema_compatible() -> None:
+    """schema_compatible 应区分破坏性与非破坏性变更."""
+    report = schema_compatible(SimpleUser, UserV2)
+    assert report["compatible"]
+    assert report["breaking"] == []
+    assert [(c["tag"], c["kind"]) for c in report["non_breaking"]] == [
+        (0, "type_changed"),
+        (2, "added"),
+    ]
+
+    report = schema_compatible(SimpleUser, UserV3)
+    assert not report["compatible"]
+    assert report["breaking"] == [
+        {"tag": 0, "name": "uid", "kind": "type_changed", "old_type": 0, "new_type": 6},
+        {"tag": 1, "name": "name", "kind": "removed", "old_type": 6, "new_type": None},
+    ]
+
+
+def test_is_type_compatible() -> None:
+    """is_type_compatible 应与解码时的类型兼容规则一致."""
+    assert is_type_compatible(0, 3)
+    assert is_type_compatible(3, 12)
+    assert is_type_compatible(5, 4)
+    assert not is_type_compatible(4, 5)
+    assert is_type_compatible(4, 5, widen_floats=True)
+    assert is_type_compatible(5, 4, widen_floats=True)
+    assert is_type_compatible(7, 6)
+    assert not is_type_compatible(6, 0)
+    with pytest.raises(ValueError, match="Invalid type ID: 14"):
+        is_type_compatible(14, 0)
+
+
+def test_widen_floats() -> None:
+    """WIDEN_FLOATS 下 Float 字段应能读取 Double 并截断为单精度."""
+
+    class FloatV1(Struct):
+        value: float = Field(id=0, tars_type=FLOAT)
+
+    class DoubleV2(Struct):
+        value: float = Field(id=0, tars_type=DOUBLE)
+
+    wide = dumps(DoubleV2(value=0.1))
+    narrowed = struct.unpack(">f", struct.pack(">f", 0.1))[0]
+
+    assert loads(wide, FloatV1).value == 0.1
+    assert loads(wide, FloatV1, option=Option.WIDEN_FLOATS).value == narrowed
+    # 反方向: Double 字段读取 Float 不受选项影响
+    assert loads(dumps(FloatV1(value=0.5)), DoubleV2).value == 0.5
+
+
+class _SlottedPoint:
+    """提供 Schema 方法的 __slots__ 类."""
+
+    __slots__ = ("label", "x", "y")
+
+    def __init__(self) -> None:
+        raise AssertionError("__init__ should not be called")
+
+    @classmethod
+    def __get_core_schema__(cls) -> list[tuple]:
+        return [
+            ("x", 0, 0, 0, False),
+            ("y", 1, 0, 0, False),
+            ("label", 2, 6, "origin", False),
+        ]
+
+
+def test_slots_decode() -> None:
+    """SLOTS 下应直接填充 __slots__ 类的实例, 缺失字段使用默认值."""
+    data = dumps(StructDict({0: 3, 1: 4, 9: "unknown"}))
+
+    point = loads(data, _SlottedPoint, option=Option.SLOTS)
+
+    assert type(point) is _SlottedPoint
+    assert (point.x, point.y, point.label) == (3, 4, "origin")
+    partial = loads(data, _SlottedPoint, option=Option.SLOTS | Option.NO_DEFAULT_FILL)
+    assert not hasattr(partial, "label")
+
+
+def test_slots_ignored_for_struct() -> None:
+    """Struct 目标指定 SLOTS 时仍经过 Pydantic 验证."""
+
+    class Slotted(Struct):
+        uid: int = Field(id=0)
+        name: str = Field(id=1, default="anon")
+
+    user = loads(dumps(Slotted(uid=1)), Slotted, option=Option.SLOTS)
+
+    assert isinstance(user, Slotted)
+    assert user.model_fields_set == {"uid", "name"}
+    assert user.name == "anon"
+
+
+class _SlottedTags:
+    """默认值为可变列表的 __slots__ 类."""
+
+    __slots__ = ("tags",)
+
+    @classmethod
+    def __get_core_schema__(cls) -> list[tuple]:
+        return [("tags", 0, 9, [1], False)]
+
+
+def test_backfilled_defaults_not_shared() -> None:
+    """回填的可变默认值在每个解码结果中都是独立的副本."""
+    first = loads(b"", _SlottedTags, option=Option.SLOTS)
+    second = loads(b"", _SlottedTags, option=Option.SLOTS)
+    first.tags.append(2)
+
+    assert second.tags == [1]
+
+
+class _UnorderedTags:
+    """Tag 未按升序声明的 Schema 提供者."""
+
+    @classmethod
+    def __get_core_schema__(cls) -> list[tuple]:
+        return [("name", 1, 6, None, False), ("uid", 0, 0, None, False)]
+
+
+def test_unordered_schema_tags() -> None:
+    """Tag 未按升序排列时按声明顺序编码会警告一次, SORT_TAGS 下按升序编码且不警告."""
+    obj = SimpleNamespace(name="a", uid=1)
+
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        sorted_data = core.dumps(obj, _UnorderedTags, int(Option.SORT_TAGS))
+    assert sorted_data == b"\x00\x01\x16\x01a"
+    with pytest.warns(UserWarning, match="tag 0 after tag 1"):
+        data = core.dumps(obj, _UnorderedTags)
+    assert data == b"\x16\x01a\x00\x01"
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        assert core.dumps(obj, _UnorderedTags) == data
+
+
+class _ComputedLabel:
+    """字段值部分来自实例字典, 部分来自 property 的普通类."""
+
+    def __init__(self, x: int, y: int | None) -> None:
+        self.x = x
+        self.y = y
+
+    @property
+    def label(self) -> str:
+        return f"p{self.x}"
+
+
+def test_encode_unfiltered_attribute_lookup() -> None:
+    """不过滤字段时, 实例字典中没有的字段应回退到 getattr."""
+    schema = _SlottedPoint.__get_core_schema__()
+    expected = b"\x00\x03\x26\x02p3"
+
+    assert core.dumps(_ComputedLabel(3, None), schema) == expected
+    omitted = core.dumps(_ComputedLabel(3, 0), schema, int(Option.OMIT_DEFAULT))
+    assert omitted == expected
+    shadowed = _ComputedLabel(3, None)
+    shadowed.__dict__["label"] = "stale"
+    assert core.dumps(shadowed, schema) == expected
+    point = loads(expected, _SlottedPoint, option=Option.SLOTS)
+    assert core.dumps(point, schema) == dumps(StructDict({0: 3, 1: 0, 2: "p3"}))
+
+
+def test_not_none_fields_skip_none_check() -> None:
+    """类型不接受 None 的字段标记 not_none, 不过滤时 None 值按类型不匹配报错."""
+
+    class Mixed(Struct):
+        uid: int = Field(id=0)
+        tags: list[int] = Field(id=1, default_factory=list)
+        nick: str | None = Field(id=2, default=None)
+        extra: Any = Field(id=3, default=None)
+
+    schema = Mixed.__get_core_schema__()
+    extras = {field[0]: field[5] if len(field) > 5 else {} for field in schema}
+    assert extras["uid"]["not_none"] is True
+    assert extras["tags"]["not_none"] is True
+    assert "not_none" not in extras["nick"]
+    assert "not_none" not in extras["extra"]
+
+    assert loads(dumps(Mixed(uid=1)), Mixed) == Mixed(uid=1)
+    broken = Mixed.model_construct(uid=None, tags=[], nick=None, extra=None)
+    with pytest.raises(TypeError):
+        dumps(broken)
+    # 过滤字段的编码循环仍逐字段检查, None 值照常跳过
+    assert dumps(broken, option=Option.OMIT_DEFAULT) == dumps(StructDict({1: []}))
+
+
+def test_normalize_struct_round_trip() -> None:
+    """normalize_struct 与 denormalize_struct 应在字段名与 Tag 之间递归互转."""
+    payload = {
+        "members": [{"uid": 1, "name": "a"}],
+        "ids": [7],
+    }
+
+    normalized = normalize_struct(payload, Team)
+
+    assert normalized == {0: [{0: 1, 1: "a"}], 1: [7]}
+    assert isinstance(normalized, StructDict)
+    assert isinstance(normalized[0][0], StructDict)
+    assert dumps(normalized) == dumps(
+        Team(members=[SimpleUser(uid=1, name="a")], ids=[7])
+    )
+    assert denormalize_struct(loads(dumps(normalized)), Team) == payload
+    directory = normalize_struct({"users": {"k": {"uid": 2}}}, Directory)
+    assert loads(dumps(directory), Directory).users["k"].uid == 2
+    with pytest.raises(ValueError, match="Unknown field 'missing'"):
+        normalize_struct({"missing": 1}, SimpleUser)
+
+
+def test_dumps_dict_with_schema() -> None:
+    """指定 schema 时 dumps 应按字段名从普通 dict 取值编码."""
+    team = Team(members=[SimpleUser(uid=1, name="a")], ids=[7])
+    payload = {"members": [{"uid": 1, "name": "a"}], "ids": [7]}
+
+    assert dumps(payload, schema=Team) == dumps(team)
+    assert dumps({"uid": 1}, schema=SimpleUser) == b"\x00\x01"
+    assert (
+        dumps({"uid": 1, "name": "unknown"}, Option.OMIT_DEFAULT, schema=SimpleUser)
+        == b"\x00\x01"
+    )
+
+
+def test_dumps_dict_with_schema_rejects_bad_keys() -> None:
+    """按 schema 编码 dict 时缺少必填字段或混用 Tag 键应报错."""
+    with pytest.raises(ValueError, match="Missing required field 'uid' \\(tag 0\\)"):
+        dumps({"name": "a"}, schema=SimpleUser)
+    with pytest.raises(ValueError, match="must use field-name keys, got tag key 1"):
+        dumps({"uid": 1, 1: "a"}, schema=SimpleUser)
+    with pytest.raises(ValueError, match="tag key 1"):
+        dumps({"members": [{"uid": 1, 1: "a"}]}, schema=Team)
+
+    class FieldsSetDict(dict):
+        """带有 model_fields_set 属性的 dict, 不应被当作模型的已设置字段."""
+
+        model_fields_set: ClassVar[set[str]] = set()
+
+    payload = FieldsSetDict(uid=1, name="a")
+    assert dumps(payload, exclude_unset=True, schema=SimpleUser) == dumps(
+        SimpleUser(uid=1, name="a")
+    )
+
+
+def test_loads_generic_with_schema() -> None:
+    """通用解码指定 schema 时已知 Tag 以字段名为键, 未知 Tag 保留整数键."""
+    team = Team(members=[SimpleUser(uid=1, name="a")], ids=[7])
+    data = dumps(team) + dumps(StructDict({5: True}))
+
+    result = loads(data, schema=Team)
+
+    assert result == {"members": [{"uid": 1, "name": "a"}], "ids": [7], 5: 1}
+    assert type(result) is dict
+    assert loads(data, dict, schema=Team.__get_core_schema__()) == result
+    # 仅有部分 Schema 时, 缺失的字段保留整数 Tag
+    partial = [field for field in Team.__get_core_schema__() if field[0] == "ids"]
+    assert loads(data, schema=partial) == {0: [{0: 1, 1: "a"}], "ids": [7], 5: 1}
+    with pytest.raises(ValueError, match="ORDERED_PAIRS"):
+        loads(data, option=Option.ORDERED_PAIRS, schema=Team)
+    with pytest.raises(ValueError, match="schema is only supported"):
+        loads(dumps(team), Team, schema=Team)
+
+
+def test_oversized_list_size_rejected() -> None:
+    """Schema 解码同样应在分配前拒绝超过剩余字节数的容器大小."""
+    # Tag 0 为 List, 大小为 Int4 0x7fffffff, 之后没有任何元素
+    data = b"\x09\x02\x7f\xff\xff\xff"
+
+    with pytest.raises(DecodeError, match="Container size 2147483647 exceeds"):
+        loads(data, FactoryUser)
@@ -0,0 +1,104 @@
+use crate::codec::consts::JceType;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// Fixed8/Fixed16/Fixed32/Fixed64 仅宽度不同, 由宏生成以保持三者行为一致.
+macro_rules! fixed_int {
+    ($name:ident, $int:ty, $jce_type:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// 通用编码时固定以该宽度写入, 不做最小宽度压缩.
+        #[pyclass(frozen, eq, hash, module = "tarsio._core")]
+        #[derive(PartialEq, Eq, Hash)]
+        pub struct $name {
+            /// 整数值.
+            #[pyo3(get)]
+            value: i64,
+        }
+
+        #[pymethods]
+        impl $name {
+            #[new]
+            /// 创建定宽整数.
+            ///
+            /// Args:
+            ///     value (int): 整数值, 必须在该宽度的取值范围内.
+            ///
+            /// Raises:
+            ///     ValueError: 值超出范围.
+            fn new(value: i64) -> PyResult<Self> {
+                <$int>::try_from(value).map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "{} value {} out of range",
+                        stringify!($name),
+                        value
+                    ))
+                })?;
+                Ok(Self { value })
+            }
+
+            fn __int__(&self) -> i64 {
+                self.value
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{}({})", stringify!($name), self.value)
+            }
+        }
+
+        impl $name {
+            const JCE_TYPE: JceType = $jce_type;
+        }
+    };
+}
+
+fixed_int!(
+    Fixed8,
+    i8,
+    JceType::Int1,
+    "固定以 Int1 (1 字节) 编码的整数."
+);
+fixed_int!(
+    Fixed16,
+    i16,
+    JceType::Int2,
+    "固定以 Int2 (2 字节) 编码的整数."
+);
+fixed_int!(
+    Fixed32,
+    i32,
+    JceType::Int4,
+    "固定以 Int4 (4 字节) 编码的整数."
+);
+fixed_int!(
+    Fixed64,
+    i64,
+    JceType::Int8,
+    "固定以 Int8 (8 字节) 编码的整数."
+);
+
+/// 若值为定宽整数包装类型, 返回其值与对应的 JCE 整数类型.
+pub(crate) fn extract_fixed(value: &Bound<'_, PyAny>) -> Option<(i64, JceType)> {
+    if let Ok(v) = value.cast::<Fixed64>() {
+        Some((v.get().value, Fixed64::JCE_TYPE))
+    } else if let Ok(v) = value.cast::<Fixed32>() {
+        Some((v.get().value, Fixed32::JCE_TYPE))
+    } else if let Ok(v) = value.cast::<Fixed16>() {
+        Some((v.get().value, Fixed16::JCE_TYPE))
+    } else if let Ok(v) = value.cast::<Fixed8>() {
+        Some((v.get().value, Fixed8::JCE_TYPE))
+    } else {
+        None
+    }
+}
+
+/// 将线上的整数按其宽度还原为定宽整数包装类型; ZeroTag 仍返回 int (重新编码时同样写为 ZeroTag).
+pub(crate) fn fixed_to_py(py: Python<'_>, value: i64, jce_type: JceType) -> PyResult<Py<PyAny>> {
+    Ok(match jce_type {
+        JceType::Int1 => Py::new(py, Fixed8 { value })?.into_any(),
+        JceType::Int2 => Py::new(py, Fixed16 { value })?.into_any(),
+        JceType::Int4 => Py::new(py, Fixed32 { value })?.into_any(),
+        JceType::Int8 => Py::new(py, Fixed64 { value })?.into_any(),
+        _ => value.into_pyobject(py)?.into_any().unbind(),
+    })
+}
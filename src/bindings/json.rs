@@ -0,0 +1,398 @@
+use crate::bindings::serde::{
+    BytesMode, DecodeLimitGuard, DepthLimitGuard, InputData, MapFactoryGuard, StrEncodingGuard,
+    TagHooksGuard, TextPolicyGuard, decode_generic_struct, encode_generic_struct, max_depth,
+    string_writer_config, text_control_mask,
+};
+use crate::codec::consts::MAX_DEPTH;
+use crate::codec::reader::JceReader;
+use crate::codec::writer::JceWriter;
+use byteorder::{BigEndian, LittleEndian};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBytes, PyDict, PyFloat, PyList, PyString, PyTuple, PyType};
+
+/// JSON 中表示二进制数据的对象键, 值为 Base64 编码.
+const BYTES_KEY: &str = "$bytes";
+
+/// JSON 中表示 Map 的对象键, 值为 `[键, 值]` 数组的列表.
+const MAP_KEY: &str = "$map";
+
+static ORDERED_DICT_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static STRUCT_DICT_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+#[pyfunction]
+#[pyo3(signature = (data, little_endian=false, bytes_mode=2, max_depth=None))]
+/// 将 JCE 数据通用解码为 JSON 文本.
+///
+/// 先按 `loads_generic` 的规则解码, 再转换为 JSON: 结构体输出为以 Tag 字符串为键的对象,
+/// Map 输出为 `{"$map": [[键, 值], ...]}` (键保留原始类型), 二进制数据输出为
+/// `{"$bytes": "<Base64>"}`, NaN 与无穷大输出为 `null`.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     little_endian (bool): 是否使用小端序.
+///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto), 3=View 按 Raw 处理.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     str: JSON 文本.
+pub fn to_json(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    little_endian: bool,
+    bytes_mode: u8,
+    max_depth: Option<usize>,
+) -> PyResult<String> {
+    let mode = match BytesMode::from(bytes_mode) {
+        BytesMode::View => BytesMode::Raw,
+        mode => mode,
+    };
+    // Map 解码为 OrderedDict, 以便与结构体 (精确的 dict) 区分
+    let ordered_dict = ORDERED_DICT_TYPE.import(py, "collections", "OrderedDict")?;
+    let _factory = MapFactoryGuard::new(Some(ordered_dict.as_any()));
+    let _hooks = TagHooksGuard::new(None);
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let _limits = DecodeLimitGuard::new(None, None);
+    let _text = TextPolicyGuard::new(text_control_mask(None)?);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context = PyDict::new(py).into_any();
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let value = if little_endian {
+        let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+        decode_generic_struct(py, &mut reader, 1, &context, mode, 0)?
+    } else {
+        let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+        decode_generic_struct(py, &mut reader, 0, &context, mode, 0)?
+    };
+    let b2a_base64 = py.import("binascii")?.getattr("b2a_base64")?;
+    let tree = to_json_value(py, value.bind(py), &b2a_base64)?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("ensure_ascii", false)?;
+    kwargs.set_item("separators", (",", ":"))?;
+    py.import("json")?
+        .call_method("dumps", (tree,), Some(&kwargs))?
+        .extract()
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, little_endian=false, max_depth=None, string1_max=255, force_string4=false))]
+/// 将 `to_json` 生成的 JSON 文本编码回 JCE 数据.
+///
+/// 顶层必须是以 Tag 为键的 JSON 对象. 嵌套对象中, 仅含 `$bytes` 键的对象编码为 SimpleList,
+/// 仅含 `$map` 键的对象编码为 Map, 其余对象 (包括空对象) 按结构体编码, 其键必须全部为 Tag.
+/// 其余值按 `dumps_generic` 的规则编码; 布尔值编码为整数, `null` 无法编码.
+///
+/// Args:
+///     text (str): JSON 文本.
+///     little_endian (bool): 是否使用小端序.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
+///
+/// Returns:
+///     bytes: JCE 二进制数据.
+pub fn from_json(
+    py: Python<'_>,
+    text: &str,
+    little_endian: bool,
+    max_depth: Option<usize>,
+    string1_max: i64,
+    force_string4: bool,
+) -> PyResult<Py<PyBytes>> {
+    let config = string_writer_config(0, string1_max, force_string4)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    // 对象解析为键值对元组, 保留重复的键以便报错
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("object_pairs_hook", py.get_type::<PyTuple>())?;
+    let value = py
+        .import("json")?
+        .call_method("loads", (text,), Some(&kwargs))?;
+    let Ok(pairs) = value.cast::<PyTuple>() else {
+        return Err(PyTypeError::new_err(
+            "Top-level JSON value must be an object",
+        ));
+    };
+    let a2b_base64 = py.import("binascii")?.getattr("a2b_base64")?;
+    let root = struct_from_json(py, pairs, &a2b_base64, 0)?;
+    let root = root.cast::<PyDict>()?;
+    let context = PyDict::new(py).into_any();
+    if little_endian {
+        let mut writer = JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::new());
+        writer.set_config(config);
+        encode_generic_struct(py, &mut writer, root, 0, &context, 0)?;
+        Ok(PyBytes::new(py, writer.get_buffer()).unbind())
+    } else {
+        let mut writer = JceWriter::new();
+        writer.set_config(config);
+        encode_generic_struct(py, &mut writer, root, 0, &context, 0)?;
+        Ok(PyBytes::new(py, writer.get_buffer()).unbind())
+    }
+}
+
+/// 将通用解码的结果转换为可直接 `json.dumps` 的值.
+///
+/// 精确的 dict 为结构体, OrderedDict 为 Map (见 `to_json` 设置的 Map 工厂).
+fn to_json_value<'py>(
+    py: Python<'py>,
+    value: &Bound<'py, PyAny>,
+    b2a_base64: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if let Ok(fields) = value.cast_exact::<PyDict>() {
+        let out = PyDict::new(py);
+        for (tag, field) in fields {
+            out.set_item(tag.str()?, to_json_value(py, &field, b2a_base64)?)?;
+        }
+        Ok(out.into_any())
+    } else if let Ok(map) = value.cast::<PyDict>() {
+        let entries = PyList::empty(py);
+        for (k, v) in map {
+            entries.append([
+                to_json_value(py, &k, b2a_base64)?,
+                to_json_value(py, &v, b2a_base64)?,
+            ])?;
+        }
+        let out = PyDict::new(py);
+        out.set_item(MAP_KEY, entries)?;
+        Ok(out.into_any())
+    } else if let Ok(items) = value.cast::<PyList>() {
+        let out = PyList::empty(py);
+        for item in items {
+            out.append(to_json_value(py, &item, b2a_base64)?)?;
+        }
+        Ok(out.into_any())
+    } else if value.is_instance_of::<PyBytes>() {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("newline", false)?;
+        let encoded = b2a_base64.call((value,), Some(&kwargs))?;
+        let out = PyDict::new(py);
+        out.set_item(BYTES_KEY, encoded.call_method1("decode", ("ascii",))?)?;
+        Ok(out.into_any())
+    } else if let Ok(f) = value.cast::<PyFloat>()
+        && !f.value().is_finite()
+    {
+        Ok(py.None().into_bound(py))
+    } else {
+        Ok(value.clone())
+    }
+}
+
+/// 将 JSON 值转换为通用编码接受的 Python 值.
+fn value_from_json<'py>(
+    py: Python<'py>,
+    value: &Bound<'py, PyAny>,
+    a2b_base64: &Bound<'py, PyAny>,
+    depth: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
+    }
+    if let Ok(pairs) = value.cast::<PyTuple>() {
+        struct_from_json(py, pairs, a2b_base64, depth)
+    } else if let Ok(items) = value.cast::<PyList>() {
+        let out = PyList::empty(py);
+        for item in items {
+            out.append(value_from_json(py, &item, a2b_base64, depth + 1)?)?;
+        }
+        Ok(out.into_any())
+    } else if value.is_none() {
+        Err(PyValueError::new_err("JSON null has no JCE representation"))
+    } else {
+        Ok(value.clone())
+    }
+}
+
+/// 将 JSON 对象 (键值对元组) 转换为结构体, 或 `$bytes`/`$map` 表示的二进制数据与 Map.
+fn struct_from_json<'py>(
+    py: Python<'py>,
+    pairs: &Bound<'py, PyTuple>,
+    a2b_base64: &Bound<'py, PyAny>,
+    depth: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
+    }
+    if pairs.len() == 1 {
+        let (key, value): (String, Bound<'py, PyAny>) = pairs.get_item(0)?.extract()?;
+        if key == BYTES_KEY {
+            let text = value.cast_into::<PyString>().map_err(|_| {
+                PyTypeError::new_err(format!("{} value must be a string", BYTES_KEY))
+            })?;
+            return a2b_base64
+                .call1((text,))
+                .map_err(|_| PyValueError::new_err("Invalid base64 in $bytes"));
+        }
+        if key == MAP_KEY {
+            return map_from_json(py, &value, a2b_base64, depth);
+        }
+    }
+    let fields = STRUCT_DICT_TYPE
+        .import(py, "tarsio", "StructDict")?
+        .call0()?;
+    let fields = fields.cast::<PyDict>()?;
+    for pair in pairs {
+        let (key, value): (String, Bound<'py, PyAny>) = pair.extract()?;
+        let tag = parse_tag(&key)
+            .ok_or_else(|| PyValueError::new_err(format!("Invalid tag key '{}'", key)))?;
+        if fields.contains(tag)? {
+            return Err(PyValueError::new_err(format!(
+                "Duplicate tag key '{}' in JSON object",
+                key
+            )));
+        }
+        fields.set_item(tag, value_from_json(py, &value, a2b_base64, depth + 1)?)?;
+    }
+    Ok(fields.clone().into_any())
+}
+
+/// 将 `$map` 的 `[[键, 值], ...]` 列表转换为 dict, 重复的键视为错误.
+fn map_from_json<'py>(
+    py: Python<'py>,
+    entries: &Bound<'py, PyAny>,
+    a2b_base64: &Bound<'py, PyAny>,
+    depth: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    let entries = entries
+        .cast::<PyList>()
+        .map_err(|_| PyTypeError::new_err(format!("{} value must be a list", MAP_KEY)))?;
+    let map = PyDict::new(py);
+    for entry in entries {
+        let entry = entry
+            .cast_into::<PyList>()
+            .ok()
+            .filter(|e| e.len() == 2)
+            .ok_or_else(|| {
+                PyTypeError::new_err(format!("{} entries must be [key, value]", MAP_KEY))
+            })?;
+        let key = value_from_json(py, &entry.get_item(0)?, a2b_base64, depth + 1)?;
+        if map.contains(&key)? {
+            return Err(PyValueError::new_err(format!(
+                "Duplicate map key {}",
+                key.repr()?
+            )));
+        }
+        let value = value_from_json(py, &entry.get_item(1)?, a2b_base64, depth + 1)?;
+        map.set_item(key, value)?;
+    }
+    Ok(map.into_any())
+}
+
+/// 解析规范的十进制 Tag 字符串 (不允许前导零或符号).
+fn parse_tag(key: &str) -> Option<u8> {
+    key.parse::<u8>().ok().filter(|t| t.to_string() == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json_keeps_structs_and_maps_distinct() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            // Tag 0: Map {1: 1, "1": 2}, Tag 1: 空结构体
+            let data = PyBytes::new(py, b"\x08\x00\x02\x00\x01\x10\x01\x06\x011\x10\x02\x1a\x0b");
+            let text = to_json(py, data.as_any(), false, 2, None).unwrap();
+            assert_eq!(text, r#"{"0":{"$map":[[1,1],["1",2]]},"1":{}}"#);
+        });
+    }
+
+    #[test]
+    fn test_parse_tag_is_canonical() {
+        assert_eq!(parse_tag("0"), Some(0));
+        assert_eq!(parse_tag("255"), Some(255));
+        assert_eq!(parse_tag("01"), None);
+        assert_eq!(parse_tag("+1"), None);
+        assert_eq!(parse_tag("256"), None);
+    }
+
+    /// 黄金向量: `tests/vectors/<name>.json` 为期望的通用解码结果 (`to_json` 格式),
+    /// `<name>.be.bin` 与 `<name>.le.bin` 为参考编码器以对应字节序生成的数据.
+    ///
+    /// 此处校验解码方向; 编码方向需要导入 `tarsio.StructDict`, 由 Python 测试覆盖.
+    #[cfg(feature = "golden-vectors")]
+    mod golden {
+        use super::*;
+        use std::path::{Path, PathBuf};
+
+        struct GoldenVector {
+            name: String,
+            json: String,
+            encodings: Vec<(bool, Vec<u8>)>,
+        }
+
+        fn load_vectors(dir: &Path) -> Vec<GoldenVector> {
+            let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+                .expect("vector directory should be readable")
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            paths.sort();
+            paths
+                .into_iter()
+                .map(|path| {
+                    let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+                    let encodings = [(false, "be"), (true, "le")]
+                        .into_iter()
+                        .filter_map(|(little_endian, suffix)| {
+                            let bin = dir.join(format!("{}.{}.bin", name, suffix));
+                            std::fs::read(bin).ok().map(|bytes| (little_endian, bytes))
+                        })
+                        .collect();
+                    GoldenVector {
+                        json: std::fs::read_to_string(&path).unwrap(),
+                        name,
+                        encodings,
+                    }
+                })
+                .collect()
+        }
+
+        /// 以 JSON 语义比较两段文本, 忽略空白与对象键顺序.
+        fn assert_json_eq(py: Python<'_>, actual: &str, expected: &str, context: &str) {
+            let json = py.import("json").unwrap();
+            let actual_value = json.call_method1("loads", (actual,)).unwrap();
+            let expected_value = json.call_method1("loads", (expected,)).unwrap();
+            assert!(
+                actual_value.eq(&expected_value).unwrap(),
+                "{}: decoded {} != expected {}",
+                context,
+                actual,
+                expected
+            );
+        }
+
+        #[test]
+        fn test_golden_vectors() {
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+            let vectors = load_vectors(&dir);
+            assert!(!vectors.is_empty(), "no vectors in {}", dir.display());
+
+            #[allow(deprecated)]
+            pyo3::prepare_freethreaded_python();
+            Python::attach(|py| {
+                for vector in &vectors {
+                    assert!(
+                        !vector.encodings.is_empty(),
+                        "{}: no .bin files",
+                        vector.name
+                    );
+                    for (little_endian, bytes) in &vector.encodings {
+                        let context = format!(
+                            "{} ({})",
+                            vector.name,
+                            if *little_endian { "le" } else { "be" }
+                        );
+                        let data = PyBytes::new(py, bytes);
+                        let decoded = to_json(py, data.as_any(), *little_endian, 0, None)
+                            .unwrap_or_else(|e| panic!("{}: decode failed: {}", context, e));
+                        assert_json_eq(py, &decoded, &vector.json, &context);
+                    }
+                }
+            });
+        }
+    }
+}
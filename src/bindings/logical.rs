@@ -0,0 +1,407 @@
+use crate::bindings::serde::{OPT_DATETIME_MILLIS, OPT_NAIVE_DATETIME_UTC};
+use crate::bindings::str_view::JceStr;
+use crate::codec::consts::JceType;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{
+    PyBytes, PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyInt, PyString,
+    PyTimeAccess, PyType, PyTzInfo,
+};
+
+static DECIMAL_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static IPV4_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static IPV6_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// `decimal.Decimal` 类型.
+pub(crate) fn decimal_type(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    DECIMAL_TYPE.import(py, "decimal", "Decimal")
+}
+
+/// Decimal 字段的存储方式.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalMode {
+    /// 以十进制字符串存储 (String1/String4), 无损.
+    String,
+    /// 乘以 `10^scale` 后以整数存储, 小数位数超过 `scale` 时拒绝编码.
+    ScaledInt(u32),
+}
+
+/// 时间戳的单位.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Seconds,
+    Millis,
+}
+
+impl TimeUnit {
+    /// 字段未指定单位时, 按 `OPT_DATETIME_MILLIS` 选择.
+    pub(crate) fn from_options(options: i64) -> Self {
+        if options & OPT_DATETIME_MILLIS != 0 {
+            TimeUnit::Millis
+        } else {
+            TimeUnit::Seconds
+        }
+    }
+}
+
+/// 逻辑类型: 线上以 JCE 基础类型存储, 在 Python 侧表现为特定对象的字段.
+///
+/// 编码时先由 `to_wire` 转换为基础值, 再按字段的 `tars_type` 写入;
+/// 解码时按 `tars_type` 读取后由 `from_wire` 还原.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalType {
+    Decimal(DecimalMode),
+    /// `datetime` (或 `date_only` 时的 `date`), 以 Int8 的 Unix 时间戳存储.
+    /// `unit` 为 None 时按编解码选项选择单位.
+    Datetime {
+        date_only: bool,
+        unit: Option<TimeUnit>,
+    },
+    /// `IPv4Address`, 以网络字节序 (大端) 的 Int4 存储, 不受数据包字节序影响.
+    Ipv4,
+    /// `IPv6Address`, 以 16 字节的 SimpleList 存储.
+    Ipv6,
+}
+
+impl LogicalType {
+    /// 从 Schema 额外描述中的 `decimal` 项解析: `"string"` 或整数 scale.
+    pub fn decimal_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(scale) = obj.extract::<u32>() {
+            return Ok(LogicalType::Decimal(DecimalMode::ScaledInt(scale)));
+        }
+        match obj.extract::<String>() {
+            Ok(mode) if mode == "string" => Ok(LogicalType::Decimal(DecimalMode::String)),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid decimal mode: {}",
+                obj.repr()?
+            ))),
+        }
+    }
+
+    /// 从 Schema 额外描述中的 `datetime` 项解析: `(kind, unit)`,
+    /// `kind` 为 `"datetime"` 或 `"date"`, `unit` 为 `"s"`, `"ms"` 或 None.
+    pub fn datetime_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let invalid = || {
+            PyValueError::new_err(format!(
+                "Invalid datetime spec: {}",
+                obj.repr().map_or_else(|_| "?".into(), |r| r.to_string())
+            ))
+        };
+        let (kind, unit): (String, Option<String>) = obj.extract().map_err(|_| invalid())?;
+        let date_only = match kind.as_str() {
+            "datetime" => false,
+            "date" => true,
+            _ => return Err(invalid()),
+        };
+        let unit = match unit.as_deref() {
+            None => None,
+            Some("s") => Some(TimeUnit::Seconds),
+            Some("ms") => Some(TimeUnit::Millis),
+            Some(_) => return Err(invalid()),
+        };
+        Ok(LogicalType::Datetime { date_only, unit })
+    }
+
+    /// 从 Schema 额外描述中的 `ip` 项解析: 4 或 6.
+    pub fn ip_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        match obj.extract::<u8>() {
+            Ok(4) => Ok(LogicalType::Ipv4),
+            Ok(6) => Ok(LogicalType::Ipv6),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid IP version: {}",
+                obj.repr()?
+            ))),
+        }
+    }
+
+    /// 写入时固定使用的整数宽度 (不做最小宽度压缩).
+    ///
+    /// `Ipv4` 同样固定为 Int4, 但需按网络字节序写入, 由调用方单独处理.
+    pub fn fixed_int_type(&self) -> Option<JceType> {
+        match self {
+            LogicalType::Datetime { .. } => Some(JceType::Int8),
+            LogicalType::Ipv4 => Some(JceType::Int4),
+            LogicalType::Decimal(_) | LogicalType::Ipv6 => None,
+        }
+    }
+
+    /// 将字段值转换为待写入的基础值.
+    pub fn to_wire<'py>(
+        &self,
+        value: &Bound<'py, PyAny>,
+        tag: u8,
+        options: i64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            LogicalType::Decimal(mode) => decimal_to_wire(value, *mode, tag),
+            LogicalType::Datetime { unit, .. } => {
+                if value.is_instance_of::<PyInt>() {
+                    return Ok(value.clone());
+                }
+                let unit = unit.unwrap_or_else(|| TimeUnit::from_options(options));
+                let timestamp =
+                    datetime_to_timestamp(value, unit, tag, options)?.ok_or_else(|| {
+                        PyTypeError::new_err(format!("Expected datetime or date at tag {}", tag))
+                    })?;
+                Ok(timestamp.into_pyobject(value.py())?.into_any())
+            }
+            LogicalType::Ipv4 => {
+                let py = value.py();
+                let address = if value.is_instance_of::<PyInt>() {
+                    value.clone()
+                } else {
+                    IPV4_TYPE
+                        .import(py, "ipaddress", "IPv4Address")?
+                        .call1((value,))?
+                };
+                Ok(py.get_type::<PyInt>().call1((address,))?)
+            }
+            LogicalType::Ipv6 => {
+                let py = value.py();
+                if value.is_instance_of::<PyBytes>() {
+                    return Ok(value.clone());
+                }
+                IPV6_TYPE
+                    .import(py, "ipaddress", "IPv6Address")?
+                    .call1((value,))?
+                    .getattr(intern!(py, "packed"))
+            }
+        }
+    }
+
+    /// 将解码得到的基础值还原为字段值.
+    ///
+    /// 无法识别的线上值原样返回, 交由后续验证处理.
+    pub fn from_wire(
+        &self,
+        py: Python<'_>,
+        raw: Py<PyAny>,
+        tag: u8,
+        options: i64,
+    ) -> PyResult<Py<PyAny>> {
+        match self {
+            LogicalType::Decimal(mode) => decimal_from_wire(py, raw, *mode, tag),
+            LogicalType::Datetime { date_only, unit } => {
+                let Ok(timestamp) = raw.extract::<i64>(py) else {
+                    return Ok(raw);
+                };
+                let unit = unit.unwrap_or_else(|| TimeUnit::from_options(options));
+                let datetime = timestamp_to_datetime(py, timestamp, unit, tag)?;
+                if *date_only {
+                    return Ok(datetime.call_method0("date")?.unbind());
+                }
+                Ok(datetime.into_any().unbind())
+            }
+            LogicalType::Ipv4 => {
+                // 按其他宽度写入的整数按有符号的 32 位值解释
+                let Ok(value) = raw.extract::<i64>(py) else {
+                    return Ok(raw);
+                };
+                let value = u32::try_from(value)
+                    .or_else(|_| i32::try_from(value).map(|v| v as u32))
+                    .map_err(|_| {
+                        PyValueError::new_err(format!(
+                            "Invalid IPv4 address value {} at tag {}",
+                            value, tag
+                        ))
+                    })?;
+                Ok(IPV4_TYPE
+                    .import(py, "ipaddress", "IPv4Address")?
+                    .call1((value,))?
+                    .unbind())
+            }
+            LogicalType::Ipv6 => {
+                let bound = raw.bind(py);
+                match bound.cast::<PyBytes>() {
+                    Ok(bytes) if bytes.as_bytes().len() == 16 => Ok(IPV6_TYPE
+                        .import(py, "ipaddress", "IPv6Address")?
+                        .call1((bytes,))?
+                        .unbind()),
+                    Ok(bytes) => Err(PyValueError::new_err(format!(
+                        "IPv6 address at tag {} must be 16 bytes, got {}",
+                        tag,
+                        bytes.as_bytes().len()
+                    ))),
+                    Err(_) => Ok(raw),
+                }
+            }
+        }
+    }
+}
+
+/// 将 `datetime`/`date` 转换为 Unix 时间戳, 其他类型返回 None.
+///
+/// `date` 按当天 UTC 零点计算; 不足一个单位的部分向下取整.
+/// 无时区的 `datetime` 默认抛出 ValueError, `OPT_NAIVE_DATETIME_UTC` 下视为 UTC.
+pub(crate) fn datetime_to_timestamp(
+    value: &Bound<'_, PyAny>,
+    unit: TimeUnit,
+    tag: u8,
+    options: i64,
+) -> PyResult<Option<i64>> {
+    let py = value.py();
+    let (days, seconds, micros) = if let Ok(dt) = value.cast::<PyDateTime>() {
+        let utc = PyTzInfo::utc(py)?;
+        let aware = if dt.call_method0("utcoffset")?.is_none() {
+            if options & OPT_NAIVE_DATETIME_UTC == 0 {
+                return Err(PyValueError::new_err(format!(
+                    "Naive datetime at tag {}; attach a timezone or use \
+                     Option.NAIVE_DATETIME_UTC",
+                    tag
+                )));
+            }
+            PyDateTime::new(
+                py,
+                dt.get_year(),
+                dt.get_month(),
+                dt.get_day(),
+                dt.get_hour(),
+                dt.get_minute(),
+                dt.get_second(),
+                dt.get_microsecond(),
+                Some(&utc),
+            )?
+            .into_any()
+        } else {
+            dt.clone().into_any()
+        };
+        let epoch = PyDateTime::new(py, 1970, 1, 1, 0, 0, 0, 0, Some(&utc))?;
+        let delta = aware.sub(epoch)?;
+        let delta = delta.cast::<PyDelta>()?;
+        (
+            i64::from(delta.get_days()),
+            i64::from(delta.get_seconds()),
+            i64::from(delta.get_microseconds()),
+        )
+    } else if let Ok(date) = value.cast::<PyDate>() {
+        // 0001-01-01 的序数为 1, 1970-01-01 的序数为 719163
+        let ordinal: i64 = date.call_method0("toordinal")?.extract()?;
+        (ordinal - 719_163, 0, 0)
+    } else {
+        return Ok(None);
+    };
+    let seconds = days * 86_400 + seconds;
+    Ok(Some(match unit {
+        TimeUnit::Seconds => seconds,
+        TimeUnit::Millis => seconds * 1000 + micros / 1000,
+    }))
+}
+
+/// 将 Unix 时间戳还原为 UTC 时区的 `datetime`.
+fn timestamp_to_datetime<'py>(
+    py: Python<'py>,
+    timestamp: i64,
+    unit: TimeUnit,
+    tag: u8,
+) -> PyResult<Bound<'py, PyAny>> {
+    let out_of_range = || {
+        PyValueError::new_err(format!(
+            "Timestamp {} at tag {} out of range",
+            timestamp, tag
+        ))
+    };
+    let (seconds, micros) = match unit {
+        TimeUnit::Seconds => (timestamp, 0),
+        TimeUnit::Millis => (
+            timestamp.div_euclid(1000),
+            timestamp.rem_euclid(1000) * 1000,
+        ),
+    };
+    let days = i32::try_from(seconds.div_euclid(86_400)).map_err(|_| out_of_range())?;
+    let delta = PyDelta::new(
+        py,
+        days,
+        seconds.rem_euclid(86_400) as i32,
+        micros as i32,
+        false,
+    )
+    .map_err(|_| out_of_range())?;
+    let utc = PyTzInfo::utc(py)?;
+    let epoch = PyDateTime::new(py, 1970, 1, 1, 0, 0, 0, 0, Some(&utc))?;
+    epoch.add(delta).map_err(|_| out_of_range())
+}
+
+/// 将 `Decimal` (或可转换为 `Decimal` 的 int/str/float) 转换为字符串或缩放后的整数.
+fn decimal_to_wire<'py>(
+    value: &Bound<'py, PyAny>,
+    mode: DecimalMode,
+    tag: u8,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    let decimal = if value.is_instance(decimal_type(py)?)? {
+        value.clone()
+    } else {
+        decimal_type(py)?.call1((value,)).map_err(|_| {
+            PyTypeError::new_err(format!(
+                "Cannot convert {} to Decimal at tag {}",
+                value
+                    .get_type()
+                    .name()
+                    .map_or_else(|_| "?".into(), |n| n.to_string()),
+                tag
+            ))
+        })?
+    };
+    match mode {
+        DecimalMode::String => Ok(decimal.str()?.into_any()),
+        DecimalMode::ScaledInt(scale) => {
+            if !decimal.call_method0("is_finite")?.is_truthy()? {
+                return Err(PyValueError::new_err(format!(
+                    "Non-finite decimal value {} at tag {}",
+                    decimal.str()?,
+                    tag
+                )));
+            }
+            let scaled = decimal.call_method1("scaleb", (scale,))?;
+            if !scaled.eq(scaled.call_method0("to_integral_value")?)? {
+                return Err(PyValueError::new_err(format!(
+                    "Decimal value {} at tag {} has more than {} decimal places",
+                    decimal.str()?,
+                    tag,
+                    scale
+                )));
+            }
+            Ok(py.get_type::<PyInt>().call1((scaled,))?)
+        }
+    }
+}
+
+/// 将字符串或缩放后的整数还原为 `Decimal`.
+fn decimal_from_wire(
+    py: Python<'_>,
+    raw: Py<PyAny>,
+    mode: DecimalMode,
+    tag: u8,
+) -> PyResult<Py<PyAny>> {
+    let bound = raw.bind(py);
+    let decimal_type = decimal_type(py)?;
+    let text = if let Ok(view) = bound.cast::<JceStr>() {
+        Some(PyString::new(py, &view.get().to_text(py)?).into_any())
+    } else if bound.is_instance_of::<PyString>() {
+        Some(bound.clone())
+    } else {
+        None
+    };
+    if let Some(text) = text {
+        return match decimal_type.call1((&text,)) {
+            Ok(decimal) => Ok(decimal.unbind()),
+            Err(_) => Err(PyValueError::new_err(format!(
+                "Invalid decimal string {} at tag {}",
+                text.repr()?,
+                tag
+            ))),
+        };
+    }
+    if bound.is_exact_instance_of::<PyInt>() {
+        let decimal = decimal_type.call1((bound,))?;
+        return Ok(match mode {
+            DecimalMode::ScaledInt(scale) => decimal
+                .call_method1("scaleb", (-i64::from(scale),))?
+                .unbind(),
+            DecimalMode::String => decimal.unbind(),
+        });
+    }
+    Ok(raw)
+}
@@ -1,4 +1,11 @@
 pub mod exceptions;
+pub mod fixed;
+pub mod json;
+pub mod logical;
+pub mod reader;
 pub mod schema;
+pub mod schema_io;
 pub mod serde;
+pub mod str_view;
 pub mod stream;
+pub mod writer;
@@ -0,0 +1,194 @@
+use crate::bindings::serde::InputData;
+use crate::codec::consts::JceType;
+use crate::codec::error::Result;
+use crate::codec::reader::JceReader;
+use crate::codec::scanner::JceScanner;
+use byteorder::{BigEndian, LittleEndian};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// 手动解析 JCE 数据的读取器.
+///
+/// 持有输入数据的副本与当前偏移量, 适合按 Tag 逐个读取字段的场景.
+/// 读取失败时偏移量保持不变.
+#[pyclass(name = "JceReader")]
+pub struct Reader {
+    data: Vec<u8>,
+    position: u64,
+    little_endian: bool,
+    max_depth: usize,
+}
+
+impl Reader {
+    /// 在当前偏移量处执行读取操作, 成功时更新偏移量.
+    fn read<T>(
+        &mut self,
+        op_be: impl FnOnce(&mut JceReader<'_, BigEndian>) -> Result<T>,
+        op_le: impl FnOnce(&mut JceReader<'_, LittleEndian>) -> Result<T>,
+    ) -> PyResult<T> {
+        let (result, position) = if self.little_endian {
+            let mut reader =
+                JceReader::<LittleEndian>::new(&self.data).with_max_depth(self.max_depth);
+            reader.set_position(self.position)?;
+            (op_le(&mut reader), reader.position())
+        } else {
+            let mut reader = JceReader::<BigEndian>::new(&self.data).with_max_depth(self.max_depth);
+            reader.set_position(self.position)?;
+            (op_be(&mut reader), reader.position())
+        };
+        let value = result?;
+        self.position = position;
+        Ok(value)
+    }
+}
+
+/// 将 Python 传入的类型码转换为 `JceType`.
+pub(crate) fn jce_type(type_id: u8) -> PyResult<JceType> {
+    JceType::try_from(type_id)
+        .map_err(|id| PyValueError::new_err(format!("Invalid type ID: {}", id)))
+}
+
+#[pymethods]
+impl Reader {
+    #[new]
+    #[pyo3(signature = (data, little_endian=false, max_depth=100))]
+    /// 创建读取器.
+    ///
+    /// Args:
+    ///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+    ///     little_endian (bool): 是否按小端序解析.
+    ///     max_depth (int): `skip_field` 跳过嵌套容器时允许的最大深度.
+    fn new(data: &Bound<'_, PyAny>, little_endian: bool, max_depth: usize) -> PyResult<Self> {
+        Ok(Self {
+            data: InputData::new(data)?.as_bytes().to_vec(),
+            position: 0,
+            little_endian,
+            max_depth,
+        })
+    }
+
+    /// 当前偏移量.
+    fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 是否已读取到数据末尾.
+    fn is_end(&self) -> bool {
+        self.position >= self.data.len() as u64
+    }
+
+    /// 剩余未读取的字节数.
+    fn remaining(&self) -> u64 {
+        (self.data.len() as u64).saturating_sub(self.position)
+    }
+
+    /// 将偏移量移动到指定位置, 用于回溯解析.
+    ///
+    /// Args:
+    ///     offset (int): 目标偏移量, 不能超过数据长度.
+    ///
+    /// Raises:
+    ///     ValueError: 偏移量超出数据范围.
+    fn set_position(&mut self, offset: u64) -> PyResult<()> {
+        if offset > self.data.len() as u64 {
+            return Err(PyValueError::new_err(format!(
+                "Position {} out of range (length {})",
+                offset,
+                self.data.len()
+            )));
+        }
+        self.position = offset;
+        Ok(())
+    }
+
+    /// 读取字段头部.
+    ///
+    /// Returns:
+    ///     tuple[int, int]: (tag, type_id).
+    fn read_head(&mut self) -> PyResult<(u8, u8)> {
+        let (tag, t) = self.read(|r| r.read_head(), |r| r.read_head())?;
+        Ok((tag, t as u8))
+    }
+
+    /// 预览下一个字段头部而不移动偏移量.
+    ///
+    /// 2 字节的扩展 Tag 头部 (tag >= 15) 同样会完整回退.
+    ///
+    /// Returns:
+    ///     tuple[int, int]: (tag, type_id).
+    fn peek_head(&mut self) -> PyResult<(u8, u8)> {
+        let (tag, t) = self.read(|r| r.peek_head(), |r| r.peek_head())?;
+        Ok((tag, t as u8))
+    }
+
+    /// 在当前层级向前查找指定 Tag 的字段.
+    ///
+    /// 跳过不匹配的字段 (不进入嵌套结构体). 找到时定位到该字段的头部之前,
+    /// 随后 `read_head` 即可得到其类型; 遇到 StructEnd 或数据结束时偏移量保持不变.
+    ///
+    /// Args:
+    ///     tag (int): 目标 Tag.
+    ///
+    /// Returns:
+    ///     bool: 是否找到该字段.
+    fn seek_to_tag(&mut self, tag: u8) -> PyResult<bool> {
+        self.read(|r| r.seek_to_tag(tag), |r| r.seek_to_tag(tag))
+    }
+
+    /// 测量下一个字段的完整编码长度而不移动偏移量.
+    ///
+    /// 长度包含头部 (含扩展 Tag 头部) 与值, 嵌套的容器与结构体计入整体长度.
+    /// 基于零分配的结构扫描, 不创建任何 Python 对象.
+    ///
+    /// Returns:
+    ///     tuple[int, int, int]: (tag, type_id, length).
+    ///
+    /// Raises:
+    ///     DecodeError: 字段被截断或结构非法.
+    fn measure_field(&self) -> PyResult<(u8, u8, usize)> {
+        let (tag, t, value) = if self.little_endian {
+            let mut scanner =
+                JceScanner::<LittleEndian>::new(&self.data).with_max_depth(self.max_depth);
+            scanner.set_position(self.position)?;
+            scanner.next_field()?
+        } else {
+            let mut scanner =
+                JceScanner::<BigEndian>::new(&self.data).with_max_depth(self.max_depth);
+            scanner.set_position(self.position)?;
+            scanner.next_field()?
+        };
+        Ok((tag, t as u8, value.end - self.position as usize))
+    }
+
+    /// 跳过字段值 (不含头部), 不创建任何 Python 对象.
+    ///
+    /// 嵌套的容器与结构体会被整体跳过, 嵌套深度超过 `max_depth` 时抛出 `DecodeError`.
+    ///
+    /// Args:
+    ///     type_id (int): 头部中的类型码.
+    fn skip_field(&mut self, type_id: u8) -> PyResult<()> {
+        let t = jce_type(type_id)?;
+        self.read(|r| r.skip_field(t), |r| r.skip_field(t))
+    }
+
+    /// 读取整数值 (不含头部).
+    ///
+    /// Args:
+    ///     type_id (int): 头部中的类型码 (Int1/Int2/Int4/Int8/ZeroTag).
+    fn read_int(&mut self, type_id: u8) -> PyResult<i64> {
+        let t = jce_type(type_id)?;
+        self.read(|r| r.read_int(t), |r| r.read_int(t))
+    }
+
+    /// 读取字符串值 (不含头部).
+    ///
+    /// Args:
+    ///     type_id (int): 头部中的类型码 (String1/String4).
+    fn read_string(&mut self, type_id: u8) -> PyResult<String> {
+        let t = jce_type(type_id)?;
+        self.read(
+            |r| r.read_string(t).map(|s| s.into_owned()),
+            |r| r.read_string(t).map(|s| s.into_owned()),
+        )
+    }
+}
@@ -1,5 +1,10 @@
+use crate::bindings::logical::LogicalType;
+use crate::bindings::serde::get_or_compile_schema;
+use crate::codec::consts::JceType;
 use pyo3::prelude::*;
-use pyo3::types::{PyCapsule, PyList, PyString, PyTuple};
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBytes, PyCapsule, PyDict, PyList, PyString, PyTuple, PyType};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Debug)]
 pub struct FieldDef {
@@ -8,35 +13,221 @@ pub struct FieldDef {
     pub tag: u8,
     pub tars_type: u8,
     pub default_val: Py<PyAny>,
-    pub has_serializer: bool,
+    pub has_serializer: bool,   // 声明了编码钩子 `__tars_serialize_field__`
+    pub has_deserializer: bool, // 声明了解码钩子 `__tars_deserialize_field__`
+    pub codec: Option<Py<PyString>>, // SimpleList 文本字段的编码 (如 "gbk")
+    pub elem: Option<TypeDesc>, // List 字段的元素类型
+    pub key: Option<TypeDesc>,  // Map 字段的键类型
+    pub value: Option<TypeDesc>, // Map 字段的值类型
+    pub nested: Option<NestedSchema>, // Struct/二进制块字段声明的结构体类型
+    pub enum_cls: Option<Py<PyType>>, // 整数字段对应的枚举类型
+    pub logical: Option<LogicalType>, // 逻辑类型 (如 Decimal), 编解码时与基础值互转
+    pub little_endian: Option<bool>, // 嵌套结构体字段的字节序覆盖 (true 为小端)
+    pub alias: Option<String>,  // 以字段名为键的字典中可代替 `name` 的旧名称
+    pub required: bool,         // 模型中没有默认值, 以字段名为键的字典编码时必须提供
+    pub not_none: bool,         // 类型注解不接受 None, 不做字段过滤时编码省去 None 检查
+}
+
+impl FieldDef {
+    /// 字段名或别名是否与 `name` 相同.
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.name == name || self.alias.as_deref() == Some(name)
+    }
+}
+
+/// 嵌套结构体的 Schema 引用.
+///
+/// 编译结果在首次使用时通过 `get_or_compile_schema` 获取 (胶囊同时缓存在类型上),
+/// 编译阶段不会递归进入嵌套类型, 因此自引用的类型定义不会导致无限编译.
+pub struct NestedSchema {
+    source: Py<PyAny>, // Struct 类或 Schema 列表
+    compiled: PyOnceLock<Py<PyCapsule>>,
+}
+
+impl std::fmt::Debug for NestedSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NestedSchema")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NestedSchema {
+    pub fn new(source: Py<PyAny>) -> Self {
+        Self {
+            source,
+            compiled: PyOnceLock::new(),
+        }
+    }
+
+    /// 获取 (必要时编译) 嵌套结构体的 `CompiledSchema`.
+    pub fn compiled<'s>(&'s self, py: Python<'_>) -> PyResult<&'s CompiledSchema> {
+        let capsule = self.compiled.get_or_try_init(py, || {
+            let source = self.source.bind(py);
+            if let Ok(list) = source.cast::<PyList>() {
+                return compile_schema(py, list);
+            }
+            get_or_compile_schema(py, source)?.ok_or_else(|| {
+                pyo3::exceptions::PyTypeError::new_err("Invalid nested struct schema")
+            })
+        })?;
+        let ptr = capsule
+            .bind(py)
+            .pointer_checked(None)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid capsule"))?;
+        // 胶囊由 `self.compiled` 持有, 其生命周期不短于 `self`
+        Ok(unsafe { &*(ptr.as_ptr() as *const CompiledSchema) })
+    }
+
+    /// 值的类型是否恰好为声明的结构体类型.
+    ///
+    /// 子类实例可能携带额外字段, 需按其自身 Schema 编码; Schema 列表来源总是视为匹配.
+    pub fn matches(&self, value: &Bound<'_, PyAny>) -> bool {
+        match self.source.bind(value.py()).cast::<PyType>() {
+            Ok(cls) => value.get_type().is(cls),
+            Err(_) => true,
+        }
+    }
+}
+
+/// 布尔字段的类型码 (非 JCE 线上类型).
+///
+/// 编码为最小宽度的整数 0/1, 解码时还原为 `True`/`False`.
+pub(crate) const TYPE_CODE_BOOL: u8 = 254;
+
+/// 容器元素的类型描述.
+#[derive(Debug)]
+pub enum TypeDesc {
+    /// 类型码 (与 Schema 元组中的 tars_type 一致, 255 表示运行时推断).
+    Code(u8),
+    /// 结构体 (Struct 类或 Schema 列表).
+    Struct(NestedSchema),
+}
+
+impl TypeDesc {
+    /// 从 Python 描述对象 (类型码 int, Struct 类或 Schema 列表) 解析.
+    pub fn from_py(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(code) = obj.extract::<u8>() {
+            return Ok(TypeDesc::Code(code));
+        }
+        if obj.cast::<PyType>().is_ok() || obj.cast::<PyList>().is_ok() {
+            return Ok(TypeDesc::Struct(NestedSchema::new(obj.clone().unbind())));
+        }
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Invalid type descriptor: {}",
+            obj.repr()?
+        )))
+    }
 }
 
 #[derive(Debug)]
 pub struct CompiledSchema {
     pub fields: Vec<FieldDef>,
     pub tag_lookup: [Option<usize>; 256], // Map tag -> index in fields
+    pub owner: Option<Py<PyType>>,        // 存在字段钩子时, 提供钩子方法的 Struct 类
+    pub size_hint: usize,                 // 编码结果的预估字节数, 用于预分配缓冲区
+    pub tag_order: Option<Vec<usize>>,    // Tag 未按升序声明时, 按 Tag 升序排列的字段索引
+    pub unordered_warned: AtomicBool,     // 是否已就 Tag 未按升序声明发出过警告
+}
+
+impl CompiledSchema {
+    /// Tag 未按升序声明且按声明顺序编码时发出警告, 每个 Schema 只警告一次.
+    pub(crate) fn warn_unordered_tags(&self, py: Python<'_>) -> PyResult<()> {
+        if self.tag_order.is_none() || self.unordered_warned.swap(true, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let Some(pair) = self
+            .fields
+            .windows(2)
+            .find(|pair| pair[0].tag > pair[1].tag)
+        else {
+            return Ok(());
+        };
+        let message = format!(
+            "Schema tags are not in ascending order (tag {} after tag {}); \
+             encoded output will not be canonical unless Option.SORT_TAGS is set",
+            pair[1].tag, pair[0].tag
+        );
+        let message = std::ffi::CString::new(message)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        PyErr::warn(
+            py,
+            &py.get_type::<pyo3::exceptions::PyUserWarning>(),
+            &message,
+            1,
+        )
+    }
+}
+
+/// 容器, 结构体与运行时推断类型的预估字节数 (不含头部).
+const CONTAINER_SIZE_HINT: usize = 16;
+
+/// 估算单个字段编码后的字节数.
+///
+/// 整数与浮点数按最大宽度计算; 字符串与二进制数据按默认值长度计算;
+/// 容器, 结构体与运行时推断类型的大小在编译期未知, 按固定值计算.
+pub(crate) fn field_size_hint(tag: u8, tars_type: u8, default_len: usize) -> usize {
+    let head = if tag < 15 { 1 } else { 2 };
+    head + match tars_type {
+        0..=3 | 5 | 12 => 8,
+        4 => 4,
+        TYPE_CODE_BOOL => 1,
+        // 长度字段最多 4 字节
+        6 | 7 => 4 + default_len,
+        // 元素类型字节 + 长度 (Int4, 含头部)
+        13 => 1 + 5 + default_len,
+        _ => CONTAINER_SIZE_HINT,
+    }
 }
 
 /// 编译 Schema 以加速序列化/反序列化.
 ///
 /// 将 Python 中的 Schema 列表 (`[(name, tag, type, default, has_ser), ...]`)
-/// 转换为 Rust 内部的高效结构 `CompiledSchema`.
+/// 转换为 Rust 内部的高效结构 `CompiledSchema`, 并封装为胶囊以便缓存.
 ///
 /// 优化点:
 /// 1. 字符串驻留 (Interning): 减少 Python 字符串创建开销.
 /// 2. Tag 查找表 (O(1)): 使用数组直接索引 Tag，避免线性扫描.
 pub fn compile_schema(py: Python<'_>, schema_list: &Bound<'_, PyList>) -> PyResult<Py<PyCapsule>> {
+    let compiled = build_schema(py, schema_list)?;
+    let capsule = PyCapsule::new(py, compiled, None)?;
+    Ok(capsule.into())
+}
+
+/// 解析 Schema 列表为 `CompiledSchema`.
+///
+/// 每项可选携带第 6 个元素 (dict), 用于描述额外信息:
+/// - `codec`: SimpleList 文本字段的编码.
+/// - `elem`: List 字段的元素类型描述 (见 `TypeDesc`).
+/// - `key` / `value`: Map 字段的键/值类型描述.
+/// - `schema`: Struct 或二进制块 (SimpleList) 字段的结构体类型.
+/// - `enum`: 整数字段对应的枚举类型 (解码时转换为枚举成员, 编码时写入 `.value`).
+/// - `decimal`: `Decimal` 字段的存储方式, `"string"` 或整数 scale (见 `LogicalType`).
+/// - `datetime`: `datetime`/`date` 字段的 `(kind, unit)`, 以 Int8 时间戳存储.
+/// - `ip`: IP 地址字段的版本 (4 或 6).
+/// - `endian`: Struct 字段的字节序 (`"big"` 或 `"little"`), 与外层不同时该结构体按此字节序编解码.
+/// - `alias`: 字段的别名. 以字段名为键的字典 (编码与 `normalize_struct`) 中可用别名代替字段名,
+///   解码结果始终使用字段名. 别名不能与其他字段的名称或别名重复.
+/// - `required`: 为真时字段没有默认值, 以字段名为键的字典编码时缺少该键会报错.
+/// - `not_none`: 为真时字段的类型不接受 None, 不做字段过滤的编码循环不再逐字段检查 None,
+///   此时字段值为 None 会按类型不匹配报错.
+///
+/// 编码默认按列表顺序写出字段, 因此 Tag 未按升序排列时发出 `UserWarning`:
+/// 输出不符合 JCE 规范, 严格的对端可能无法解析. 此时同时记录按 Tag 升序的字段索引,
+/// 供 `OPT_SORT_TAGS` 使用.
+pub fn build_schema(py: Python<'_>, schema_list: &Bound<'_, PyList>) -> PyResult<CompiledSchema> {
     let mut fields = Vec::with_capacity(schema_list.len());
     let mut tag_lookup = [None; 256];
+    let mut size_hint = 0;
 
     for (idx, item) in schema_list.iter().enumerate() {
         let tuple = item
             .cast::<PyTuple>()
             .map_err(|_| pyo3::exceptions::PyTypeError::new_err("Schema item must be a tuple"))?;
 
-        if tuple.len() != 5 {
+        if tuple.len() != 5 && tuple.len() != 6 {
             return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Schema item must have 5 elements, got {}",
+                "Schema item must have 5 or 6 elements, got {}",
                 tuple.len()
             )));
         }
@@ -53,6 +244,81 @@ pub fn compile_schema(py: Python<'_>, schema_list: &Bound<'_, PyList>) -> PyResu
         let default_val = tuple.get_item(3)?.unbind();
         let has_serializer: bool = tuple.get_item(4)?.extract()?;
 
+        let mut codec = None;
+        let (mut elem, mut key, mut value) = (None, None, None);
+        let mut nested = None;
+        let mut enum_cls = None;
+        let mut logical = None;
+        let mut little_endian = None;
+        let mut alias = None;
+        let mut required = false;
+        let mut not_none = false;
+        let mut has_deserializer = false;
+        if tuple.len() == 6
+            && let Ok(extras) = tuple.get_item(5)?.cast_into::<PyDict>()
+        {
+            if let Some(c) = extras.get_item("codec")?
+                && !c.is_none()
+            {
+                codec = Some(c.cast_into::<PyString>()?.unbind());
+            }
+            elem = extras_type_desc(&extras, "elem")?;
+            key = extras_type_desc(&extras, "key")?;
+            value = extras_type_desc(&extras, "value")?;
+            if let Some(schema) = extras.get_item("schema")?
+                && !schema.is_none()
+            {
+                nested = Some(NestedSchema::new(schema.unbind()));
+            }
+            if let Some(cls) = extras.get_item("enum")?
+                && !cls.is_none()
+            {
+                enum_cls = Some(cls.cast_into::<PyType>()?.unbind());
+            }
+            if let Some(mode) = extras.get_item("decimal")?
+                && !mode.is_none()
+            {
+                logical = Some(LogicalType::decimal_from_py(&mode)?);
+            }
+            if let Some(spec) = extras.get_item("datetime")?
+                && !spec.is_none()
+            {
+                logical = Some(LogicalType::datetime_from_py(&spec)?);
+            }
+            if let Some(version) = extras.get_item("ip")?
+                && !version.is_none()
+            {
+                logical = Some(LogicalType::ip_from_py(&version)?);
+            }
+            if let Some(endian) = extras.get_item("endian")?
+                && !endian.is_none()
+            {
+                little_endian = Some(match endian.extract::<&str>()? {
+                    "big" => false,
+                    "little" => true,
+                    other => {
+                        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "endian must be 'big' or 'little', got '{other}'"
+                        )));
+                    }
+                });
+            }
+            if let Some(a) = extras.get_item("alias")?
+                && !a.is_none()
+            {
+                alias = Some(a.extract::<String>()?);
+            }
+            if let Some(r) = extras.get_item("required")? {
+                required = r.is_truthy()?;
+            }
+            if let Some(n) = extras.get_item("not_none")? {
+                not_none = n.is_truthy()?;
+            }
+            if let Some(d) = extras.get_item("deserializer")? {
+                has_deserializer = d.is_truthy()?;
+            }
+        }
+
         if tag_lookup[tag as usize].is_some() {
             return Err(pyo3::exceptions::PyValueError::new_err(format!(
                 "Duplicate tag {} in schema",
@@ -61,6 +327,14 @@ pub fn compile_schema(py: Python<'_>, schema_list: &Bound<'_, PyList>) -> PyResu
         }
 
         tag_lookup[tag as usize] = Some(idx);
+        let default_len = if let Ok(s) = default_val.bind(py).cast::<PyString>() {
+            s.to_str().map_or(0, str::len)
+        } else if let Ok(b) = default_val.bind(py).cast::<PyBytes>() {
+            b.as_bytes().len()
+        } else {
+            0
+        };
+        size_hint += field_size_hint(tag, tars_type_code, default_len);
         fields.push(FieldDef {
             name,
             py_name,
@@ -68,12 +342,300 @@ pub fn compile_schema(py: Python<'_>, schema_list: &Bound<'_, PyList>) -> PyResu
             tars_type: tars_type_code,
             default_val,
             has_serializer,
+            has_deserializer,
+            codec,
+            elem,
+            key,
+            value,
+            nested,
+            enum_cls,
+            logical,
+            little_endian,
+            alias,
+            required,
+            not_none,
         });
     }
 
-    let compiled = CompiledSchema { fields, tag_lookup };
-    let capsule = PyCapsule::new(py, compiled, None)?;
-    Ok(capsule.into())
+    for (idx, field) in fields.iter().enumerate() {
+        if let Some(alias) = &field.alias
+            && let Some(other) = fields
+                .iter()
+                .enumerate()
+                .find(|&(i, f)| i != idx && f.matches_name(alias))
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Alias '{}' of field '{}' conflicts with field '{}'",
+                alias, field.name, other.1.name
+            )));
+        }
+    }
+
+    let mut tag_order = None;
+    if fields.windows(2).any(|pair| pair[0].tag > pair[1].tag) {
+        let mut order: Vec<usize> = (0..fields.len()).collect();
+        order.sort_by_key(|&i| fields[i].tag);
+        tag_order = Some(order);
+    }
+
+    Ok(CompiledSchema {
+        fields,
+        tag_lookup,
+        owner: None,
+        size_hint,
+        tag_order,
+        unordered_warned: AtomicBool::new(false),
+    })
+}
+
+/// 判断线上类型码为 `actual` 的数据能否按声明的类型码 `expected` 解码.
+///
+/// 与 `decode_field` 的兼容规则一致: 整数 (含布尔) 之间互通, String1 与 String4 互通,
+/// 声明为 Double 时接受 Float; 运行时推断 (255) 接受任何类型.
+pub(crate) fn type_code_compatible(actual: u8, expected: u8) -> bool {
+    fn wire_type(code: u8) -> Option<JceType> {
+        match code {
+            TYPE_CODE_BOOL => Some(JceType::Int1),
+            _ => JceType::try_from(code).ok(),
+        }
+    }
+    if actual == 255 || expected == 255 {
+        return true;
+    }
+    match (wire_type(actual), wire_type(expected)) {
+        (Some(actual), Some(expected)) => expected.accepts(actual),
+        _ => actual == expected,
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (expected, actual, widen_floats=false))]
+/// 判断声明为 `expected` 类型的字段能否接受线上类型为 `actual` 的数据.
+///
+/// 与解码时使用的兼容规则一致: 各宽度的整数 (含 ZeroTag) 互通,
+/// String1 与 String4 互通, Double 可接受 Float, 其余类型必须完全一致.
+///
+/// Args:
+///     expected (int): 声明的 JCE 类型码.
+///     actual (int): 线上的 JCE 类型码.
+///     widen_floats (bool): 是否按 `OPT_WIDEN_FLOATS` 的规则允许 Float 接受 Double.
+///
+/// Returns:
+///     bool: 是否兼容.
+///
+/// Raises:
+///     ValueError: 类型码无效.
+pub fn is_type_compatible(expected: u8, actual: u8, widen_floats: bool) -> PyResult<bool> {
+    let parse = |code: u8| {
+        JceType::try_from(code).map_err(|id| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid type ID: {}", id))
+        })
+    };
+    Ok(parse(expected)?.accepts_with(parse(actual)?, widen_floats))
+}
+
+#[pyfunction]
+#[pyo3(signature = (old, new))]
+/// 检查两个版本的 Schema 之间的线上兼容性.
+///
+/// 按 Tag 比较两个 Schema: 删除的字段与类型不兼容的修改属于破坏性变更,
+/// 新增的字段与双向兼容的类型修改 (如 Int1 -> Int8) 属于非破坏性变更.
+/// 嵌套结构体的内部字段不会递归比较.
+///
+/// Args:
+///     old (list): 旧版本的 Schema 列表.
+///     new (list): 新版本的 Schema 列表.
+///
+/// Returns:
+///     dict: `{"compatible": bool, "breaking": list, "non_breaking": list}`,
+///     每项变更为 `{"tag", "name", "kind", "old_type", "new_type"}`,
+///     `kind` 为 `"removed"`, `"added"` 或 `"type_changed"`.
+pub fn schema_compatible(
+    py: Python<'_>,
+    old: &Bound<'_, PyList>,
+    new: &Bound<'_, PyList>,
+) -> PyResult<Py<PyDict>> {
+    let old = build_schema(py, old)?;
+    let new = build_schema(py, new)?;
+    let breaking = PyList::empty(py);
+    let non_breaking = PyList::empty(py);
+    for tag in 0..=255u8 {
+        let old_field = old.tag_lookup[tag as usize].map(|i| &old.fields[i]);
+        let new_field = new.tag_lookup[tag as usize].map(|i| &new.fields[i]);
+        let (kind, name, is_breaking) = match (old_field, new_field) {
+            (None, None) => continue,
+            (Some(o), None) => ("removed", &o.name, true),
+            (None, Some(n)) => ("added", &n.name, false),
+            (Some(o), Some(n)) if o.tars_type == n.tars_type => continue,
+            (Some(o), Some(n)) => {
+                // 新数据按旧 Schema 解码, 以及旧数据按新 Schema 解码都需要兼容
+                let compatible = type_code_compatible(n.tars_type, o.tars_type)
+                    && type_code_compatible(o.tars_type, n.tars_type);
+                ("type_changed", &n.name, !compatible)
+            }
+        };
+        let change = PyDict::new(py);
+        change.set_item("tag", tag)?;
+        change.set_item("name", name)?;
+        change.set_item("kind", kind)?;
+        change.set_item("old_type", old_field.map(|f| f.tars_type))?;
+        change.set_item("new_type", new_field.map(|f| f.tars_type))?;
+        if is_breaking {
+            breaking.append(change)?;
+        } else {
+            non_breaking.append(change)?;
+        }
+    }
+    let report = PyDict::new(py);
+    report.set_item("compatible", breaking.is_empty())?;
+    report.set_item("breaking", breaking)?;
+    report.set_item("non_breaking", non_breaking)?;
+    Ok(report.unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, schema, struct_type=None))]
+/// 将以字段名为键的字典转换为以 Tag 为键的字典 (通用编码使用的形式).
+///
+/// 嵌套结构体字段, 元素为结构体的 List 与值为结构体的 Map 会按各自的 Schema 递归转换.
+/// 已经是 Tag 的整数键原样保留.
+///
+/// Args:
+///     data (dict): 以字段名为键的字典.
+///     schema (type | list): Struct 类或 Schema 列表.
+///     struct_type (type | None): 用于构造结果 (含嵌套结构体) 的字典类型, 如 `StructDict`.
+///
+/// Returns:
+///     dict: 以 Tag 为键的字典.
+///
+/// Raises:
+///     ValueError: 存在 Schema 中没有的字段名.
+pub fn normalize_struct(
+    py: Python<'_>,
+    data: &Bound<'_, PyDict>,
+    schema: &Bound<'_, PyAny>,
+    struct_type: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    let schema = NestedSchema::new(schema.clone().unbind());
+    Ok(convert_struct(py, data, schema.compiled(py)?, true, struct_type)?.unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, schema))]
+/// 将以 Tag 为键的字典转换为以字段名为键的字典, 是 `normalize_struct` 的逆操作.
+///
+/// Schema 中没有的 Tag 原样保留.
+///
+/// Args:
+///     data (dict): 以 Tag 为键的字典.
+///     schema (type | list): Struct 类或 Schema 列表.
+///
+/// Returns:
+///     dict: 以字段名为键的字典.
+pub fn denormalize_struct(
+    py: Python<'_>,
+    data: &Bound<'_, PyDict>,
+    schema: &Bound<'_, PyAny>,
+) -> PyResult<Py<PyAny>> {
+    let schema = NestedSchema::new(schema.clone().unbind());
+    Ok(convert_struct(py, data, schema.compiled(py)?, false, None)?.unbind())
+}
+
+/// 在字段名与 Tag 两种键之间转换结构体字典.
+///
+/// `to_tags` 为真时输出以 Tag 为键, 否则以字段名为键; 两种键 (以及字段别名) 在输入中均可识别.
+fn convert_struct<'py>(
+    py: Python<'py>,
+    data: &Bound<'py, PyDict>,
+    schema: &CompiledSchema,
+    to_tags: bool,
+    struct_type: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let result = PyDict::new(py);
+    for (key, value) in data.iter() {
+        let field = if let Ok(name) = key.cast::<PyString>() {
+            let name = name.to_str()?;
+            match schema.fields.iter().find(|f| f.matches_name(name)) {
+                // 同时出现字段名与别名时以字段名为准
+                Some(field) if field.name != name && data.contains(field.py_name.bind(py))? => {
+                    continue;
+                }
+                Some(field) => Some(field),
+                None if to_tags => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "Unknown field '{}'",
+                        name
+                    )));
+                }
+                None => None,
+            }
+        } else {
+            key.extract::<u8>()
+                .ok()
+                .and_then(|tag| schema.tag_lookup[tag as usize])
+                .map(|i| &schema.fields[i])
+        };
+        let Some(field) = field else {
+            result.set_item(key, value)?;
+            continue;
+        };
+        let value = convert_field_value(py, field, value, to_tags, struct_type)?;
+        if to_tags {
+            result.set_item(field.tag, value)?;
+        } else {
+            result.set_item(field.py_name.bind(py), value)?;
+        }
+    }
+    match struct_type {
+        Some(cls) if to_tags => cls.call1((result,)),
+        _ => Ok(result.into_any()),
+    }
+}
+
+/// 转换字段值中以字典表示的结构体 (嵌套结构体, List 元素与 Map 值).
+fn convert_field_value<'py>(
+    py: Python<'py>,
+    field: &FieldDef,
+    value: Bound<'py, PyAny>,
+    to_tags: bool,
+    struct_type: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let convert = |nested: &NestedSchema, item: Bound<'py, PyAny>| match item.cast::<PyDict>() {
+        Ok(dict) => convert_struct(py, dict, nested.compiled(py)?, to_tags, struct_type),
+        Err(_) => Ok(item),
+    };
+    if field.tars_type == JceType::StructBegin as u8
+        && let Some(nested) = &field.nested
+    {
+        return convert(nested, value);
+    }
+    if let Some(TypeDesc::Struct(nested)) = &field.elem
+        && let Ok(list) = value.cast::<PyList>()
+    {
+        let items = list
+            .iter()
+            .map(|item| convert(nested, item))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(PyList::new(py, items)?.into_any());
+    }
+    if let Some(TypeDesc::Struct(nested)) = &field.value
+        && let Ok(map) = value.cast::<PyDict>()
+    {
+        let converted = PyDict::new(py);
+        for (k, v) in map.iter() {
+            converted.set_item(k, convert(nested, v)?)?;
+        }
+        return Ok(converted.into_any());
+    }
+    Ok(value)
+}
+
+/// 读取 extras 中的类型描述, 缺失或为 None 时返回 None.
+fn extras_type_desc(extras: &Bound<'_, PyDict>, name: &str) -> PyResult<Option<TypeDesc>> {
+    match extras.get_item(name)? {
+        Some(obj) if !obj.is_none() => Ok(Some(TypeDesc::from_py(&obj)?)),
+        _ => Ok(None),
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +663,93 @@ mod tests {
             assert_eq!(schema.fields[0].name, "uid");
             assert_eq!(schema.tag_lookup[0], Some(0));
             assert_eq!(schema.tag_lookup[1], Some(1));
+            // uid: 1 + 8, name: 1 + 4 + len("unknown")
+            assert_eq!(schema.size_hint, 9 + 12);
+        });
+    }
+
+    #[test]
+    fn test_type_code_compatible() {
+        // Int1 <-> Int8, 布尔 <-> 整数, String1 <-> String4
+        assert!(type_code_compatible(0, 3));
+        assert!(type_code_compatible(TYPE_CODE_BOOL, 0));
+        assert!(type_code_compatible(7, 6));
+        // Float 可按 Double 解码, 反之不行
+        assert!(type_code_compatible(4, 5));
+        assert!(!type_code_compatible(5, 4));
+        // String -> Int 不兼容, 运行时推断接受任何类型
+        assert!(!type_code_compatible(6, 0));
+        assert!(type_code_compatible(9, 255));
+    }
+
+    #[test]
+    fn test_size_hint_avoids_realloc() {
+        use crate::codec::writer::JceWriter;
+        use byteorder::BigEndian;
+
+        let long = "x".repeat(300);
+        let mut hint = field_size_hint(0, 7, long.len());
+        for tag in 1..=255u8 {
+            hint += field_size_hint(tag, 3, 0);
+        }
+        let buffer = Vec::with_capacity(hint);
+        let capacity = buffer.capacity();
+        let mut writer = JceWriter::<Vec<u8>, BigEndian>::with_buffer(buffer);
+        writer.write_string(0, &long);
+        for tag in 1..=255u8 {
+            writer.write_int(tag, i64::MIN);
+        }
+        // 按最大宽度写入所有字段后缓冲区没有发生扩容
+        assert_eq!(writer.finish().capacity(), capacity);
+    }
+
+    #[test]
+    fn test_compile_schema_with_codec() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let schema_list = PyList::empty(py);
+            let extras = PyDict::new(py);
+            extras.set_item("codec", "gbk").unwrap();
+            schema_list
+                .append(("title", 0, 13, "", false, extras))
+                .unwrap();
+            schema_list.append(("uid", 1, 0, 0, false)).unwrap();
+
+            let capsule = compile_schema(py, &schema_list).unwrap();
+            let bound = capsule.bind(py);
+            let ptr = bound.pointer_checked(None).expect("Capsule pointer error");
+            let schema: &CompiledSchema = unsafe { &*(ptr.as_ptr() as *const CompiledSchema) };
+            let codec = schema.fields[0].codec.as_ref().unwrap();
+            assert_eq!(codec.bind(py).to_str().unwrap(), "gbk");
+            assert!(schema.fields[1].codec.is_none());
+        });
+    }
+
+    #[test]
+    fn test_build_schema_with_elem() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let inner = PyList::empty(py);
+            inner.append(("uid", 0, 0, 0, false)).unwrap();
+
+            let int_elem = PyDict::new(py);
+            int_elem.set_item("elem", 0).unwrap();
+            let struct_elem = PyDict::new(py);
+            struct_elem.set_item("elem", &inner).unwrap();
+
+            let schema_list = PyList::empty(py);
+            schema_list
+                .append(("ids", 0, 9, py.None(), false, int_elem))
+                .unwrap();
+            schema_list
+                .append(("users", 1, 9, py.None(), false, struct_elem))
+                .unwrap();
+
+            let schema = build_schema(py, &schema_list).unwrap();
+            assert!(matches!(schema.fields[0].elem, Some(TypeDesc::Code(0))));
+            assert!(matches!(schema.fields[1].elem, Some(TypeDesc::Struct(_))));
         });
     }
 
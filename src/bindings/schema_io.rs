@@ -0,0 +1,600 @@
+//! Schema 列表的二进制描述.
+//!
+//! 将 `__get_core_schema__` 返回的 Schema 列表序列化为紧凑的字节串, 以便跨进程缓存;
+//! 加载时直接重建 Schema 列表并编译, 跳过 Pydantic 模型的字段内省.
+//!
+//! 描述本身以 JCE 编码: 魔数与版本号之后是一个值, 每个值为结构体
+//! `{0: 种类, 1: 内容}`. 类型 (Struct 类, 枚举类) 以 `module:qualname` 引用,
+//! 加载时重新导入.
+
+use crate::bindings::logical::decimal_type;
+use crate::bindings::schema::build_schema;
+use crate::bindings::serde::{InputData, schema_method};
+use crate::codec::consts::{JceType, MAX_DEPTH};
+use crate::codec::error::Error as CodecError;
+use crate::codec::reader::JceReader;
+use crate::codec::writer::JceWriter;
+use byteorder::BigEndian;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{
+    PyBool, PyBytes, PyCapsule, PyDelta, PyDeltaAccess, PyDict, PyFloat, PyInt, PyList, PyString,
+    PyTuple, PyType,
+};
+use std::borrow::Cow;
+
+/// 描述的魔数与格式版本.
+const MAGIC: &[u8] = b"TSCH\x01";
+const KIND_NONE: i64 = 0;
+const KIND_BOOL: i64 = 1;
+const KIND_INT: i64 = 2;
+const KIND_FLOAT: i64 = 3;
+const KIND_STR: i64 = 4;
+const KIND_BYTES: i64 = 5;
+const KIND_TUPLE: i64 = 6;
+const KIND_LIST: i64 = 7;
+const KIND_DICT: i64 = 8;
+const KIND_TYPE: i64 = 9;
+const KIND_ENUM: i64 = 10;
+const KIND_DECIMAL: i64 = 11;
+const KIND_DATETIME: i64 = 12;
+const KIND_TIMEDELTA: i64 = 13;
+const KIND_IP: i64 = 14;
+
+type Writer = JceWriter<Vec<u8>, BigEndian>;
+
+static ENUM_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static TIMEZONE_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// 可作为默认值的 `datetime` 类型, `timedelta` 以外的类型以 isoformat 文本记录.
+const DATETIME_TYPES: [&str; 4] = ["datetime", "date", "time", "timedelta"];
+/// 可作为默认值的 `ipaddress` 类型, 加载时只会调用这些构造函数.
+const IP_TYPES: [&str; 6] = [
+    "IPv4Address",
+    "IPv6Address",
+    "IPv4Network",
+    "IPv6Network",
+    "IPv4Interface",
+    "IPv6Interface",
+];
+
+/// 写入单个值, 不支持的值抛出 TypeError.
+fn write_value(writer: &mut Writer, tag: u8, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let py = value.py();
+    writer.write_tag(tag, JceType::StructBegin);
+    if value.is_none() {
+        writer.write_int(0, KIND_NONE);
+    } else if let Ok(b) = value.cast::<PyBool>() {
+        writer.write_int(0, KIND_BOOL);
+        writer.write_int(1, b.is_true() as i64);
+    } else if value.is_instance_of::<PyInt>() && !is_enum_member(value)? {
+        let Ok(v) = value.extract::<i64>() else {
+            return Err(unsupported(value));
+        };
+        writer.write_int(0, KIND_INT);
+        writer.write_int(1, v);
+    } else if value.is_exact_instance_of::<PyFloat>() {
+        writer.write_int(0, KIND_FLOAT);
+        writer.write_double(1, value.extract::<f64>()?);
+    } else if let Ok(s) = value.cast_exact::<PyString>() {
+        writer.write_int(0, KIND_STR);
+        writer.write_string(1, s.to_str()?);
+    } else if let Ok(b) = value.cast_exact::<PyBytes>() {
+        writer.write_int(0, KIND_BYTES);
+        writer.write_bytes(1, b.as_bytes());
+    } else if let Ok(t) = value.cast_exact::<PyTuple>() {
+        writer.write_int(0, KIND_TUPLE);
+        write_items(writer, t.iter())?;
+    } else if let Ok(l) = value.cast_exact::<PyList>() {
+        writer.write_int(0, KIND_LIST);
+        write_items(writer, l.iter())?;
+    } else if let Ok(d) = value.cast_exact::<PyDict>() {
+        writer.write_int(0, KIND_DICT);
+        writer.write_map_header(1, d.len());
+        for (k, v) in d.iter() {
+            let Ok(k) = k.cast::<PyString>() else {
+                return Err(unsupported(value));
+            };
+            writer.write_string(0, k.to_str()?);
+            write_value(writer, 1, &v)?;
+        }
+    } else if let Ok(cls) = value.cast::<PyType>() {
+        if !is_schema_type(cls)? {
+            return Err(unsupported(value));
+        }
+        let Some(path) = type_path(cls)? else {
+            return Err(unsupported(value));
+        };
+        writer.write_int(0, KIND_TYPE);
+        writer.write_string(1, &path);
+    } else if is_enum_member(value)? {
+        let Some(path) = type_path(&value.get_type())? else {
+            return Err(unsupported(value));
+        };
+        let member_value = value.getattr(intern!(py, "value"))?;
+        writer.write_int(0, KIND_ENUM);
+        writer.write_string(1, &path);
+        write_value(writer, 2, &member_value)?;
+    } else if value.is_exact_instance(decimal_type(py)?) {
+        writer.write_int(0, KIND_DECIMAL);
+        writer.write_string(1, value.str()?.to_str()?);
+    } else if let Some(name) = stdlib_type_name(value, "datetime", &DATETIME_TYPES)? {
+        if name == "timedelta" {
+            let delta = value.cast::<PyDelta>()?;
+            writer.write_int(0, KIND_TIMEDELTA);
+            writer.write_int(1, delta.get_days() as i64);
+            writer.write_int(2, delta.get_seconds() as i64);
+            writer.write_int(3, delta.get_microseconds() as i64);
+        } else {
+            // 时区仅支持固定偏移量 (`datetime.timezone`), isoformat 无法记录其他时区的规则
+            if name != "date" {
+                let tzinfo = value.getattr(intern!(py, "tzinfo"))?;
+                if !tzinfo.is_none()
+                    && !tzinfo.is_exact_instance(TIMEZONE_TYPE.import(py, "datetime", "timezone")?)
+                {
+                    return Err(unsupported(value));
+                }
+            }
+            writer.write_int(0, KIND_DATETIME);
+            writer.write_string(1, name);
+            let text = value.call_method0(intern!(py, "isoformat"))?;
+            writer.write_string(2, text.cast::<PyString>()?.to_str()?);
+        }
+    } else if let Some(name) = stdlib_type_name(value, "ipaddress", &IP_TYPES)? {
+        writer.write_int(0, KIND_IP);
+        writer.write_string(1, name);
+        writer.write_string(2, value.str()?.to_str()?);
+    } else {
+        return Err(unsupported(value));
+    }
+    writer.write_tag(0, JceType::StructEnd);
+    Ok(())
+}
+
+fn unsupported(value: &Bound<'_, PyAny>) -> PyErr {
+    match value.repr() {
+        Ok(repr) => PyTypeError::new_err(format!("Cannot serialize schema value {}", repr)),
+        Err(err) => err,
+    }
+}
+
+/// 以 List 写入序列的元素.
+fn write_items<'py>(
+    writer: &mut Writer,
+    items: impl ExactSizeIterator<Item = Bound<'py, PyAny>>,
+) -> PyResult<()> {
+    writer.write_list_header(1, items.len());
+    for item in items {
+        write_value(writer, 0, &item)?;
+    }
+    Ok(())
+}
+
+fn is_enum_member(value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    value.is_instance(ENUM_TYPE.import(value.py(), "enum", "Enum")?)
+}
+
+/// 类型能否出现在描述中: 只有 Schema 实际引用的 Struct (提供 Schema 方法的类) 与枚举类.
+fn is_schema_type(cls: &Bound<'_, PyType>) -> PyResult<bool> {
+    Ok(schema_method(cls.as_any()).is_some()
+        || cls.is_subclass(ENUM_TYPE.import(cls.py(), "enum", "Enum")?)?)
+}
+
+/// 值的类型属于标准库模块 `module` 且为 `names` 之一 (不含子类) 时返回其类名.
+fn stdlib_type_name(
+    value: &Bound<'_, PyAny>,
+    module: &str,
+    names: &[&'static str],
+) -> PyResult<Option<&'static str>> {
+    let cls = value.get_type();
+    if cls.module()?.to_str()? != module {
+        return Ok(None);
+    }
+    let qualname = cls.qualname()?;
+    Ok(names.iter().copied().find(|name| qualname == *name))
+}
+
+/// 类型的 `module:qualname` 引用; 函数内定义的类型无法重新导入, 返回 `None`.
+fn type_path(cls: &Bound<'_, PyType>) -> PyResult<Option<String>> {
+    let module = cls.module()?.to_string();
+    let qualname = cls.qualname()?.to_string();
+    if qualname.contains("<locals>") {
+        return Ok(None);
+    }
+    Ok(Some(format!("{}:{}", module, qualname)))
+}
+
+/// 按 `module:qualname` 导入类型.
+fn import_type<'py>(py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyAny>> {
+    let (module, qualname) = path
+        .split_once(':')
+        .ok_or_else(|| PyValueError::new_err(format!("Invalid type reference: {}", path)))?;
+    let mut obj = py.import(module)?.into_any();
+    for part in qualname.split('.') {
+        obj = obj.getattr(part)?;
+    }
+    if obj.cast::<PyType>().is_err() {
+        return Err(PyTypeError::new_err(format!("{} is not a type", path)));
+    }
+    Ok(obj)
+}
+
+fn invalid(reader: &JceReader<'_, BigEndian>, msg: impl Into<String>) -> PyErr {
+    CodecError::new(reader.position() as usize, msg).into()
+}
+
+/// 读取字段头部并检查 Tag 与类型.
+fn expect_head(
+    reader: &mut JceReader<'_, BigEndian>,
+    tag: u8,
+    accepts: impl Fn(JceType) -> bool,
+) -> PyResult<JceType> {
+    let (t, jce_type) = reader.read_head()?;
+    if t != tag || !accepts(jce_type) {
+        return Err(invalid(
+            reader,
+            format!("Unexpected field tag {} type {:?}", t, jce_type),
+        ));
+    }
+    Ok(jce_type)
+}
+
+fn read_int_field(reader: &mut JceReader<'_, BigEndian>, tag: u8) -> PyResult<i64> {
+    let t = expect_head(reader, tag, |t| {
+        matches!(
+            t,
+            JceType::ZeroTag | JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8
+        )
+    })?;
+    Ok(reader.read_int(t)?)
+}
+
+fn read_string_field<'a>(reader: &mut JceReader<'a, BigEndian>, tag: u8) -> PyResult<Cow<'a, str>> {
+    let t = expect_head(reader, tag, |t| {
+        matches!(t, JceType::String1 | JceType::String4)
+    })?;
+    Ok(reader.read_string(t)?)
+}
+
+/// 读取 `write_value` 写入的值 (头部的 Tag 为 `tag`).
+fn read_value<'py>(
+    py: Python<'py>,
+    reader: &mut JceReader<'_, BigEndian>,
+    tag: u8,
+    depth: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    if depth > reader.max_depth() {
+        return Err(invalid(reader, "Max recursion depth exceeded"));
+    }
+    expect_head(reader, tag, |t| t == JceType::StructBegin)?;
+    let kind = read_int_field(reader, 0)?;
+    let value = match kind {
+        KIND_NONE => py.None().into_bound(py),
+        KIND_BOOL => PyBool::new(py, read_int_field(reader, 1)? != 0)
+            .to_owned()
+            .into_any(),
+        KIND_INT => read_int_field(reader, 1)?.into_pyobject(py)?.into_any(),
+        KIND_FLOAT => {
+            expect_head(reader, 1, |t| t == JceType::Double)?;
+            PyFloat::new(py, reader.read_double()?).into_any()
+        }
+        KIND_STR => PyString::new(py, &read_string_field(reader, 1)?).into_any(),
+        KIND_BYTES => {
+            expect_head(reader, 1, |t| t == JceType::SimpleList)?;
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem != JceType::Int1 {
+                return Err(invalid(reader, "Expected byte SimpleList"));
+            }
+            PyBytes::new(py, reader.read_bytes(count)?).into_any()
+        }
+        KIND_TUPLE | KIND_LIST => {
+            expect_head(reader, 1, |t| t == JceType::List)?;
+            let size = reader.read_container_size()?;
+            let items = PyList::empty(py);
+            for _ in 0..size {
+                items.append(read_value(py, reader, 0, depth + 1)?)?;
+            }
+            if kind == KIND_TUPLE {
+                items.to_tuple().into_any()
+            } else {
+                items.into_any()
+            }
+        }
+        KIND_DICT => {
+            expect_head(reader, 1, |t| t == JceType::Map)?;
+            let size = reader.read_container_size()?;
+            let dict = PyDict::new(py);
+            for _ in 0..size {
+                let key = read_string_field(reader, 0)?.into_owned();
+                dict.set_item(key, read_value(py, reader, 1, depth + 1)?)?;
+            }
+            dict.into_any()
+        }
+        KIND_TYPE => {
+            let path = read_string_field(reader, 1)?;
+            let cls = import_type(py, &path)?;
+            // 与写入时一致, 不接受 Struct 与枚举类以外的类型
+            if !is_schema_type(cls.cast::<PyType>()?)? {
+                return Err(PyTypeError::new_err(format!(
+                    "{} is not a Struct or Enum type",
+                    path
+                )));
+            }
+            cls
+        }
+        KIND_ENUM => {
+            let path = read_string_field(reader, 1)?;
+            let cls = import_type(py, &path)?;
+            // 只允许调用枚举类, 避免描述借 KIND_ENUM 调用任意可导入的类型
+            if !cls
+                .cast::<PyType>()?
+                .is_subclass(ENUM_TYPE.import(py, "enum", "Enum")?)?
+            {
+                return Err(PyTypeError::new_err(format!("{} is not an Enum", path)));
+            }
+            cls.call1((read_value(py, reader, 2, depth + 1)?,))?
+        }
+        KIND_DECIMAL => decimal_type(py)?.call1((&*read_string_field(reader, 1)?,))?,
+        KIND_DATETIME => {
+            let name = read_string_field(reader, 1)?;
+            let Some(name) = DATETIME_TYPES
+                .into_iter()
+                .find(|t| *t == name && *t != "timedelta")
+            else {
+                return Err(invalid(reader, format!("Unknown datetime type {}", name)));
+            };
+            let text = read_string_field(reader, 2)?;
+            py.import(intern!(py, "datetime"))?
+                .getattr(name)?
+                .call_method1(intern!(py, "fromisoformat"), (&*text,))?
+        }
+        KIND_TIMEDELTA => {
+            let mut parts = [0i32; 3];
+            for (part, tag) in parts.iter_mut().zip(1..) {
+                *part = i32::try_from(read_int_field(reader, tag)?)
+                    .map_err(|_| invalid(reader, "timedelta component out of range"))?;
+            }
+            PyDelta::new(py, parts[0], parts[1], parts[2], true)?.into_any()
+        }
+        KIND_IP => {
+            let name = read_string_field(reader, 1)?;
+            let Some(name) = IP_TYPES.into_iter().find(|t| *t == name) else {
+                return Err(invalid(reader, format!("Unknown ipaddress type {}", name)));
+            };
+            let text = read_string_field(reader, 2)?;
+            py.import(intern!(py, "ipaddress"))?
+                .getattr(name)?
+                .call1((&*text,))?
+        }
+        _ => return Err(invalid(reader, format!("Unknown value kind {}", kind))),
+    };
+    expect_head(reader, 0, |t| t == JceType::StructEnd)?;
+    Ok(value)
+}
+
+#[pyfunction]
+/// 将 Schema 列表序列化为可持久化的二进制描述.
+///
+/// 支持的值为 None, bool, int, float, str, bytes, tuple, list, dict (字符串键),
+/// `Decimal`, `datetime`/`date`/`time` (无时区或固定偏移量)/`timedelta`,
+/// `ipaddress` 的地址/网络/接口, 可导入的类型 (以 `module:qualname` 引用) 及其枚举成员.
+///
+/// Args:
+///     schema (type | list): Struct 类或 Schema 列表.
+///
+/// Returns:
+///     bytes: 二进制描述.
+///
+/// Raises:
+///     TypeError: Schema 中含有无法序列化的值 (如函数内定义的类型, 任意对象默认值).
+pub fn serialize_schema<'py>(
+    py: Python<'py>,
+    schema: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let list = match schema.cast::<PyList>() {
+        Ok(list) => list.clone(),
+        Err(_) => schema_method(schema)
+            .ok_or_else(|| PyTypeError::new_err("Expected a Struct type or schema list"))?
+            .call0()?
+            .cast_into::<PyList>()?,
+    };
+    let mut writer = Writer::new();
+    writer.write_raw(MAGIC);
+    writer.write_list_header(0, list.len());
+    for item in list.iter() {
+        let tuple = item
+            .cast::<PyTuple>()
+            .map_err(|_| PyTypeError::new_err("Schema item must be a tuple"))?;
+        writer.write_list_header(0, tuple.len());
+        for value in tuple.iter() {
+            write_value(&mut writer, 0, &value)?;
+        }
+    }
+    Ok(PyBytes::new(py, &writer.finish()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, owner=None, max_depth=None))]
+/// 从二进制描述加载并编译 Schema.
+///
+/// 结果可作为 `dumps`/`loads` 的 Schema 参数. 指定 `owner` 时同时缓存到该类上,
+/// 该类之后的编解码直接使用此结果, 不再调用其 Schema 方法.
+///
+/// 安全: 描述中的类型引用会被导入 (执行对应模块的导入代码), 枚举成员通过调用
+/// 其枚举类构造. 与 pickle 类似, 只能加载可信来源 (如本程序自身生成) 的描述.
+/// 导入的对象必须是 Struct (提供 Schema 方法的类) 或枚举类, 否则抛出 TypeError,
+/// 但此时模块的导入代码已经执行.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): `serialize_schema` 生成的描述.
+///     owner (type | None): 描述对应的 Struct 类, 提供字段钩子.
+///     max_depth (int | None): 默认值等嵌套数据的最大深度, 默认为 100.
+///
+/// Returns:
+///     capsule: 编译后的 Schema.
+///
+/// Raises:
+///     DecodeError: 描述格式无效.
+///     TypeError: 描述引用了 Struct 与枚举类以外的对象.
+pub fn deserialize_schema(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    owner: Option<&Bound<'_, PyType>>,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyCapsule>> {
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let Some(body) = bytes.strip_prefix(MAGIC) else {
+        return Err(PyValueError::new_err("Invalid schema descriptor header"));
+    };
+    let mut reader =
+        JceReader::<BigEndian>::new(body).with_max_depth(max_depth.unwrap_or(MAX_DEPTH));
+    let list = PyList::empty(py);
+    expect_head(&mut reader, 0, |t| t == JceType::List)?;
+    for _ in 0..reader.read_container_size()? {
+        expect_head(&mut reader, 0, |t| t == JceType::List)?;
+        let size = reader.read_container_size()?;
+        let item = PyList::empty(py);
+        for _ in 0..size {
+            item.append(read_value(py, &mut reader, 0, 0)?)?;
+        }
+        list.append(item.to_tuple())?;
+    }
+    if !reader.is_end() {
+        return Err(invalid(&reader, "Trailing data after schema descriptor"));
+    }
+    let mut compiled = build_schema(py, &list)?;
+    if let Some(owner) = owner
+        && compiled.fields.iter().any(|f| f.has_deserializer)
+    {
+        compiled.owner = Some(owner.clone().unbind());
+    }
+    let capsule = PyCapsule::new(py, compiled, None)?.unbind();
+    if let Some(owner) = owner {
+        owner.setattr("__tars_compiled_schema__", &capsule)?;
+    }
+    Ok(capsule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_round_trip() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let items = PyList::empty(py);
+            items
+                .append(("name", 300, 1.5, PyBytes::new(py, b"ab"), py.None(), true))
+                .unwrap();
+            let dict = PyDict::new(py);
+            dict.set_item("elem", &items).unwrap();
+            dict.set_item(
+                "enum",
+                py.import("enum").unwrap().getattr("IntEnum").unwrap(),
+            )
+            .unwrap();
+
+            let builtins = py
+                .eval(
+                    c"(__import__('decimal').Decimal('1.50'), \
+                   __import__('datetime').datetime(2024, 1, 2, 3, 4, 5, 6, \
+                       __import__('datetime').timezone.utc), \
+                   __import__('datetime').date(2024, 1, 2), \
+                   __import__('datetime').time(1, 2), \
+                   __import__('datetime').timedelta(days=-1, microseconds=7), \
+                   __import__('ipaddress').ip_network('10.0.0.0/8'), \
+                   __import__('ipaddress').ip_interface('::1/128'))",
+                    None,
+                    None,
+                )
+                .unwrap();
+            dict.set_item("logical", builtins).unwrap();
+
+            let mut writer = Writer::new();
+            write_value(&mut writer, 3, &dict).unwrap();
+            let bytes = writer.finish();
+            let mut reader = JceReader::<BigEndian>::new(&bytes);
+            let decoded = read_value(py, &mut reader, 3, 0).unwrap();
+            assert!(reader.is_end());
+            assert!(decoded.eq(&dict).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_unsupported_value() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let mut writer = Writer::new();
+            let value = PyList::new(
+                py,
+                [PyDict::new(py).into_any(), py.Ellipsis().into_bound(py)],
+            )
+            .unwrap();
+            let err = write_value(&mut writer, 0, &value).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+
+            // 非固定偏移量的时区无法以 isoformat 还原
+            let zoned = py
+                .eval(
+                    c"__import__('datetime').datetime(2024, 1, 1, \
+                       tzinfo=__import__('zoneinfo').ZoneInfo('UTC'))",
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert!(write_value(&mut Writer::new(), 0, &zoned).is_err());
+        });
+    }
+
+    #[test]
+    fn test_enum_kind_requires_enum_class() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let mut writer = Writer::new();
+            writer.write_tag(0, JceType::StructBegin);
+            writer.write_int(0, KIND_ENUM);
+            writer.write_string(1, "builtins:int");
+            write_value(&mut writer, 2, &1i64.into_pyobject(py).unwrap()).unwrap();
+            writer.write_tag(0, JceType::StructEnd);
+            let bytes = writer.finish();
+
+            let mut reader = JceReader::<BigEndian>::new(&bytes);
+            let err = read_value(py, &mut reader, 0, 0).unwrap_err();
+            assert!(err.to_string().contains("is not an Enum"));
+        });
+    }
+
+    #[test]
+    fn test_type_kind_requires_schema_type() {
+        #[allow(deprecated)]
+        pyo3::prepare_freethreaded_python();
+        Python::attach(|py| {
+            let read_type = |path: &str| {
+                let mut writer = Writer::new();
+                writer.write_tag(0, JceType::StructBegin);
+                writer.write_int(0, KIND_TYPE);
+                writer.write_string(1, path);
+                writer.write_tag(0, JceType::StructEnd);
+                let bytes = writer.finish();
+                let mut reader = JceReader::<BigEndian>::new(&bytes);
+                read_value(py, &mut reader, 0, 0).map(|v| v.unbind())
+            };
+            let err = read_type("os:sep").unwrap_err();
+            assert!(err.to_string().contains("is not a type"));
+            let err = read_type("subprocess:Popen").unwrap_err();
+            assert!(err.to_string().contains("is not a Struct or Enum type"));
+            assert!(read_type("enum:IntEnum").is_ok());
+
+            // 写入时同样拒绝其他类型
+            let int_type = py.get_type::<PyInt>().into_any();
+            assert!(write_value(&mut Writer::new(), 0, &int_type).is_err());
+        });
+    }
+}
@@ -1,26 +1,533 @@
-use crate::bindings::schema::{CompiledSchema, compile_schema};
-use crate::codec::consts::JceType;
+use crate::bindings::fixed::{extract_fixed, fixed_to_py};
+use crate::bindings::logical::{LogicalType, TimeUnit, datetime_to_timestamp, decimal_type};
+use crate::bindings::schema::{
+    CompiledSchema, FieldDef, NestedSchema, TYPE_CODE_BOOL, TypeDesc, build_schema,
+    denormalize_struct,
+};
+use crate::bindings::str_view::JceStr;
+use crate::bindings::stream::write_length_prefixed;
+use crate::codec::canonical::canonicalize;
+use crate::codec::consts::{JceType, MAX_DEPTH};
+use crate::codec::debug;
+use crate::codec::endian::Endianness;
+use crate::codec::error::Error as CodecError;
 use crate::codec::reader::JceReader;
-use crate::codec::writer::JceWriter;
+use crate::codec::scanner::JceScanner;
+use crate::codec::writer::{BytesMark, JceWriter, WriterConfig};
 use byteorder::{BigEndian, LittleEndian};
-use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{
+    PyLookupError, PyOverflowError, PyRuntimeError, PyTypeError, PyUnicodeDecodeError, PyValueError,
+};
+use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyCapsule, PyDict, PyList, PyTuple, PyType};
-use std::cell::RefCell;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyCapsule, PyDict, PyFloat, PyFrozenSet, PyInt, PyList,
+    PyMemoryView, PySet, PySlice, PyString, PyTuple, PyType,
+};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::LocalKey;
+
+static ARRAY_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static BASE_MODEL_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static DECODE_ERROR_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static DEEPCOPY: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
 
 thread_local! {
     static TLS_WRITER: RefCell<JceWriter<Vec<u8>, BigEndian>> = RefCell::new(JceWriter::new());
+    static TLS_WRITER_LE: RefCell<JceWriter<Vec<u8>, LittleEndian>> =
+        RefCell::new(JceWriter::with_buffer(Vec::with_capacity(128)));
+    static DEPTH_LIMIT: Cell<usize> = const { Cell::new(MAX_DEPTH) };
+    static DECODE_LIMITS: Cell<DecodeLimits> = const { Cell::new(DecodeLimits::UNBOUNDED) };
+    static TEXT_CONTROL_MASK: Cell<u128> = const { Cell::new(DEFAULT_TEXT_CONTROL_MASK) };
+    static VIEW_SOURCE: RefCell<Option<ViewSource>> = const { RefCell::new(None) };
+    static STR_ENCODING: RefCell<Option<Py<PyString>>> = const { RefCell::new(None) };
+    static MAP_FACTORY: RefCell<Option<Py<PyAny>>> = const { RefCell::new(None) };
+    static TAG_HOOKS: RefCell<Option<Py<PyDict>>> = const { RefCell::new(None) };
+    static CALLBACK_FAILED: Cell<bool> = const { Cell::new(false) };
+    static STRING_CACHE: RefCell<HashMap<Box<str>, Py<PyString>>> = RefCell::new(HashMap::new());
+}
+
+/// 当前调用允许的最大递归深度.
+#[inline]
+pub(crate) fn max_depth() -> usize {
+    DEPTH_LIMIT.with(Cell::get)
+}
+
+/// 在作用域内覆盖最大递归深度 (`None` 表示默认值), 离开作用域时恢复原值.
+///
+/// 恢复原值而非默认值, 保证字段序列化器等回调中重入的调用互不影响.
+pub(crate) struct DepthLimitGuard(usize);
+
+impl DepthLimitGuard {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self(DEPTH_LIMIT.with(|cell| cell.replace(limit.unwrap_or(MAX_DEPTH))))
+    }
+}
+
+impl Drop for DepthLimitGuard {
+    fn drop(&mut self) {
+        DEPTH_LIMIT.with(|cell| cell.set(self.0));
+    }
+}
+
+/// 在作用域内设置字符串字段的文本编码, 离开作用域时恢复原值.
+///
+/// 编码名按 Python 的 `codecs.lookup` 解析, UTF-8 (默认) 记为 None 以走原生快速路径.
+pub(crate) struct StrEncodingGuard(Option<Py<PyString>>);
+
+impl StrEncodingGuard {
+    pub(crate) fn new(py: Python<'_>, encoding: Option<&str>) -> PyResult<Self> {
+        let encoding = match encoding {
+            // UTF-8 的常见写法无需查询 codecs
+            Some(name)
+                if ["utf-8", "utf8", "utf_8"]
+                    .iter()
+                    .any(|alias| name.eq_ignore_ascii_case(alias)) =>
+            {
+                None
+            }
+            Some(name) => {
+                let info = py
+                    .import("codecs")?
+                    .call_method1("lookup", (name,))
+                    .map_err(|err| {
+                        if err.is_instance_of::<PyLookupError>(py) {
+                            PyValueError::new_err(format!("Unknown str_encoding '{}'", name))
+                        } else {
+                            err
+                        }
+                    })?;
+                let canonical = info.getattr("name")?.cast_into::<PyString>()?;
+                if canonical.to_str()? == "utf-8" {
+                    None
+                } else {
+                    Some(canonical.unbind())
+                }
+            }
+            None => None,
+        };
+        Ok(Self(STR_ENCODING.with(|cell| cell.replace(encoding))))
+    }
+}
+
+impl Drop for StrEncodingGuard {
+    fn drop(&mut self) {
+        STR_ENCODING.with(|cell| *cell.borrow_mut() = self.0.take());
+    }
+}
+
+/// 在作用域内设置解码 Map 时使用的容器工厂, 离开作用域时恢复原值.
+///
+/// 不接受 `map_factory` 的解码入口以 `None` 设置, 避免在钩子中嵌套调用时继承外层的工厂.
+pub(crate) struct MapFactoryGuard(Option<Py<PyAny>>);
+
+impl MapFactoryGuard {
+    pub(crate) fn new(factory: Option<&Bound<'_, PyAny>>) -> Self {
+        let factory = factory.map(|f| f.clone().unbind());
+        Self(MAP_FACTORY.with(|cell| cell.replace(factory)))
+    }
+}
+
+impl Drop for MapFactoryGuard {
+    fn drop(&mut self) {
+        MAP_FACTORY.with(|cell| *cell.borrow_mut() = self.0.take());
+    }
+}
+
+/// 创建解码 Map 使用的容器: 默认为 dict, 设置了工厂时为其无参调用的结果.
+fn new_map(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    match MAP_FACTORY.with(|cell| cell.borrow().as_ref().map(|f| f.clone_ref(py))) {
+        Some(factory) => factory
+            .bind(py)
+            .call0()
+            .inspect_err(|_| mark_callback_failed()),
+        None => Ok(PyDict::new(py).into_any()),
+    }
+}
+
+/// 在作用域内设置按 Tag 的解码后处理函数, 离开作用域时恢复原值.
+///
+/// 不接受钩子的解码入口以 `None` 设置, 避免在钩子中嵌套调用时继承外层的钩子.
+pub(crate) struct TagHooksGuard(Option<Py<PyDict>>);
+
+impl TagHooksGuard {
+    pub(crate) fn new(hooks: Option<&Bound<'_, PyDict>>) -> Self {
+        let hooks = hooks.map(|h| h.clone().unbind());
+        Self(TAG_HOOKS.with(|cell| cell.replace(hooks)))
+    }
+}
+
+impl Drop for TagHooksGuard {
+    fn drop(&mut self) {
+        TAG_HOOKS.with(|cell| *cell.borrow_mut() = self.0.take());
+    }
+}
+
+/// 记录用户回调 (Tag 钩子, Map 工厂) 抛出了异常.
+///
+/// Auto 模式试探解析嵌套结构体失败时会回退为 bytes, 但回调抛出的异常必须原样传播.
+fn mark_callback_failed() {
+    CALLBACK_FAILED.with(|flag| flag.set(true));
+}
+
+/// 向 `new_map` 创建的容器写入键值对.
+///
+/// 仅对精确的 dict 使用 `PyDict_SetItem`, 子类 (如 `OrderedDict`) 与其他映射类型
+/// 走 `__setitem__`, 以免绕过其自身的逻辑.
+#[inline]
+fn map_set_item<'py, K, V>(map: &Bound<'py, PyAny>, key: K, value: V) -> PyResult<()>
+where
+    K: IntoPyObject<'py>,
+    V: IntoPyObject<'py>,
+{
+    match map.cast_exact::<PyDict>() {
+        Ok(dict) => dict.set_item(key, value),
+        Err(_) => map.set_item(key, value),
+    }
+}
+
+/// 当前调用的字符串编码, UTF-8 时为 None.
+#[inline]
+fn str_encoding(py: Python<'_>) -> Option<Py<PyString>> {
+    STR_ENCODING.with(|cell| cell.borrow().as_ref().map(|e| e.clone_ref(py)))
+}
+
+/// 写入字符串字段, 设置了非 UTF-8 编码时先转码为该编码的字节.
+fn write_text<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    text: &str,
+) -> PyResult<()> {
+    match str_encoding(py) {
+        Some(encoding) => {
+            let encoded = PyString::new(py, text).call_method1("encode", (encoding,))?;
+            writer.write_string_bytes(tag, encoded.cast::<PyBytes>()?.as_bytes());
+        }
+        None => writer.write_string(tag, text),
+    }
+    Ok(())
+}
+
+/// 按指定编码读取字符串字段.
+fn read_encoded_text<E: Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'_, E>,
+    jce_type: JceType,
+    encoding: &Py<PyString>,
+) -> PyResult<Py<PyAny>> {
+    let start = reader.position() as usize;
+    let bytes = reader.read_string_bytes(jce_type)?;
+    PyBytes::new(py, bytes)
+        .call_method1("decode", (encoding.bind(py),))
+        .map(Bound::unbind)
+        .map_err(|err| {
+            if err.is_instance_of::<PyUnicodeDecodeError>(py) {
+                CodecError::new(
+                    start,
+                    format!("Invalid {} string: {}", encoding.bind(py), err.value(py)),
+                )
+                .into()
+            } else {
+                err
+            }
+        })
+}
+
+/// 通用解码的宽度限制, 用于不可信输入.
+#[derive(Clone, Copy)]
+struct DecodeLimits {
+    /// 单个结构体的最大字段数.
+    max_fields: Option<usize>,
+    /// 一次解码中结构体字段, List 元素与 Map 键值对的最大总数.
+    max_total_elements: Option<usize>,
+    /// 当前调用已登记的元素数.
+    total: usize,
+}
+
+impl DecodeLimits {
+    const UNBOUNDED: Self = Self {
+        max_fields: None,
+        max_total_elements: None,
+        total: 0,
+    };
+}
+
+/// 在作用域内设置通用解码的宽度限制, 离开作用域时恢复原值.
+pub(crate) struct DecodeLimitGuard(DecodeLimits);
+
+impl DecodeLimitGuard {
+    pub(crate) fn new(max_fields: Option<usize>, max_total_elements: Option<usize>) -> Self {
+        let limits = DecodeLimits {
+            max_fields,
+            max_total_elements,
+            total: 0,
+        };
+        Self(DECODE_LIMITS.with(|cell| cell.replace(limits)))
+    }
+}
+
+impl Drop for DecodeLimitGuard {
+    fn drop(&mut self) {
+        DECODE_LIMITS.with(|cell| cell.set(self.0));
+    }
+}
+
+/// 登记 `count` 个即将解码的元素, 累计超过 `max_total_elements` 时报错.
+///
+/// 容器在读取大小后、创建任何元素前即登记, 超限的数据不会分配内存.
+fn count_elements(count: usize, offset: usize) -> PyResult<()> {
+    DECODE_LIMITS.with(|cell| {
+        let mut limits = cell.get();
+        let Some(max) = limits.max_total_elements else {
+            return Ok(());
+        };
+        limits.total = limits.total.saturating_add(count);
+        cell.set(limits);
+        if limits.total > max {
+            return Err(CodecError::new(
+                offset,
+                format!("Total element count exceeds limit {}", max),
+            )
+            .into());
+        }
+        Ok(())
+    })
+}
+
+/// 当前调用已登记的元素数, 与 `restore_element_total` 配合撤销试探性解码的登记.
+fn element_total() -> usize {
+    DECODE_LIMITS.with(|cell| cell.get().total)
+}
+
+/// 将已登记的元素数恢复为 `total`.
+fn restore_element_total(total: usize) {
+    DECODE_LIMITS.with(|cell| {
+        let mut limits = cell.get();
+        limits.total = total;
+        cell.set(limits);
+    });
+}
+
+/// 当前调用的单个结构体最大字段数.
+#[inline]
+fn max_fields() -> Option<usize> {
+    DECODE_LIMITS.with(|cell| cell.get().max_fields)
+}
+
+/// 登记结构体中的一个字段: `fields` 为当前结构体已读取的字段数,
+/// 超过 `max_fields` 或累计元素数超限时报错.
+fn count_struct_field(
+    fields: &mut usize,
+    max_fields: Option<usize>,
+    offset: usize,
+) -> PyResult<()> {
+    *fields += 1;
+    if let Some(max) = max_fields
+        && *fields > max
+    {
+        return Err(
+            CodecError::new(offset, format!("Struct field count exceeds limit {}", max)).into(),
+        );
+    }
+    count_elements(1, offset)
+}
+
+/// `BytesMode::View` 与 `OPT_STR_VIEW` 使用的输入源: 输入数据的起始地址, 长度与覆盖整个输入的 memoryview.
+struct ViewSource {
+    base: usize,
+    len: usize,
+    view: Py<PyAny>,
+}
+
+/// 在作用域内登记视图的输入源, 离开作用域时恢复原值.
+struct ViewSourceGuard(Option<ViewSource>);
+
+impl ViewSourceGuard {
+    /// `enabled` 为真且输入直接借用自 Python 对象时登记;
+    /// 拷贝得到的临时数据不登记, 解码时回退为 bytes/str.
+    fn new(data: &Bound<'_, PyAny>, input: &InputData<'_>, enabled: bool) -> PyResult<Self> {
+        let source = if enabled && !matches!(input, InputData::Owned(_)) {
+            let bytes = input.as_bytes();
+            Some(ViewSource {
+                base: bytes.as_ptr() as usize,
+                len: bytes.len(),
+                view: PyMemoryView::from(data)?.into_any().unbind(),
+            })
+        } else {
+            None
+        };
+        Ok(Self(VIEW_SOURCE.with(|cell| cell.replace(source))))
+    }
+}
+
+impl Drop for ViewSourceGuard {
+    fn drop(&mut self) {
+        VIEW_SOURCE.with(|cell| *cell.borrow_mut() = self.0.take());
+    }
+}
+
+/// 返回 `bytes` 在已登记输入上的 memoryview 切片.
+///
+/// 未登记输入或 `bytes` 不属于该输入时返回 None, 由调用方回退为拷贝.
+fn view_slice(py: Python<'_>, bytes: &[u8]) -> PyResult<Option<Py<PyAny>>> {
+    VIEW_SOURCE.with(|cell| {
+        let source = cell.borrow();
+        let Some(source) = source.as_ref() else {
+            return Ok(None);
+        };
+        let start = (bytes.as_ptr() as usize).wrapping_sub(source.base);
+        if start > source.len || bytes.len() > source.len - start {
+            return Ok(None);
+        }
+        let slice = PySlice::new(py, start as isize, (start + bytes.len()) as isize, 1);
+        Ok(Some(source.view.bind(py).get_item(slice)?.unbind()))
+    })
+}
+
+/// 字符串缓存的最大条目数, 超过后整体清空, 防止恶意数据使缓存无限增长.
+const STRING_CACHE_CAPACITY: usize = 4096;
+/// 参与缓存的字符串最大字节数, 更长的字符串重复的可能性低, 直接创建.
+const STRING_CACHE_MAX_LEN: usize = 64;
+
+/// 将解码得到的字符串转换为 Python 对象.
+///
+/// `OPT_INTERN_STRINGS` 下短字符串通过线程局部缓存复用同一个 Python 对象,
+/// 缓存跨调用保留, 因此同一线程上连续解码的多个数据包也能共享.
+fn string_to_py(py: Python<'_>, s: &str, options: i64) -> Py<PyAny> {
+    if options & OPT_INTERN_STRINGS == 0 || s.len() > STRING_CACHE_MAX_LEN {
+        return PyString::new(py, s).into_any().unbind();
+    }
+    STRING_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(cached) = cache.get(s) {
+            return cached.clone_ref(py).into_any();
+        }
+        if cache.len() >= STRING_CACHE_CAPACITY {
+            cache.clear();
+        }
+        let value = PyString::new(py, s).unbind();
+        cache.insert(s.into(), value.clone_ref(py));
+        value.into_any()
+    })
+}
+
+// 序列化选项位, 与 Python 侧 `Option` 一一对应; 公开以供基准测试等 Rust 调用方使用.
+pub const OPT_OMIT_DEFAULT: i64 = 32;
+pub const OPT_EXCLUDE_UNSET: i64 = 64;
+pub const OPT_RELEASE_GIL: i64 = 128;
+pub const OPT_REJECT_NONFINITE: i64 = 256;
+pub const OPT_NO_DEFAULT_FILL: i64 = 512;
+pub const OPT_ENUM_PASSTHROUGH: i64 = 1024;
+pub const OPT_DUP_TAG_ERROR: i64 = 2048;
+pub const OPT_DUP_TAG_LIST: i64 = 4096;
+pub const OPT_ORDERED_PAIRS: i64 = 8192;
+pub const OPT_SORT_MAP_KEYS: i64 = 16384;
+pub const OPT_INTERN_STRINGS: i64 = 32768;
+pub const OPT_LIST_AS_TUPLE: i64 = 65536;
+pub const OPT_KEEP_INT_WIDTH: i64 = 131072;
+pub const OPT_WIDEN_FLOATS: i64 = 262144;
+pub const OPT_STR_VIEW: i64 = 524288;
+pub const OPT_SORT_TAGS: i64 = 1048576;
+pub const OPT_SLOTS: i64 = 2097152;
+pub const OPT_FIXED_SIMPLE_LIST_LEN: i64 = 4194304;
+pub const OPT_DATETIME_MILLIS: i64 = 8388608;
+pub const OPT_NAIVE_DATETIME_UTC: i64 = 16777216;
+pub const OPT_OMIT_NONE_DEFAULTS: i64 = 33554432;
+pub const OPT_STRICT_LIST: i64 = 67108864;
+pub const OPT_OMIT_EMPTY: i64 = 134217728;
+pub const OPT_STRICT_STRUCT_END: i64 = 268435456;
+pub const OPT_VALIDATE_FIRST: i64 = 536870912;
+pub const OPT_STRICT_BYTES: i64 = 1073741824;
+pub const OPT_INCLUDE_NONE: i64 = 2147483648;
+
+/// 缺失字段回填时是否跳过该字段.
+///
+/// `OPT_OMIT_NONE_DEFAULTS` 下默认值为 `None` 的字段 (含使用 `default_factory` 的字段)
+/// 不写入结果, 交由模型自身的默认值与验证器处理.
+fn skip_backfill(py: Python<'_>, field: &FieldDef, options: i64) -> bool {
+    options & OPT_OMIT_NONE_DEFAULTS != 0 && field.default_val.is_none(py)
+}
+
+/// 回填缺失字段时使用的默认值.
+///
+/// 不可变的标量直接共享, 其余值 (list, dict 等) 经 `copy.deepcopy` 复制,
+/// 避免多个解码结果共享同一个可变默认值.
+fn fresh_default<'py>(py: Python<'py>, field: &FieldDef) -> PyResult<Bound<'py, PyAny>> {
+    let value = field.default_val.bind(py);
+    if value.is_none()
+        || value.is_exact_instance_of::<PyBool>()
+        || value.is_exact_instance_of::<PyInt>()
+        || value.is_exact_instance_of::<PyFloat>()
+        || value.is_exact_instance_of::<PyString>()
+        || value.is_exact_instance_of::<PyBytes>()
+    {
+        return Ok(value.clone());
+    }
+    DEEPCOPY
+        .get_or_try_init(py, || {
+            py.import("copy")?.getattr("deepcopy").map(Bound::unbind)
+        })?
+        .bind(py)
+        .call1((value,))
+}
+
+/// 由 options 得到的写入器配置.
+fn writer_config(options: i64) -> WriterConfig {
+    WriterConfig {
+        fixed_simple_list_len: options & OPT_FIXED_SIMPLE_LIST_LEN != 0,
+        ..WriterConfig::default()
+    }
+}
+
+/// 由 options 与字符串写入参数得到的写入器配置.
+///
+/// `string1_max` 超出 0-255 时抛出 `ValueError`.
+pub(crate) fn string_writer_config(
+    options: i64,
+    string1_max: i64,
+    force_string4: bool,
+) -> PyResult<WriterConfig> {
+    let string1_max = u8::try_from(string1_max).map_err(|_| {
+        PyValueError::new_err(format!(
+            "string1_max must be between 0 and 255, got {}",
+            string1_max
+        ))
+    })?;
+    Ok(WriterConfig {
+        string1_max,
+        force_string4,
+        ..writer_config(options)
+    })
 }
 
-const MAX_DEPTH: usize = 100;
-const OPT_OMIT_DEFAULT: i32 = 32;
-const OPT_EXCLUDE_UNSET: i32 = 64;
+/// 以显式的 `endian` 参数 (`"big"`/`"little"`) 覆盖 options 中的字节序位.
+///
+/// 未指定时保留 `options & 1` 的原有行为.
+fn resolve_endian(options: i64, endian: Option<&str>) -> PyResult<i64> {
+    match endian {
+        None => Ok(options),
+        Some("big") => Ok(options & !1),
+        Some("little") => Ok(options | 1),
+        Some(other) => Err(PyValueError::new_err(format!(
+            "endian must be 'big' or 'little', got '{other}'"
+        ))),
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum BytesMode {
     Raw = 0,
     String = 1,
     Auto = 2,
+    View = 3,
 }
 
 impl From<u8> for BytesMode {
@@ -28,32 +535,209 @@ impl From<u8> for BytesMode {
         match v {
             1 => BytesMode::String,
             2 => BytesMode::Auto,
+            3 => BytesMode::View,
             _ => BytesMode::Raw,
         }
     }
 }
 
+/// 通过缓冲区协议借用的输入数据.
+///
+/// 接受 `bytes`, `bytearray`, `memoryview` 等任意支持缓冲区协议的对象.
+/// `bytes` 与连续缓冲区直接借用, 不产生拷贝; 非连续缓冲区会先拷贝为连续数据.
+/// 缓冲区在导出期间无法被调整大小 (如 `bytearray` 扩容会抛出 `BufferError`),
+/// 因此在解码调用期间借用其内存是安全的.
+pub(crate) enum InputData<'py> {
+    Bytes(Bound<'py, PyBytes>),
+    Buffer(PyBuffer<u8>),
+    Owned(Vec<u8>),
+}
+
+impl<'py> InputData<'py> {
+    /// 从 Python 对象获取输入数据.
+    pub(crate) fn new(data: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(bytes) = data.cast::<PyBytes>() {
+            return Ok(InputData::Bytes(bytes.clone()));
+        }
+        let buffer = PyBuffer::<u8>::get(data)?;
+        if buffer.is_c_contiguous() {
+            Ok(InputData::Buffer(buffer))
+        } else {
+            Ok(InputData::Owned(buffer.to_vec(data.py())?))
+        }
+    }
+
+    /// 获取底层字节切片.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        match self {
+            InputData::Bytes(bytes) => bytes.as_bytes(),
+            // 连续缓冲区: 指针与长度在 PyBuffer 存活期间有效
+            InputData::Buffer(buffer) => unsafe {
+                std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes())
+            },
+            InputData::Owned(vec) => vec,
+        }
+    }
+
+    /// 数据是否不可被其他线程修改 (可在释放 GIL 时安全读取).
+    ///
+    /// 只读缓冲区不代表不可变: `memoryview(bytearray).toreadonly()` 或 mmap 的只读视图
+    /// 仍可被其所有者修改, 因此只有 `bytes` 与自有的拷贝视为不可变.
+    pub(crate) fn is_immutable(&self) -> bool {
+        !matches!(self, InputData::Buffer(_))
+    }
+}
+
+/// `BytesMode::Auto` 默认允许出现在文本中的控制字符: \t, \n, \r.
+const DEFAULT_TEXT_CONTROL_MASK: u128 = (1 << b'\t') | (1 << b'\n') | (1 << b'\r');
+
+/// 将允许的控制字符列表转换为 ASCII 位掩码, `None` 表示默认的 \t, \n, \r.
+///
+/// 列表中只能包含 ASCII 控制字符 (0x00-0x1F 与 0x7F).
+pub(crate) fn text_control_mask(allowed: Option<&[u8]>) -> PyResult<u128> {
+    let Some(allowed) = allowed else {
+        return Ok(DEFAULT_TEXT_CONTROL_MASK);
+    };
+    allowed.iter().try_fold(0u128, |mask, &b| {
+        if b < 32 || b == 127 {
+            Ok(mask | (1 << b))
+        } else {
+            Err(PyValueError::new_err(format!(
+                "allowed_control_bytes must only contain ASCII control characters, got {:#04x}",
+                b
+            )))
+        }
+    })
+}
+
+/// 在作用域内覆盖 `check_safe_text` 允许的控制字符, 离开作用域时恢复原值.
+pub(crate) struct TextPolicyGuard(u128);
+
+impl TextPolicyGuard {
+    pub(crate) fn new(mask: u128) -> Self {
+        Self(TEXT_CONTROL_MASK.with(|cell| cell.replace(mask)))
+    }
+}
+
+impl Drop for TextPolicyGuard {
+    fn drop(&mut self) {
+        TEXT_CONTROL_MASK.with(|cell| cell.set(self.0));
+    }
+}
+
 /// 检查字节序列是否为安全的 UTF-8 文本.
 ///
-/// 排除 ASCII 控制字符 (除了 \t, \n, \r) 并验证 UTF-8 有效性.
+/// 排除 ASCII 控制字符 (默认允许 \t, \n, \r, 见 `TextPolicyGuard`) 并验证 UTF-8 有效性.
 /// 用于 `BytesMode::Auto` 判断是解码为 str 还是保留 bytes.
-fn check_safe_text(data: &[u8]) -> bool {
+pub(crate) fn check_safe_text(data: &[u8]) -> bool {
+    let allowed = TEXT_CONTROL_MASK.with(Cell::get);
     for &b in data {
-        if b < 32 {
-            if b != 9 && b != 10 && b != 13 {
-                return false;
-            }
-        } else if b == 127 {
+        if (b < 32 || b == 127) && allowed & (1 << b) == 0 {
             return false;
         }
     }
     std::str::from_utf8(data).is_ok()
 }
 
+/// 使用线程局部 Writer 完成一次编码.
+///
+/// `encode` 写入数据后由 `output` 读取结果, 随后 Writer 被清空但保留容量以供复用.
+/// Writer 已被占用 (如二进制块内嵌套编码导致的重入) 时回退到新分配的 Writer.
+/// `size_hint` 为预估的编码字节数, 编码前一次性预留, 避免编码过程中反复扩容.
+/// `config` 仅作用于本次编码.
+fn with_tls_writer<E: Endianness, R>(
+    tls: &'static LocalKey<RefCell<JceWriter<Vec<u8>, E>>>,
+    size_hint: usize,
+    config: WriterConfig,
+    encode: impl FnOnce(&mut JceWriter<Vec<u8>, E>) -> PyResult<()>,
+    output: impl FnOnce(&[u8]) -> R,
+) -> PyResult<R> {
+    tls.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut writer) => {
+            writer.clear();
+            writer.reserve(size_hint);
+            writer.set_config(config);
+            let result = encode(&mut writer).map(|_| output(writer.get_buffer()));
+            writer.clear();
+            writer.set_config(WriterConfig::default());
+            result
+        }
+        Err(_) => {
+            let mut writer =
+                JceWriter::<Vec<u8>, E>::with_buffer(Vec::with_capacity(size_hint.max(128)));
+            writer.set_config(config);
+            encode(&mut writer)?;
+            Ok(output(writer.get_buffer()))
+        }
+    })
+}
+
+/// 用户配置的 Schema 方法名, 为 None 时仅使用 `__get_core_schema__`.
+static SCHEMA_METHOD_NAME: Mutex<Option<Py<PyString>>> = Mutex::new(None);
+
+/// `SCHEMA_METHOD_NAME` 的版本号, 每次设置后递增.
+static SCHEMA_METHOD_VERSION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// 当前线程缓存的 (版本号, 方法名) 快照, 版本号未变化时无需加锁.
+    static SCHEMA_METHOD_CACHE: RefCell<(u64, Option<Py<PyString>>)> =
+        const { RefCell::new((0, None)) };
+}
+
+#[pyfunction]
+#[pyo3(signature = (name))]
+/// 设置用于获取结构体 Schema 的方法名.
+///
+/// 配置后优先调用该方法, 对象不存在该方法时回退到 `__get_core_schema__`,
+/// 因此 Tarsio 自身的 Struct 不受影响. 已编译的 Schema 缓存在类型上,
+/// 应在编码或解码任何数据之前设置.
+///
+/// Args:
+///     name (str | None): 方法名, 为 None 时恢复默认.
+pub fn set_schema_method_name(py: Python<'_>, name: Option<&str>) {
+    let name = name.map(|n| PyString::intern(py, n).unbind());
+    *SCHEMA_METHOD_NAME
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = name;
+    SCHEMA_METHOD_VERSION.fetch_add(1, Ordering::Release);
+}
+
+/// 当前配置的 Schema 方法名.
+///
+/// 热路径上只读取一次原子版本号, 仅在设置变化后才加锁刷新线程本地的快照.
+fn configured_schema_method_name(py: Python<'_>) -> Option<Py<PyString>> {
+    let version = SCHEMA_METHOD_VERSION.load(Ordering::Acquire);
+    if version == 0 {
+        return None;
+    }
+    SCHEMA_METHOD_CACHE.with_borrow_mut(|(cached_version, name)| {
+        if *cached_version != version {
+            *name = SCHEMA_METHOD_NAME
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .as_ref()
+                .map(|name| name.clone_ref(py));
+            *cached_version = version;
+        }
+        name.as_ref().map(|name| name.clone_ref(py))
+    })
+}
+
+/// 查找对象的 Schema 方法: 优先使用配置的方法名, 其次为 `__get_core_schema__`.
+pub(crate) fn schema_method<'py>(value: &Bound<'py, PyAny>) -> Option<Bound<'py, PyAny>> {
+    let py = value.py();
+    if let Some(name) = configured_schema_method_name(py)
+        && let Ok(method) = value.getattr(name)
+    {
+        return Some(method);
+    }
+    value.getattr(intern!(py, "__get_core_schema__")).ok()
+}
+
 /// 获取或编译 Python 类型的 Schema 缓存.
 ///
 /// 尝试从目标类型获取预编译的 Schema (`__tars_compiled_schema__`)。
-/// 如果不存在，则调用 `__get_core_schema__` 并编译它，然后缓存结果。
+/// 如果不存在，则调用 Schema 方法 (见 `schema_method`) 并编译它，然后缓存结果。
 ///
 /// Args:
 ///     py: Python 解释器实例.
@@ -61,7 +745,7 @@ fn check_safe_text(data: &[u8]) -> bool {
 ///
 /// Returns:
 ///     Option<Py<PyCapsule>>: 编译好的 Schema 胶囊 (如果输入有效).
-fn get_or_compile_schema(
+pub(crate) fn get_or_compile_schema(
     py: Python<'_>,
     schema_or_type: &Bound<'_, PyAny>,
 ) -> PyResult<Option<Py<PyCapsule>>> {
@@ -69,30 +753,69 @@ fn get_or_compile_schema(
         return Ok(Some(capsule.clone().unbind()));
     }
     if let Ok(cls) = schema_or_type.cast::<PyType>() {
-        if let Ok(cached) = cls.getattr("__tars_compiled_schema__")
+        // 仅查找类型自身的 __dict__, 避免子类复用父类的缓存
+        if let Some(cached) = cls
+            .getattr("__dict__")?
+            .call_method1("get", ("__tars_compiled_schema__",))
+            .ok()
             && let Ok(capsule) = cached.cast::<PyCapsule>()
         {
             return Ok(Some(capsule.clone().unbind()));
         }
-        let schema_list_method = cls.getattr("__get_core_schema__")?;
+        let schema_list_method = schema_method(cls.as_any()).ok_or_else(|| {
+            pyo3::exceptions::PyAttributeError::new_err(format!(
+                "type object '{}' has no schema method",
+                cls.name().map(|n| n.to_string()).unwrap_or_default()
+            ))
+        })?;
         let schema_list = schema_list_method.call0()?;
         let list = schema_list.cast::<PyList>()?;
-        let capsule = compile_schema(py, list)?;
+        let mut compiled = build_schema(py, list)?;
+        if compiled.fields.iter().any(|f| f.has_deserializer) {
+            compiled.owner = Some(cls.clone().unbind());
+        }
+        let capsule = PyCapsule::new(py, compiled, None)?.unbind();
         cls.setattr("__tars_compiled_schema__", &capsule)?;
         return Ok(Some(capsule));
     }
     Ok(None)
 }
 
+/// 解析 Schema 参数 (Struct 类型, 胶囊或 Schema 列表) 并以编译结果调用 `f`.
+///
+/// 类型与胶囊复用已缓存的编译结果; 未缓存的 Schema 列表临时编译后走同一路径.
+fn with_compiled_schema<R>(
+    py: Python<'_>,
+    schema: &Bound<'_, PyAny>,
+    f: impl FnOnce(&CompiledSchema) -> PyResult<R>,
+) -> PyResult<R> {
+    if let Some(capsule_py) = get_or_compile_schema(py, schema)? {
+        let capsule = capsule_py.bind(py);
+        let ptr = capsule
+            .pointer_checked(None)
+            .map_err(|_| PyValueError::new_err("Invalid capsule"))?;
+        let compiled = unsafe { &*(ptr.as_ptr() as *mut CompiledSchema) };
+        return f(compiled);
+    }
+    let compiled = build_schema(py, schema.cast::<PyList>()?)?;
+    f(&compiled)
+}
+
 #[pyfunction]
-#[pyo3(signature = (obj, schema, options=0, context=None))]
+#[pyo3(signature = (obj, schema, options=0, context=None, max_depth=None, endian=None, string1_max=255, force_string4=false, str_encoding=None))]
+#[allow(clippy::too_many_arguments)]
 /// 序列化 Struct 对象.
 ///
 /// Args:
-///     obj (Any): 要序列化的 Struct 对象.
+///     obj (Any): 要序列化的 Struct 对象, 或以字段名为键的 dict.
 ///     schema (Any): 对象的 schema 信息 (Capsule 或 List).
 ///     options (int): 序列化选项 flags.
 ///     context (dict | None): 序列化上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
 ///
 /// Returns:
 ///     bytes: 序列化后的二进制数据.
@@ -104,9 +827,18 @@ pub fn dumps(
     py: Python<'_>,
     obj: &Bound<'_, PyAny>,
     schema: &Bound<'_, PyAny>,
-    options: i32,
+    options: i64,
     context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    string1_max: i64,
+    force_string4: bool,
+    str_encoding: Option<&str>,
 ) -> PyResult<Py<PyBytes>> {
+    let options = resolve_endian(options, endian)?;
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let config = string_writer_config(options, string1_max, force_string4)?;
+    let _limit = DepthLimitGuard::new(max_depth);
     let context_bound = match context {
         Some(ctx) => ctx.clone(),
         None => PyDict::new(py).into_any(),
@@ -114,324 +846,2179 @@ pub fn dumps(
     // 根据 options 选择 BigEndian 或 LittleEndian 写入器
     // options & 1 == 0 -> BigEndian (默认)
     // options & 1 == 1 -> LittleEndian
-    let bytes = if options & 1 == 0 {
-        TLS_WRITER.with(|cell| {
-            if let Ok(mut writer) = cell.try_borrow_mut() {
-                writer.clear();
-                encode_struct(py, &mut *writer, obj, schema, options, &context_bound, 0)?;
-                Ok::<Vec<u8>, PyErr>(writer.get_buffer().to_vec())
-            } else {
-                let mut writer = JceWriter::<Vec<u8>, BigEndian>::new();
-                encode_struct(py, &mut writer, obj, schema, options, &context_bound, 0)?;
-                Ok(writer.get_buffer().to_vec())
-            }
-        })?
-    } else {
-        let mut writer = JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::with_capacity(128));
-        encode_struct(py, &mut writer, obj, schema, options, &context_bound, 0)?;
-        writer.get_buffer().to_vec()
-    };
-    Ok(PyBytes::new(py, &bytes).into())
+    // 结果直接从 Writer 的缓冲区复制到 bytes 对象, 仅一次拷贝
+    let to_bytes = |buf: &[u8]| PyBytes::new(py, buf).unbind();
+    with_compiled_schema(py, schema, |compiled| {
+        let size_hint = compiled.size_hint;
+        if options & 1 == 0 {
+            with_tls_writer(
+                &TLS_WRITER,
+                size_hint,
+                config,
+                |w| encode_struct_compiled(py, w, obj, compiled, options, &context_bound, 0),
+                to_bytes,
+            )
+        } else {
+            with_tls_writer(
+                &TLS_WRITER_LE,
+                size_hint,
+                config,
+                |w| encode_struct_compiled(py, w, obj, compiled, options, &context_bound, 0),
+                to_bytes,
+            )
+        }
+    })
+}
+
+/// 以 `dumps` 的默认参数序列化 Struct 对象, 仅指定 options.
+///
+/// 供 Rust 侧调用方 (如基准测试) 使用, 免于逐个传入位置参数.
+pub fn dumps_with_options(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    schema: &Bound<'_, PyAny>,
+    options: i64,
+) -> PyResult<Py<PyBytes>> {
+    dumps(py, obj, schema, options, None, None, None, 255, false, None)
 }
 
 #[pyfunction]
-#[pyo3(signature = (data, options=0, context=None))]
-/// 通用序列化函数 (无需 Struct 定义).
+#[pyo3(signature = (obj, schema, out, options=0, context=None, max_depth=None, string1_max=255, force_string4=false))]
+#[allow(clippy::too_many_arguments)]
+/// 序列化 Struct 对象并追加到已有的 bytearray 末尾.
 ///
-/// 支持将 dict, list, int, str 等基础类型序列化为 JCE 格式.
+/// 编码复用线程局部 Writer, 结果一次性复制到 `out`, 不创建新的 bytes 对象.
+/// 编码失败时 `out` 保持不变.
 ///
 /// Args:
-///     data (Any): 要序列化的数据.
-///     options (int): 序列化选项.
-///     context (dict | None): 上下文.
+///     obj (Any): 要序列化的 Struct 对象.
+///     schema (Any): 对象的 schema 信息 (Capsule 或 List).
+///     out (bytearray): 输出缓冲区.
+///     options (int): 序列化选项 flags.
+///     context (dict | None): 序列化上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
 ///
 /// Returns:
-///     bytes: 序列化后的二进制数据.
-pub fn dumps_generic(
+///     int: 写入的字节数.
+pub fn dumps_into(
     py: Python<'_>,
-    data: &Bound<'_, PyAny>,
-    options: i32,
+    obj: &Bound<'_, PyAny>,
+    schema: &Bound<'_, PyAny>,
+    out: &Bound<'_, PyByteArray>,
+    options: i64,
     context: Option<&Bound<'_, PyAny>>,
-) -> PyResult<Py<PyBytes>> {
+    max_depth: Option<usize>,
+    string1_max: i64,
+    force_string4: bool,
+) -> PyResult<usize> {
+    let config = string_writer_config(options, string1_max, force_string4)?;
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
     let context_bound = match context {
         Some(ctx) => ctx.clone(),
         None => PyDict::new(py).into_any(),
     };
-    let bytes = if options & 1 == 0 {
-        TLS_WRITER.with(|cell| {
-            if let Ok(mut writer) = cell.try_borrow_mut() {
-                writer.clear();
-                if let Ok(dict) = data.cast::<PyDict>() {
-                    encode_generic_struct(py, &mut *writer, dict, options, &context_bound, 0)?;
-                } else {
-                    encode_generic_field(py, &mut *writer, 0, data, options, &context_bound, 0)?;
-                }
-                Ok::<Vec<u8>, PyErr>(writer.get_buffer().to_vec())
-            } else {
-                let mut writer = JceWriter::<Vec<u8>, BigEndian>::new();
-                if let Ok(dict) = data.cast::<PyDict>() {
-                    encode_generic_struct(py, &mut writer, dict, options, &context_bound, 0)?;
-                } else {
-                    encode_generic_field(py, &mut writer, 0, data, options, &context_bound, 0)?;
-                }
-                Ok(writer.get_buffer().to_vec())
-            }
-        })?
-    } else {
-        let mut writer = JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::with_capacity(128));
-        if let Ok(dict) = data.cast::<PyDict>() {
-            encode_generic_struct(py, &mut writer, dict, options, &context_bound, 0)?;
+    let append = |buf: &[u8]| -> PyResult<usize> {
+        let start = out.len();
+        out.resize(start + buf.len())?;
+        // 调整大小后立即复制, 期间不会执行任何 Python 代码
+        unsafe { out.as_bytes_mut()[start..].copy_from_slice(buf) };
+        Ok(buf.len())
+    };
+    with_compiled_schema(py, schema, |compiled| {
+        let size_hint = compiled.size_hint;
+        if options & 1 == 0 {
+            with_tls_writer(
+                &TLS_WRITER,
+                size_hint,
+                config,
+                |w| encode_struct_compiled(py, w, obj, compiled, options, &context_bound, 0),
+                append,
+            )?
         } else {
-            encode_generic_field(py, &mut writer, 0, data, options, &context_bound, 0)?;
+            with_tls_writer(
+                &TLS_WRITER_LE,
+                size_hint,
+                config,
+                |w| encode_struct_compiled(py, w, obj, compiled, options, &context_bound, 0),
+                append,
+            )?
         }
-        writer.get_buffer().to_vec()
-    };
-    Ok(PyBytes::new(py, &bytes).into())
+    })
 }
 
 #[pyfunction]
-#[pyo3(signature = (data, target, options=0))]
-/// 反序列化 Struct 对象.
+#[pyo3(signature = (objs, schema, options=0, context=None, max_depth=None, string1_max=255, force_string4=false))]
+#[allow(clippy::too_many_arguments)]
+/// 批量序列化共享同一 Schema 的 Struct 对象.
+///
+/// Schema 只解析一次, 所有对象复用同一个 Writer (逐个清空),
+/// 每个对象的输出与单独调用 `dumps` 完全一致.
 ///
 /// Args:
-///     data (bytes): JCE 二进制数据.
-///     target (type): 目标 Struct 类.
-///     options (int): 反序列化选项.
+///     objs (list): 要序列化的 Struct 对象列表.
+///     schema (Any): Struct 类型 (推荐) 或 Schema 列表.
+///     options (int): 序列化选项 flags.
+///     context (dict | None): 序列化上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
 ///
 /// Returns:
-///     Any: 解析后的 Struct 实例.
-pub fn loads(
+///     list[bytes]: 每个对象对应的二进制数据.
+///
+/// Raises:
+///     TypeError: 如果对象不是 `schema` 指定类型的实例.
+pub fn dumps_batch(
+    py: Python<'_>,
+    objs: &Bound<'_, PyList>,
+    schema: &Bound<'_, PyAny>,
+    options: i64,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    string1_max: i64,
+    force_string4: bool,
+) -> PyResult<Py<PyList>> {
+    let config = string_writer_config(options, string1_max, force_string4)?;
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let out = PyList::empty(py);
+    encode_batch(py, objs, schema, options, config, context, |payload| {
+        out.append(PyBytes::new(py, payload))
+    })?;
+    Ok(out.unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (objs, schema, options=0, context=None, length_type=4, inclusive_length=true, little_endian_length=false, max_depth=None, string1_max=255, force_string4=false))]
+#[allow(clippy::too_many_arguments)]
+/// 批量序列化并拼接为带长度前缀的数据流.
+///
+/// 每个数据包的格式与 `LengthPrefixedWriter` 输出一致.
+///
+/// Args:
+///     objs (list): 要序列化的 Struct 对象列表.
+///     schema (Any): Struct 类型 (推荐) 或 Schema 列表.
+///     options (int): 序列化选项 flags.
+///     context (dict | None): 序列化上下文.
+///     length_type (int): 长度头字节数 (1, 2, 4).
+///     inclusive_length (bool): 长度是否包含头部本身.
+///     little_endian_length (bool): 长度头是否为小端序.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
+///
+/// Returns:
+///     bytes: 所有数据包按顺序拼接后的二进制数据.
+pub fn dumps_batch_framed(
+    py: Python<'_>,
+    objs: &Bound<'_, PyList>,
+    schema: &Bound<'_, PyAny>,
+    options: i64,
+    context: Option<&Bound<'_, PyAny>>,
+    length_type: u8,
+    inclusive_length: bool,
+    little_endian_length: bool,
+    max_depth: Option<usize>,
+    string1_max: i64,
+    force_string4: bool,
+) -> PyResult<Py<PyBytes>> {
+    if ![1, 2, 4].contains(&length_type) {
+        return Err(PyValueError::new_err("length_type must be 1, 2, or 4"));
+    }
+    let config = string_writer_config(options, string1_max, force_string4)?;
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let mut out: Vec<u8> = Vec::new();
+    encode_batch(py, objs, schema, options, config, context, |payload| {
+        write_length_prefixed(
+            &mut out,
+            payload,
+            length_type,
+            inclusive_length,
+            little_endian_length,
+        )
+    })?;
+    Ok(PyBytes::new(py, &out).unbind())
+}
+
+/// 批量编码的公共实现: 解析一次 Schema, 逐个编码后交给 `emit` 处理.
+fn encode_batch(
+    py: Python<'_>,
+    objs: &Bound<'_, PyList>,
+    schema: &Bound<'_, PyAny>,
+    options: i64,
+    config: WriterConfig,
+    context: Option<&Bound<'_, PyAny>>,
+    emit: impl FnMut(&[u8]) -> PyResult<()>,
+) -> PyResult<()> {
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let cls = schema.cast::<PyType>().ok();
+    with_compiled_schema(py, schema, |compiled| {
+        // 使用独立的 Writer, 线程局部 Writer 留给嵌套二进制块复用
+        let capacity = compiled.size_hint.max(128);
+        if options & 1 == 0 {
+            let writer = JceWriter::<Vec<u8>, BigEndian>::with_buffer(Vec::with_capacity(capacity));
+            encode_batch_with(
+                py,
+                writer,
+                objs,
+                cls,
+                compiled,
+                options,
+                config,
+                &context_bound,
+                emit,
+            )
+        } else {
+            let writer =
+                JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::with_capacity(capacity));
+            encode_batch_with(
+                py,
+                writer,
+                objs,
+                cls,
+                compiled,
+                options,
+                config,
+                &context_bound,
+                emit,
+            )
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn encode_batch_with<E: Endianness>(
+    py: Python<'_>,
+    mut writer: JceWriter<Vec<u8>, E>,
+    objs: &Bound<'_, PyList>,
+    cls: Option<&Bound<'_, PyType>>,
+    compiled: &CompiledSchema,
+    options: i64,
+    config: WriterConfig,
+    context: &Bound<'_, PyAny>,
+    mut emit: impl FnMut(&[u8]) -> PyResult<()>,
+) -> PyResult<()> {
+    writer.set_config(config);
+    for obj in objs.iter() {
+        if let Some(cls) = cls
+            && !obj.get_type().is(cls)
+        {
+            return Err(PyTypeError::new_err(format!(
+                "Expected {} instance, got {}",
+                cls.name()?,
+                obj.get_type().name()?
+            )));
+        }
+        writer.clear();
+        encode_struct_compiled(py, &mut writer, &obj, compiled, options, context, 0)?;
+        emit(writer.get_buffer())?;
+    }
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, options=0, context=None, max_depth=None, endian=None, string1_max=255, force_string4=false, str_encoding=None))]
+#[allow(clippy::too_many_arguments)]
+/// 通用序列化函数 (无需 Struct 定义).
+///
+/// 支持将 dict, list, int, str 等基础类型序列化为 JCE 格式.
+///
+/// Args:
+///     data (Any): 要序列化的数据.
+///     options (int): 序列化选项.
+///     context (dict | None): 上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+///     force_string4 (bool): 是否所有字符串都使用 String4.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+///
+/// Returns:
+///     bytes: 序列化后的二进制数据.
+pub fn dumps_generic(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    options: i64,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    string1_max: i64,
+    force_string4: bool,
+    str_encoding: Option<&str>,
+) -> PyResult<Py<PyBytes>> {
+    let options = resolve_endian(options, endian)?;
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let config = string_writer_config(options, string1_max, force_string4)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let to_bytes = |buf: &[u8]| PyBytes::new(py, buf).unbind();
+    if options & 1 == 0 {
+        with_tls_writer(
+            &TLS_WRITER,
+            0,
+            config,
+            |w| encode_generic_root(py, w, data, options, &context_bound),
+            to_bytes,
+        )
+    } else {
+        with_tls_writer(
+            &TLS_WRITER_LE,
+            0,
+            config,
+            |w| encode_generic_root(py, w, data, options, &context_bound),
+            to_bytes,
+        )
+    }
+}
+
+/// 通用序列化的顶层入口: dict 按结构体编码, 其余值编码为 Tag 0 字段.
+///
+/// `OPT_ORDERED_PAIRS` 下顶层的 `(tag, value)` 列表按给定顺序编码为结构体.
+fn encode_generic_root<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    data: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    if let Ok(dict) = data.cast::<PyDict>() {
+        encode_generic_struct(py, writer, dict, options, context, 0)
+    } else if options & OPT_ORDERED_PAIRS != 0
+        && let Some(pairs) = data.cast::<PyList>().ok().and_then(struct_pairs)
+    {
+        encode_generic_pairs(py, writer, pairs, options, context, 0)
+    } else {
+        encode_generic_field(py, writer, 0, data, options, context, 0)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, target, options=0, context=None, max_depth=None, endian=None, str_encoding=None, verify_length_tag=None, return_consumed=false, map_factory=None, hooks=None, max_fields=None, max_total_elements=None))]
+#[allow(clippy::too_many_arguments)]
+/// 反序列化 Struct 对象.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     target (type): 目标 Struct 类.
+///     options (int): 反序列化选项.
+///     context (dict | None): 反序列化上下文, 传递给字段反序列化钩子;
+///         未指定 `hooks` 时其中的 `hooks` 键作为钩子映射.
+///     max_depth (int | None): 最大嵌套深度 (同时限制跳过未知字段时的深度), 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+///     verify_length_tag (int | None): 声明结构体总长度的字段 Tag, 指定时校验其值与实际消耗的字节数一致.
+///     return_consumed (bool): 为 True 时返回 `(结果, 消耗的字节数)`.
+///     map_factory (Callable[[], MutableMapping] | None): 构造 Map 容器的无参可调用对象
+///         (如 `collections.OrderedDict`), 键值对按线上顺序通过 `__setitem__` 写入. 默认为 dict.
+///     hooks (dict[int, Callable] | None): Tag 到 `hook(value, context)` 的映射,
+///         各层结构体中对应 Tag 的字段值替换为钩子的返回值. 默认使用 `context["hooks"]`.
+///     max_fields (int | None): 单个结构体允许的最大字段数, 默认不限制.
+///     max_total_elements (int | None): 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
+///
+/// Returns:
+///     Any: 解析后的 Struct 实例; `return_consumed` 为 True 时为 `(实例, 消耗的字节数)`.
+///
+/// Raises:
+///     DecodeError: 数据格式错误, 超出 `max_fields`/`max_total_elements` 限制,
+///         或 `verify_length_tag` 字段缺失或与实际长度不符.
+pub fn loads(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    target: &Bound<'_, PyAny>,
+    options: i64,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    str_encoding: Option<&str>,
+    verify_length_tag: Option<u8>,
+    return_consumed: bool,
+    map_factory: Option<&Bound<'_, PyAny>>,
+    hooks: Option<&Bound<'_, PyDict>>,
+    max_fields: Option<usize>,
+    max_total_elements: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    let options = resolve_endian(options, endian)?;
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let _factory = MapFactoryGuard::new(map_factory);
+    let _hooks = TagHooksGuard::new(resolve_hooks(hooks, context)?.as_ref());
+    let _limit = DepthLimitGuard::new(max_depth);
+    let _limits = DecodeLimitGuard::new(max_fields, max_total_elements);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    if options & OPT_VALIDATE_FIRST != 0 {
+        validate_structure(bytes, options, limit)?;
+    }
+    let (result, consumed) = if options & 1 == 0 {
+        let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+        let result = if options & OPT_SLOTS != 0 {
+            decode_struct_slots(py, &mut reader, target, options, &context_bound)?
+        } else {
+            decode_struct(py, &mut reader, target, options, &context_bound, 0)?
+        };
+        (result, reader.position() as usize)
+    } else {
+        let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+        let result = if options & OPT_SLOTS != 0 {
+            decode_struct_slots(py, &mut reader, target, options, &context_bound)?
+        } else {
+            decode_struct(py, &mut reader, target, options, &context_bound, 0)?
+        };
+        (result, reader.position() as usize)
+    };
+    if let Some(tag) = verify_length_tag {
+        check_length_tag(&bytes[..consumed], tag, options & 1 != 0)?;
+    }
+    with_consumed(py, result, consumed, return_consumed)
+}
+
+/// `return_consumed` 为真时将结果与消耗的字节数组成元组, 否则原样返回结果.
+fn with_consumed(
+    py: Python<'_>,
+    result: Py<PyAny>,
+    consumed: usize,
+    return_consumed: bool,
+) -> PyResult<Py<PyAny>> {
+    if return_consumed {
+        Ok((result, consumed).into_pyobject(py)?.into_any().unbind())
+    } else {
+        Ok(result)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (frames, target, options=0, skip_errors=false, context=None, max_depth=None))]
+/// 批量反序列化共享同一 Schema 的数据包.
+///
+/// Schema 只解析一次, 各数据包直接使用编译后的 Schema 解码.
+///
+/// Args:
+///     frames (list): JCE 二进制数据包列表 (任意支持缓冲区协议的对象).
+///     target (type): 目标 Struct 类.
+///     options (int): 反序列化选项.
+///     skip_errors (bool): 为 True 时因数据无效 (`DecodeError` 或 `ValueError`) 而解码失败的
+///         数据包返回 None, 而不是中断整个批次. 其他异常 (如钩子抛出的 TypeError) 照常抛出.
+///     context (dict | None): 反序列化上下文, 传递给字段反序列化钩子.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     list[dict | None]: 与 `frames` 一一对应的解码结果 (字段名 -> 值).
+pub fn loads_batch(
+    py: Python<'_>,
+    frames: &Bound<'_, PyList>,
+    target: &Bound<'_, PyAny>,
+    options: i64,
+    skip_errors: bool,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyList>> {
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let context = &context_bound;
+    with_compiled_schema(py, target, |compiled| {
+        let out = PyList::empty(py);
+        for frame in frames.iter() {
+            let result = InputData::new(&frame).and_then(|input| {
+                let bytes = input.as_bytes();
+                if options & 1 == 0 {
+                    let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+                    decode_struct_compiled(py, &mut reader, compiled, options, context, 0)
+                } else {
+                    let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+                    decode_struct_compiled(py, &mut reader, compiled, options, context, 0)
+                }
+            });
+            match result {
+                Ok(value) => out.append(value)?,
+                Err(e) if skip_errors && is_data_error(py, &e)? => out.append(py.None())?,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(out.unbind())
+    })
+}
+
+/// 错误是否由数据本身无效导致 (`DecodeError` 或 `ValueError` 及其子类).
+fn is_data_error(py: Python<'_>, err: &PyErr) -> PyResult<bool> {
+    if err.is_instance_of::<PyValueError>(py) {
+        return Ok(true);
+    }
+    let decode_error = DECODE_ERROR_TYPE.import(py, "tarsio.exceptions", "DecodeError")?;
+    Ok(err.is_instance(py, decode_error))
+}
+
+#[pyfunction]
+#[pyo3(signature = (frames, target, options=0, threads=None, context=None, max_depth=None))]
+/// 并行校验并批量反序列化共享同一 Schema 的数据包.
+///
+/// 结构校验在释放 GIL 后分摊到多个线程上完成, 全部通过后再在当前线程依次构建 Python 对象.
+/// `bytes` 以外的缓冲区 (包括只读视图, 其所有者仍可修改底层内存) 会先复制一份, 避免校验期间被其他线程修改.
+///
+/// Args:
+///     frames (list): JCE 二进制数据包列表 (任意支持缓冲区协议的对象).
+///     target (type): 目标 Struct 类.
+///     options (int): 反序列化选项.
+///     threads (int | None): 校验线程数, 默认使用可用的 CPU 核心数.
+///     context (dict | None): 反序列化上下文, 传递给字段反序列化钩子.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     list[dict]: 与 `frames` 顺序一致的解码结果 (字段名 -> 值).
+///
+/// Raises:
+///     DecodeError: 任一数据包校验失败, 错误信息包含其在 `frames` 中的索引.
+///     RuntimeError: 校验线程异常退出.
+pub fn loads_batch_parallel(
+    py: Python<'_>,
+    frames: &Bound<'_, PyList>,
+    target: &Bound<'_, PyAny>,
+    options: i64,
+    threads: Option<usize>,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyList>> {
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let context = &context_bound;
+    let mut inputs = Vec::with_capacity(frames.len());
+    for frame in frames.iter() {
+        let input = InputData::new(&frame)?;
+        if input.is_immutable() {
+            inputs.push(input);
+        } else {
+            inputs.push(InputData::Owned(input.as_bytes().to_vec()));
+        }
+    }
+    let slices: Vec<&[u8]> = inputs.iter().map(InputData::as_bytes).collect();
+    let threads = threads
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+        .clamp(1, slices.len().max(1));
+    // 工作线程中没有 DepthLimitGuard 设置的线程局部变量, 直接使用参数
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let outcomes = py.detach(|| {
+        let chunk_len = slices.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = slices
+                .chunks(chunk_len)
+                .enumerate()
+                .map(|(chunk_index, chunk)| {
+                    scope.spawn(move || {
+                        chunk.iter().enumerate().find_map(|(i, bytes)| {
+                            let result = if options & 1 == 0 {
+                                JceScanner::<BigEndian>::new(bytes)
+                                    .with_max_depth(limit)
+                                    .validate_struct()
+                            } else {
+                                JceScanner::<LittleEndian>::new(bytes)
+                                    .with_max_depth(limit)
+                                    .validate_struct()
+                            };
+                            result.err().map(|e| (chunk_index * chunk_len + i, e))
+                        })
+                    })
+                })
+                .collect();
+            // 必须等待全部线程结束: 未 join 的线程 panic 会使 scope 本身 panic
+            workers
+                .into_iter()
+                .map(|worker| worker.join().map_err(|_| ()))
+                .collect::<Vec<_>>()
+        })
+    });
+    // 按分块顺序汇总, 保证报告的是索引最小的失败数据包
+    for outcome in outcomes {
+        match outcome {
+            Ok(None) => {}
+            Ok(Some((index, err))) => {
+                let reason = match &err {
+                    CodecError::Custom { msg, .. } => msg.clone(),
+                    CodecError::BufferOverflow { .. } => "Buffer overflow".to_string(),
+                    CodecError::InvalidType { type_id, .. } => {
+                        format!("Invalid type ID: {}", type_id)
+                    }
+                };
+                return Err(
+                    CodecError::new(err.offset(), format!("Frame {}: {}", index, reason)).into(),
+                );
+            }
+            Err(()) => {
+                return Err(PyRuntimeError::new_err(
+                    "Frame validation worker thread panicked",
+                ));
+            }
+        }
+    }
+    with_compiled_schema(py, target, |compiled| {
+        let out = PyList::empty(py);
+        for (index, bytes) in slices.iter().enumerate() {
+            let value = if options & 1 == 0 {
+                let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+                decode_struct_compiled(py, &mut reader, compiled, options, context, 0)
+            } else {
+                let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+                decode_struct_compiled(py, &mut reader, compiled, options, context, 0)
+            };
+            out.append(value.map_err(|err| frame_indexed_error(py, index, err))?)?;
+        }
+        Ok(out.unbind())
+    })
+}
+
+/// 为批量解码中的异常加上数据包索引, 原异常作为其 `__cause__`.
+///
+/// 异常类型无法仅以消息构造时 (如 Pydantic 的 `ValidationError`) 原样返回.
+fn frame_indexed_error(py: Python<'_>, index: usize, err: PyErr) -> PyErr {
+    let message = format!("Frame {}: {}", index, err.value(py));
+    match err.get_type(py).call1((message,)) {
+        Ok(obj) => {
+            let new_err = PyErr::from_value(obj);
+            new_err.set_cause(py, Some(err));
+            new_err
+        }
+        Err(_) => err,
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, target, tags, options=0, max_depth=None))]
+/// 投影反序列化: 仅解码指定 Tag 的字段.
+///
+/// 未请求的字段 (包括 Map/List 等容器) 通过 `skip_field` 直接跳过,
+/// 不会创建任何 Python 对象.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     target (type): 目标 Struct 类.
+///     tags (set[int]): 需要解码的 Tag 集合.
+///     options (int): 反序列化选项.
+///     max_depth (int | None): 最大嵌套深度 (同时限制跳过字段时的深度), 默认为 100.
+///
+/// Returns:
+///     dict: 仅包含请求字段的字典 (字段名 -> 值).
+pub fn loads_projection(
     py: Python<'_>,
-    data: &Bound<'_, PyBytes>,
+    data: &Bound<'_, PyAny>,
     target: &Bound<'_, PyAny>,
-    options: i32,
+    tags: HashSet<u8>,
+    options: i64,
+    max_depth: Option<usize>,
 ) -> PyResult<Py<PyAny>> {
-    let bytes = data.as_bytes();
-    let dict = if options & 1 == 0 {
-        decode_struct(
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let capsule_py = get_or_compile_schema(py, target)?
+        .ok_or_else(|| PyTypeError::new_err("Projection decode requires a Struct type"))?;
+    let capsule = capsule_py.bind(py);
+    let ptr = capsule
+        .pointer_checked(None)
+        .map_err(|_| PyValueError::new_err("Invalid capsule"))?;
+    let compiled = unsafe { &*(ptr.as_ptr() as *mut CompiledSchema) };
+    let context = PyDict::new(py).into_any();
+    let context = &context;
+    if options & 1 == 0 {
+        decode_struct_projected(
             py,
-            &mut JceReader::<BigEndian>::new(bytes),
-            target,
+            &mut JceReader::<BigEndian>::new(bytes).with_max_depth(limit),
+            compiled,
+            &tags,
             options,
+            context,
             0,
-        )?
+        )
     } else {
-        decode_struct(
+        decode_struct_projected(
             py,
-            &mut JceReader::<LittleEndian>::new(bytes),
-            target,
+            &mut JceReader::<LittleEndian>::new(bytes).with_max_depth(limit),
+            compiled,
+            &tags,
             options,
+            context,
             0,
-        )?
-    };
-    Ok(dict)
+        )
+    }
 }
 
 #[pyfunction]
-#[pyo3(signature = (data, options=0, bytes_mode=2))]
+#[pyo3(signature = (data, options=0, bytes_mode=2, context=None, max_depth=None, endian=None, str_encoding=None, max_fields=None, max_total_elements=None, allowed_control_bytes=None, schema=None, verify_length_tag=None, return_consumed=false, map_factory=None, hooks=None))]
+#[allow(clippy::too_many_arguments)]
 /// 通用反序列化函数.
 ///
 /// 将 JCE 数据解析为 dict, list 等基础类型.
 ///
 /// Args:
-///     data (bytes): JCE 二进制数据.
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
 ///     options (int): 选项.
-///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto).
+///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto, 3=View).
+///     context (dict | None): 反序列化上下文, 传递给 `hooks` 中的钩子;
+///         未指定 `hooks` 时其中的 `hooks` 键作为钩子映射.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+///     max_fields (int | None): 单个结构体允许的最大字段数, 默认不限制.
+///     max_total_elements (int | None): 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
+///     allowed_control_bytes (bytes | None): Auto 模式下文本中允许出现的 ASCII 控制字符,
+///         默认为 `b"\t\n\r"`. 含有其他控制字符的 SimpleList 保留为 bytes.
+///     schema (type | list | None): Struct 类或 Schema 列表. 指定时顶层字典中
+///         Schema 已知的 Tag 以字段名为键, 未知 Tag 保留整数键; 带有嵌套 Schema 的字段同样转换.
+///     verify_length_tag (int | None): 声明结构体总长度的字段 Tag, 指定时校验其值与实际消耗的字节数一致.
+///     return_consumed (bool): 为 True 时返回 `(结果, 消耗的字节数)`.
+///     map_factory (Callable[[], MutableMapping] | None): 构造 Map 容器的无参可调用对象
+///         (如 `collections.OrderedDict`), 键值对按线上顺序通过 `__setitem__` 写入. 默认为 dict.
+///     hooks (dict[int, Callable] | None): Tag 到 `hook(value, context)` 的映射,
+///         各层结构体中对应 Tag 的字段值替换为钩子的返回值. 默认使用 `context["hooks"]`.
 ///
 /// Returns:
-///     Any: 解析后的 Python 对象 (通常是 dict).
+///     Any: 解析后的 Python 对象 (通常是 dict); `return_consumed` 为 True 时为 `(结果, 消耗的字节数)`.
+///
+/// Raises:
+///     DecodeError: 数据格式错误, 超出 `max_fields`/`max_total_elements` 限制,
+///         或 `verify_length_tag` 字段缺失或与实际长度不符.
+///     ValueError: `allowed_control_bytes` 含有非控制字符, 或 `schema` 与 ORDERED_PAIRS 同时指定.
 pub fn loads_generic(
     py: Python<'_>,
-    data: &Bound<'_, PyBytes>,
-    options: i32,
+    data: &Bound<'_, PyAny>,
+    options: i64,
     bytes_mode: u8,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    str_encoding: Option<&str>,
+    max_fields: Option<usize>,
+    max_total_elements: Option<usize>,
+    allowed_control_bytes: Option<&[u8]>,
+    schema: Option<&Bound<'_, PyAny>>,
+    verify_length_tag: Option<u8>,
+    return_consumed: bool,
+    map_factory: Option<&Bound<'_, PyAny>>,
+    hooks: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<Py<PyAny>> {
-    let bytes = data.as_bytes();
+    let options = resolve_endian(options, endian)?;
+    if schema.is_some() && options & OPT_ORDERED_PAIRS != 0 {
+        return Err(PyValueError::new_err(
+            "schema cannot be combined with ORDERED_PAIRS",
+        ));
+    }
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let _factory = MapFactoryGuard::new(map_factory);
+    let _hooks = TagHooksGuard::new(resolve_hooks(hooks, context)?.as_ref());
+    let _limit = DepthLimitGuard::new(max_depth);
+    let _limits = DecodeLimitGuard::new(max_fields, max_total_elements);
+    let _text = TextPolicyGuard::new(text_control_mask(allowed_control_bytes)?);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let context = &context_bound;
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
     let mode = BytesMode::from(bytes_mode);
-    if options & 1 == 0 {
-        decode_generic_struct(
+    let _view = ViewSourceGuard::new(
+        data,
+        &input,
+        mode == BytesMode::View || options & OPT_STR_VIEW != 0,
+    )?;
+    if options & OPT_RELEASE_GIL != 0 {
+        // 先在释放 GIL 的情况下完成结构校验, 非法数据无需构建任何 Python 对象即可报错
+        // bytes 以外的缓冲区可能被其所有者在其他线程中修改, 校验其拷贝
+        if input.is_immutable() {
+            py.detach(|| validate_structure(bytes, options, limit))?;
+        } else {
+            let copy = bytes.to_vec();
+            py.detach(|| validate_structure(&copy, options, limit))?;
+        }
+    } else if options & OPT_VALIDATE_FIRST != 0 {
+        validate_structure(bytes, options, limit)?;
+    }
+    let (result, consumed) = if options & 1 == 0 {
+        let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+        let result = decode_generic_struct(py, &mut reader, options, context, mode, 0)?;
+        (result, reader.position() as usize)
+    } else {
+        let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+        let result = decode_generic_struct(py, &mut reader, options, context, mode, 0)?;
+        (result, reader.position() as usize)
+    };
+    if let Some(tag) = verify_length_tag {
+        check_length_tag(&bytes[..consumed], tag, options & 1 != 0)?;
+    }
+    let result = match schema {
+        Some(schema) => denormalize_struct(py, result.bind(py).cast::<PyDict>()?, schema)?,
+        None => result,
+    };
+    with_consumed(py, result, consumed, return_consumed)
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, options=0, bytes_mode=2, context=None, max_depth=None, endian=None, str_encoding=None))]
+#[allow(clippy::too_many_arguments)]
+/// 通用反序列化, 出错时返回出错前已解码的顶层字段.
+///
+/// 用于分析损坏的数据包: 与 `loads_generic` 不同, 遇到第一个错误时不抛出异常,
+/// 而是停止解码并返回已成功解码的字段与错误信息. 嵌套结构中的错误视为所在顶层字段解码失败.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     options (int): 选项.
+///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto, 3=View).
+///     context (dict | None): 反序列化上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+///
+/// Returns:
+///     tuple[Any, dict | None]: 已解码的字段 (通常是 dict) 与错误信息.
+///         错误信息包含 `offset` (出错字段头部的偏移量), `tag` (出错字段的 Tag,
+///         读取头部失败时为 None) 与 `error` (异常对象); 解码成功时为 None.
+pub fn loads_generic_partial(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    options: i64,
+    bytes_mode: u8,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    str_encoding: Option<&str>,
+) -> PyResult<(Py<PyAny>, Option<Py<PyDict>>)> {
+    let options = resolve_endian(options, endian)?;
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _limit = DepthLimitGuard::new(max_depth);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let mode = BytesMode::from(bytes_mode);
+    let _view = ViewSourceGuard::new(
+        data,
+        &input,
+        mode == BytesMode::View || options & OPT_STR_VIEW != 0,
+    )?;
+    let mut failure = None;
+    let (value, result) = if options & 1 == 0 {
+        decode_generic_struct_partial(
             py,
-            &mut JceReader::<BigEndian>::new(bytes),
+            &mut JceReader::<BigEndian>::new(bytes).with_max_depth(limit),
             options,
+            &context_bound,
             mode,
             0,
+            &mut failure,
         )
     } else {
-        decode_generic_struct(
+        decode_generic_struct_partial(
             py,
-            &mut JceReader::<LittleEndian>::new(bytes),
+            &mut JceReader::<LittleEndian>::new(bytes).with_max_depth(limit),
             options,
+            &context_bound,
             mode,
             0,
+            &mut failure,
+        )
+    };
+    let (Err(err), Some((offset, tag))) = (result, failure) else {
+        return Ok((value, None));
+    };
+    let info = PyDict::new(py);
+    info.set_item("offset", offset)?;
+    info.set_item("tag", tag)?;
+    info.set_item("error", err.into_value(py))?;
+    Ok((value, Some(info.unbind())))
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, options=0, bytes_mode=2, context=None, max_depth=None, endian=None, str_encoding=None, max_fields=None, max_total_elements=None, allowed_control_bytes=None))]
+#[allow(clippy::too_many_arguments)]
+/// 通用反序列化单个 JCE 值.
+///
+/// 用于数据体本身就是一个值 (如顶层的 List, Map 或 SimpleList) 而非结构体字段序列的协议:
+/// 读取一个头部并解码其后的值, 忽略头部中的 Tag, 直接返回该值.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     options (int): 选项.
+///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto, 3=View).
+///     context (dict | None): 反序列化上下文.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///     endian (str | None): 字节序 ("big" 或 "little"), 指定时覆盖 options 中的字节序位.
+///     str_encoding (str | None): 字符串字段的文本编码 (如 "gbk", "utf-16le"), 默认为 UTF-8.
+///     max_fields (int | None): 单个结构体允许的最大字段数, 默认不限制.
+///     max_total_elements (int | None): 结构体字段, List 元素与 Map 键值对的最大总数, 默认不限制.
+///     allowed_control_bytes (bytes | None): Auto 模式下文本中允许出现的 ASCII 控制字符,
+///         默认为 `b"\t\n\r"`.
+///
+/// Returns:
+///     Any: 解码得到的值.
+///
+/// Raises:
+///     DecodeError: 数据格式错误, 超出 `max_fields`/`max_total_elements` 限制,
+///         或值之后还有多余的数据.
+///     ValueError: `allowed_control_bytes` 含有非控制字符.
+pub fn loads_generic_value(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    options: i64,
+    bytes_mode: u8,
+    context: Option<&Bound<'_, PyAny>>,
+    max_depth: Option<usize>,
+    endian: Option<&str>,
+    str_encoding: Option<&str>,
+    max_fields: Option<usize>,
+    max_total_elements: Option<usize>,
+    allowed_control_bytes: Option<&[u8]>,
+) -> PyResult<Py<PyAny>> {
+    let options = resolve_endian(options, endian)?;
+    let _encoding = StrEncodingGuard::new(py, str_encoding)?;
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _limit = DepthLimitGuard::new(max_depth);
+    let _limits = DecodeLimitGuard::new(max_fields, max_total_elements);
+    let _text = TextPolicyGuard::new(text_control_mask(allowed_control_bytes)?);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let context_bound = match context {
+        Some(ctx) => ctx.clone(),
+        None => PyDict::new(py).into_any(),
+    };
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let mode = BytesMode::from(bytes_mode);
+    let _view = ViewSourceGuard::new(
+        data,
+        &input,
+        mode == BytesMode::View || options & OPT_STR_VIEW != 0,
+    )?;
+    if options & 1 == 0 {
+        decode_single_value(
+            py,
+            &mut JceReader::<BigEndian>::new(bytes).with_max_depth(limit),
+            options,
+            &context_bound,
+            mode,
+        )
+    } else {
+        decode_single_value(
+            py,
+            &mut JceReader::<LittleEndian>::new(bytes).with_max_depth(limit),
+            options,
+            &context_bound,
+            mode,
+        )
+    }
+}
+
+/// 读取一个头部并解码其值, 要求之后没有剩余数据.
+fn decode_single_value<E: Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'_, E>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    mode: BytesMode,
+) -> PyResult<Py<PyAny>> {
+    let (_, jce_type) = reader.read_head()?;
+    if jce_type == JceType::StructEnd {
+        return Err(CodecError::new(0, "Unexpected StructEnd at top level").into());
+    }
+    let value = decode_generic_field(py, reader, jce_type, options, context, mode, 1)?;
+    if !reader.is_end() {
+        return Err(CodecError::new(
+            reader.position() as usize,
+            format!(
+                "{} trailing bytes after top-level value",
+                reader.remaining()
+            ),
+        )
+        .into());
+    }
+    Ok(value)
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, tag, little_endian=false, bytes_mode=2, max_depth=None))]
+/// 按 Tag 提取顶层结构中的单个字段.
+///
+/// 顺序扫描顶层字段, 非目标字段通过 `skip_field` 跳过,
+/// 找到目标 Tag 后立即解码并返回, 不再解析剩余数据.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     tag (int): 目标字段的 Tag.
+///     little_endian (bool): 是否使用小端序.
+///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto).
+///     max_depth (int | None): 最大嵌套深度 (同时限制跳过字段时的深度), 默认为 100.
+///
+/// Returns:
+///     Any | None: 字段值, 如果 Tag 不存在则返回 None.
+pub fn get_field(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    tag: u8,
+    little_endian: bool,
+    bytes_mode: u8,
+    max_depth: Option<usize>,
+) -> PyResult<Option<Py<PyAny>>> {
+    let _factory = MapFactoryGuard::new(None);
+    let _hooks = TagHooksGuard::new(None);
+    let _encoding = StrEncodingGuard::new(py, None)?;
+    let _limit = DepthLimitGuard::new(max_depth);
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let mode = BytesMode::from(bytes_mode);
+    if little_endian {
+        let mut reader = JceReader::<LittleEndian>::new(bytes).with_max_depth(limit);
+        find_generic_field(py, &mut reader, tag, mode)
+    } else {
+        let mut reader = JceReader::<BigEndian>::new(bytes).with_max_depth(limit);
+        find_generic_field(py, &mut reader, tag, mode)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, little_endian=false, duplicates_as_list=false, max_depth=None))]
+/// 建立顶层字段的位置索引.
+///
+/// 通过零分配的结构扫描遍历顶层字段, 只创建索引字典本身, 不解码任何字段值.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     little_endian (bool): 是否使用小端序.
+///     duplicates_as_list (bool): 为 True 时每个 Tag 对应其所有出现位置的列表,
+///         否则重复 Tag 取最后一次出现的位置.
+///     max_depth (int | None): 扫描嵌套值时的最大深度, 默认为 100.
+///
+/// Returns:
+///     dict[int, tuple[int, int] | list[tuple[int, int]]]: Tag -> (值偏移量, 值长度),
+///         偏移量与长度均不含字段头部.
+pub fn index_struct<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    little_endian: bool,
+    duplicates_as_list: bool,
+    max_depth: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let _limit = DepthLimitGuard::new(max_depth);
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let fields = if little_endian {
+        scan_top_level::<LittleEndian>(bytes)?
+    } else {
+        scan_top_level::<BigEndian>(bytes)?
+    };
+    let index = PyDict::new(py);
+    for (tag, range) in fields {
+        let entry = (range.start, range.len());
+        if !duplicates_as_list {
+            index.set_item(tag, entry)?;
+        } else if let Some(ranges) = index.get_item(tag)? {
+            ranges.cast::<PyList>()?.append(entry)?;
+        } else {
+            index.set_item(tag, PyList::new(py, [entry])?)?;
+        }
+    }
+    Ok(index)
+}
+
+/// 校验 `tag` 字段声明的长度与结构体实际占用的字节数 `body.len()` 一致.
+///
+/// 用于内嵌冗余总长度字段的协议, `body` 为解码实际消耗的字节.
+pub(crate) fn check_length_tag(body: &[u8], tag: u8, little_endian: bool) -> PyResult<()> {
+    let declared = if little_endian {
+        declared_length::<LittleEndian>(body, tag)?
+    } else {
+        declared_length::<BigEndian>(body, tag)?
+    };
+    match declared {
+        Some(length) if length == body.len() as i64 => Ok(()),
+        Some(length) => Err(CodecError::new(
+            body.len(),
+            format!(
+                "Declared length {} at tag {} does not match body length {}",
+                length,
+                tag,
+                body.len()
+            ),
+        )
+        .into()),
+        None => Err(CodecError::new(body.len(), format!("Length tag {} not found", tag)).into()),
+    }
+}
+
+/// 读取顶层整数字段 `tag` 的值, 字段不存在时返回 None.
+fn declared_length<E: crate::codec::endian::Endianness>(
+    body: &[u8],
+    tag: u8,
+) -> Result<Option<i64>, CodecError> {
+    let mut reader = JceReader::<E>::new(body);
+    while !reader.is_end() {
+        let offset = reader.position() as usize;
+        let (t, jce_type) = reader.read_head()?;
+        if jce_type == JceType::StructEnd {
+            break;
+        }
+        if t != tag {
+            reader.skip_field(jce_type)?;
+            continue;
+        }
+        return match jce_type {
+            JceType::ZeroTag | JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => {
+                reader.read_int(jce_type).map(Some)
+            }
+            _ => Err(CodecError::new(
+                offset,
+                format!(
+                    "Length tag {} has wire type {:?}, expected integer",
+                    tag, jce_type
+                ),
+            )),
+        };
+    }
+    Ok(None)
+}
+
+/// 收集顶层字段的 Tag 与值范围, 遇到 StructEnd 或数据结束时停止.
+fn scan_top_level<E: crate::codec::endian::Endianness>(
+    bytes: &[u8],
+) -> Result<Vec<(u8, std::ops::Range<usize>)>, CodecError> {
+    let mut scanner = JceScanner::<E>::new(bytes).with_max_depth(max_depth());
+    let mut fields = Vec::new();
+    while !scanner.is_end() {
+        let (tag, jce_type, range) = scanner.next_field()?;
+        if jce_type == JceType::StructEnd {
+            break;
+        }
+        fields.push((tag, range));
+    }
+    Ok(fields)
+}
+
+/// 扫描顶层字段直到命中指定 Tag.
+///
+/// 遇到 StructEnd 或数据结束时返回 None.
+fn find_generic_field<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    tag: u8,
+    bytes_mode: BytesMode,
+) -> PyResult<Option<Py<PyAny>>> {
+    while !reader.is_end() {
+        let (t, jce_type) = reader.read_head()?;
+        if jce_type == JceType::StructEnd {
+            break;
+        }
+        if t == tag {
+            let context = PyDict::new(py).into_any();
+            return decode_generic_field(py, reader, jce_type, 0, &context, bytes_mode, 1)
+                .map(Some);
+        }
+        reader.skip_field(jce_type)?;
+    }
+    Ok(None)
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, options=0, max_depth=None))]
+/// 计算数据包规范形式的 SHA-256 摘要.
+///
+/// 先将数据重编码为规范形式 (最小整数宽度, 有序 Tag/键, 归一化浮点数),
+/// 再在 Rust 中完成哈希, 中间字节不会暴露给 Python.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     options (int): 选项 (仅使用 LITTLE_ENDIAN 位).
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     bytes: 32 字节的 SHA-256 摘要.
+pub fn canonical_hash(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    options: i64,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyBytes>> {
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let canonical = if options & 1 == 0 {
+        canonicalize::<BigEndian>(bytes, limit)?
+    } else {
+        canonicalize::<LittleEndian>(bytes, limit)?
+    };
+    Ok(PyBytes::new(py, &Sha256::digest(&canonical)).unbind())
+}
+
+#[pyfunction]
+#[pyo3(name = "canonicalize", signature = (data, little_endian=false, max_depth=None))]
+/// 将 JCE 数据重编码为规范形式.
+///
+/// 规范形式使用最小整数宽度, Tag 升序的结构体, 按键排序的 Map,
+/// 按长度选择的字符串类型, 并将 Float 提升为 Double.
+/// 对规范形式再次规范化会得到完全相同的字节.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     little_endian (bool): 是否使用小端序.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     bytes: 规范化后的二进制数据.
+///
+/// Raises:
+///     ValueError: 如果数据格式错误.
+pub fn canonicalize_bytes(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    little_endian: bool,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyBytes>> {
+    let input = InputData::new(data)?;
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    let canonical = if little_endian {
+        canonicalize::<LittleEndian>(input.as_bytes(), limit)?
+    } else {
+        canonicalize::<BigEndian>(input.as_bytes(), limit)?
+    };
+    Ok(PyBytes::new(py, &canonical).unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b, little_endian=false, max_depth=None))]
+/// 判断两段 JCE 数据在语义上是否相等.
+///
+/// 双方均重编码为规范形式后逐字节比较, 因此整数宽度, 字段顺序,
+/// Map 键顺序以及 Float/Double 的差异都不会影响结果.
+///
+/// Args:
+///     a (bytes | bytearray | memoryview): 第一段 JCE 二进制数据.
+///     b (bytes | bytearray | memoryview): 第二段 JCE 二进制数据.
+///     little_endian (bool): 是否使用小端序.
+///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+///
+/// Returns:
+///     bool: 语义相等时为 True.
+///
+/// Raises:
+///     ValueError: 如果任一数据格式错误.
+pub fn jce_equal(
+    a: &Bound<'_, PyAny>,
+    b: &Bound<'_, PyAny>,
+    little_endian: bool,
+    max_depth: Option<usize>,
+) -> PyResult<bool> {
+    let a = InputData::new(a)?;
+    let b = InputData::new(b)?;
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    if little_endian {
+        Ok(canonicalize::<LittleEndian>(a.as_bytes(), limit)?
+            == canonicalize::<LittleEndian>(b.as_bytes(), limit)?)
+    } else {
+        Ok(canonicalize::<BigEndian>(a.as_bytes(), limit)?
+            == canonicalize::<BigEndian>(b.as_bytes(), limit)?)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, little_endian=false, max_depth=None))]
+/// 生成 JCE 数据的结构化文本转储.
+///
+/// 每个字段一行, 包含偏移量, Tag, 类型与值, 嵌套结构按层级缩进.
+/// 非法数据不会抛出异常, 而是在输出末尾追加 `<parse error at 0xNN>` 标记.
+///
+/// Args:
+///     data (bytes | bytearray | memoryview): JCE 二进制数据.
+///     little_endian (bool): 是否使用小端序.
+///     max_depth (int | None): 最大嵌套深度, 超出时视为非法数据, 默认为 100.
+///
+/// Returns:
+///     str: 转储文本.
+pub fn debug_dump(
+    data: &Bound<'_, PyAny>,
+    little_endian: bool,
+    max_depth: Option<usize>,
+) -> PyResult<String> {
+    let input = InputData::new(data)?;
+    let bytes = input.as_bytes();
+    let limit = max_depth.unwrap_or(MAX_DEPTH);
+    if little_endian {
+        Ok(debug::debug_dump::<LittleEndian>(bytes, limit))
+    } else {
+        Ok(debug::debug_dump::<BigEndian>(bytes, limit))
+    }
+}
+
+/// JCE 写入器特征.
+///
+/// 定义了统一的写入接口，允许 `encode_struct` 等函数以泛型方式工作，
+/// 从而支持 `JceWriter<Vec<u8>, BigEndian>` 和 `JceWriter<Vec<u8>, LittleEndian>`.
+/// 二进制块需要回填长度, 因此仅为 `Vec<u8>` 后端实现.
+pub(crate) trait JceWriterTrait {
+    type Endian: crate::codec::endian::Endianness;
+
+    fn write_tag(&mut self, tag: u8, type_id: JceType);
+    fn write_int(&mut self, tag: u8, value: i64);
+    fn write_int_fixed(&mut self, tag: u8, value: i64, int_type: JceType);
+    fn write_float(&mut self, tag: u8, value: f32);
+    fn write_double(&mut self, tag: u8, value: f64);
+    fn write_string(&mut self, tag: u8, value: &str);
+    fn write_string_bytes(&mut self, tag: u8, bytes: &[u8]);
+    fn write_bytes(&mut self, tag: u8, value: &[u8]);
+    fn write_simple_list(
+        &mut self,
+        tag: u8,
+        element_type: JceType,
+        values: &[i64],
+    ) -> crate::codec::error::Result<()>;
+    fn write_map_header(&mut self, tag: u8, size: usize);
+    fn write_list_header(&mut self, tag: u8, size: usize);
+    fn write_raw(&mut self, bytes: &[u8]);
+    fn config(&self) -> WriterConfig;
+    fn begin_bytes(&mut self, tag: u8, size_hint: usize) -> BytesMark;
+    fn end_bytes(&mut self, mark: BytesMark);
+}
+
+impl<E: crate::codec::endian::Endianness> JceWriterTrait for JceWriter<Vec<u8>, E> {
+    type Endian = E;
+
+    #[inline]
+    fn write_tag(&mut self, tag: u8, type_id: JceType) {
+        self.write_tag(tag, type_id)
+    }
+    #[inline]
+    fn write_int(&mut self, tag: u8, value: i64) {
+        self.write_int(tag, value)
+    }
+    #[inline]
+    fn write_int_fixed(&mut self, tag: u8, value: i64, int_type: JceType) {
+        self.write_int_fixed(tag, value, int_type)
+    }
+    #[inline]
+    fn write_float(&mut self, tag: u8, value: f32) {
+        self.write_float(tag, value)
+    }
+    #[inline]
+    fn write_double(&mut self, tag: u8, value: f64) {
+        self.write_double(tag, value)
+    }
+    #[inline]
+    fn write_string(&mut self, tag: u8, value: &str) {
+        self.write_string(tag, value)
+    }
+    #[inline]
+    fn write_string_bytes(&mut self, tag: u8, bytes: &[u8]) {
+        self.write_string_bytes(tag, bytes)
+    }
+    #[inline]
+    fn write_bytes(&mut self, tag: u8, value: &[u8]) {
+        self.write_bytes(tag, value)
+    }
+    #[inline]
+    fn write_simple_list(
+        &mut self,
+        tag: u8,
+        element_type: JceType,
+        values: &[i64],
+    ) -> crate::codec::error::Result<()> {
+        self.write_simple_list(tag, element_type, values)
+    }
+    #[inline]
+    fn write_map_header(&mut self, tag: u8, size: usize) {
+        self.write_map_header(tag, size)
+    }
+    #[inline]
+    fn write_list_header(&mut self, tag: u8, size: usize) {
+        self.write_list_header(tag, size)
+    }
+    #[inline]
+    fn write_raw(&mut self, bytes: &[u8]) {
+        self.write_raw(bytes)
+    }
+    #[inline]
+    fn config(&self) -> WriterConfig {
+        self.config()
+    }
+    #[inline]
+    fn begin_bytes(&mut self, tag: u8, size_hint: usize) -> BytesMark {
+        self.begin_bytes(tag, size_hint)
+    }
+    #[inline]
+    fn end_bytes(&mut self, mark: BytesMark) {
+        self.end_bytes(mark)
+    }
+}
+
+/// 编码结构体 (对象 -> bytes).
+///
+/// 根据 Schema 遍历对象属性 (对于 dict 则按字段名取值) 并写入 JCE 流.
+/// 支持 `exclude_unset` 和 `omit_default` 选项.
+///
+/// 优先使用编译后的 Schema 以获得最佳性能.
+pub(crate) fn encode_struct<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    obj: &Bound<'_, PyAny>,
+    schema: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
+    }
+    with_compiled_schema(py, schema, |compiled| {
+        encode_struct_compiled(py, writer, obj, compiled, options, context, depth)
+    })
+}
+
+fn encode_struct_compiled<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    obj: &Bound<'_, PyAny>,
+    schema: &CompiledSchema,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
+    }
+    // 普通字典按字段名取值, 缺少的键视为未设置
+    let dict = obj.cast::<PyDict>().ok();
+    if let Some(dict) = dict {
+        check_name_keys(dict)?;
+    }
+    let fields_set = if dict.is_none() && (options & OPT_EXCLUDE_UNSET) != 0 {
+        obj.getattr("model_fields_set").ok()
+    } else {
+        None
+    };
+
+    let order = if depth == 0 {
+        context_field_order(context)?
+    } else {
+        None
+    };
+    if order.is_none() && options & OPT_SORT_TAGS == 0 {
+        schema.warn_unordered_tags(py)?;
+    }
+    if dict.is_none() && order.is_none() && options & OPT_FIELD_FILTERS == 0 {
+        return match &schema.tag_order {
+            Some(sorted) if options & OPT_SORT_TAGS != 0 => encode_fields_unfiltered(
+                py,
+                writer,
+                obj,
+                sorted.iter().map(|&i| &schema.fields[i]),
+                options,
+                context,
+                depth,
+            ),
+            _ => encode_fields_unfiltered(
+                py,
+                writer,
+                obj,
+                schema.fields.iter(),
+                options,
+                context,
+                depth,
+            ),
+        };
+    }
+    // 字段的编码顺序, 为 None 时按 Schema 声明顺序
+    let indices: Option<Cow<'_, [usize]>> = match &order {
+        Some(order) => {
+            let tags: Vec<u8> = schema.fields.iter().map(|f| f.tag).collect();
+            Some(Cow::Owned(ordered_field_indices(&tags, order)))
+        }
+        None => match &schema.tag_order {
+            Some(sorted) if options & OPT_SORT_TAGS != 0 => Some(Cow::Borrowed(sorted)),
+            _ => None,
+        },
+    };
+
+    for i in 0..schema.fields.len() {
+        let field = &schema.fields[indices.as_ref().map_or(i, |indices| indices[i])];
+        // 2. 检查 exclude_unset
+        if let Some(fs) = &fields_set {
+            // 使用 field.py_name (Interned String) 进行快速查找
+            // contains 方法在底层通常是 O(1)
+            if !fs.contains(field.py_name.bind(py))? {
+                continue;
+            }
+        }
+        // Optimization: Use interned py_name for getattr
+        let value = match dict {
+            Some(dict) => match dict.get_item(field.py_name.bind(py))? {
+                Some(value) => value,
+                None => match field
+                    .alias
+                    .as_deref()
+                    .map(|a| dict.get_item(a))
+                    .transpose()?
+                {
+                    Some(Some(value)) => value,
+                    _ if field.required => {
+                        return Err(PyValueError::new_err(format!(
+                            "Missing required field '{}' (tag {})",
+                            field.name, field.tag
+                        )));
+                    }
+                    _ => continue,
+                },
+            },
+            None => obj.getattr(field.py_name.bind(py))?,
+        };
+        if value.is_none() {
+            // JCE 没有空值; OPT_INCLUDE_NONE 下报错而不是静默丢弃该字段
+            if options & OPT_INCLUDE_NONE != 0 {
+                return Err(PyValueError::new_err(format!(
+                    "Field '{}' (tag {}) is None, but JCE has no null value",
+                    field.name, field.tag
+                )));
+            }
+            continue;
+        }
+        if (options & OPT_OMIT_DEFAULT) != 0 && value.eq(field.default_val.bind(py))? {
+            continue;
+        }
+        if (options & OPT_OMIT_EMPTY) != 0 && is_empty_value(&value)? {
+            continue;
+        }
+        let value = if field.has_serializer {
+            match serialize_hook(py, obj, field, value, context)? {
+                Some(value) => value,
+                None if options & OPT_INCLUDE_NONE != 0 => {
+                    return Err(PyValueError::new_err(format!(
+                        "Serializer of field '{}' (tag {}) returned None, but JCE has no null value",
+                        field.name, field.tag
+                    )));
+                }
+                None => continue,
+            }
+        } else {
+            value
+        };
+        encode_schema_field(py, writer, field, &value, options, context, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// 逐字段跳过或报错的选项, 均未设置时 `encode_struct_compiled` 选用 `encode_fields_unfiltered`.
+const OPT_FIELD_FILTERS: i64 =
+    OPT_EXCLUDE_UNSET | OPT_OMIT_DEFAULT | OPT_OMIT_EMPTY | OPT_INCLUDE_NONE;
+
+/// 不做字段过滤时的编码循环 (对象而非字典, 无 `field_order`).
+///
+/// Pydantic 模型的字段值都存放在实例字典中, 此时先取一次 `__dict__`, 逐字段直接查字典,
+/// 未命中时回退到 getattr, 省去每个字段在类型上查找描述符的开销.
+/// 其他对象 (普通类, `__slots__`, 自定义 `__getattribute__`) 一律走 getattr,
+/// 以免绕过 property 等数据描述符. 值为 None 的字段照常跳过,
+/// 但类型不接受 None 的字段 (`FieldDef::not_none`) 省去这一检查, None 值交由类型检查报错.
+fn encode_fields_unfiltered<'a, W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    obj: &Bound<'_, PyAny>,
+    fields: impl Iterator<Item = &'a FieldDef>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    let instance_dict =
+        if obj.is_instance(BASE_MODEL_TYPE.import(py, "pydantic", "BaseModel")?)? {
+            obj.getattr(intern!(py, "__dict__"))
+                .ok()
+                .and_then(|d| d.cast_into_exact::<PyDict>().ok())
+        } else {
+            None
+        };
+    for field in fields {
+        let name = field.py_name.bind(py);
+        let value = match instance_dict
+            .as_ref()
+            .map(|d| d.get_item(name))
+            .transpose()?
+        {
+            Some(Some(value)) => value,
+            _ => obj.getattr(name)?,
+        };
+        if !field.not_none && value.is_none() {
+            continue;
+        }
+        let value = if field.has_serializer {
+            match serialize_hook(py, obj, field, value, context)? {
+                Some(value) => value,
+                None => continue,
+            }
+        } else {
+            value
+        };
+        encode_schema_field(py, writer, field, &value, options, context, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// 对声明了编码钩子的字段调用 `__tars_serialize_field__(name, value, context)`.
+///
+/// 字典及没有该方法的对象 (如按 Schema 编码的普通对象) 不调用钩子, 原样返回值.
+/// 钩子返回 None 时结果为 None, 由调用方跳过该字段 (`OPT_INCLUDE_NONE` 下报错).
+fn serialize_hook<'py>(
+    py: Python<'py>,
+    obj: &Bound<'py, PyAny>,
+    field: &FieldDef,
+    value: Bound<'py, PyAny>,
+    context: &Bound<'py, PyAny>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if obj.is_instance_of::<PyDict>() {
+        return Ok(Some(value));
+    }
+    let Some(hook) = obj.getattr_opt(intern!(py, "__tars_serialize_field__"))? else {
+        return Ok(Some(value));
+    };
+    let hooked = hook.call1((field.py_name.bind(py), value, context))?;
+    Ok((!hooked.is_none()).then_some(hooked))
+}
+
+/// 判断值是否为空的字符串, 二进制数据或容器.
+fn is_empty_value(value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let sized = value.is_instance_of::<PyString>()
+        || value.is_instance_of::<PyBytes>()
+        || value.is_instance_of::<PyByteArray>()
+        || value.is_instance_of::<PyList>()
+        || value.is_instance_of::<PyTuple>()
+        || value.is_instance_of::<PyDict>()
+        || value.is_instance_of::<PySet>()
+        || value.is_instance_of::<PyFrozenSet>();
+    Ok(sized && value.len()? == 0)
+}
+
+/// 读取 `context["field_order"]` 指定的字段写出顺序.
+///
+/// 这是为不遵守 Tag 升序约定的对端准备的兼容手段, 产生的数据可能不符合 JCE 规范.
+/// 仅作用于顶层结构体.
+fn context_field_order(context: &Bound<'_, PyAny>) -> PyResult<Option<Vec<u8>>> {
+    let Ok(ctx) = context.cast::<PyDict>() else {
+        return Ok(None);
+    };
+    match ctx.get_item("field_order")? {
+        Some(order) if !order.is_none() => Ok(Some(order.extract()?)),
+        _ => Ok(None),
+    }
+}
+
+/// 确定本次调用的解码后处理函数: `hooks` 参数优先, 否则读取 `context["hooks"]`.
+fn resolve_hooks<'py>(
+    hooks: Option<&Bound<'py, PyDict>>,
+    context: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Option<Bound<'py, PyDict>>> {
+    if let Some(hooks) = hooks {
+        return Ok(Some(hooks.clone()));
+    }
+    let Some(ctx) = context.and_then(|c| c.cast::<PyDict>().ok()) else {
+        return Ok(None);
+    };
+    match ctx.get_item("hooks")? {
+        Some(hooks) if !hooks.is_none() => Ok(Some(hooks.cast_into::<PyDict>().map_err(|_| {
+            PyTypeError::new_err("context['hooks'] must be a dict mapping tags to callables")
+        })?)),
+        _ => Ok(None),
+    }
+}
+
+/// 当前调用的解码后处理函数 (Tag -> callable), 见 `resolve_hooks`.
+fn tag_hooks(py: Python<'_>) -> Option<Bound<'_, PyDict>> {
+    TAG_HOOKS.with(|cell| cell.borrow().as_ref().map(|h| h.bind(py).clone()))
+}
+
+/// 若 `hooks` 中注册了该 Tag, 以 `hook(value, context)` 的返回值替换解码结果.
+///
+/// 对每一层结构体的字段都会生效 (Map/List 元素不受影响).
+fn apply_tag_hook(
+    hooks: Option<&Bound<'_, PyDict>>,
+    tag: u8,
+    value: Py<PyAny>,
+    context: &Bound<'_, PyAny>,
+) -> PyResult<Py<PyAny>> {
+    match hooks.map(|h| h.get_item(tag)).transpose()?.flatten() {
+        Some(hook) => Ok(hook
+            .call1((value, context))
+            .inspect_err(|_| mark_callback_failed())?
+            .unbind()),
+        None => Ok(value),
+    }
+}
+
+/// 按 `order` 中的 Tag 顺序重排字段索引.
+///
+/// `order` 中未出现的字段按 Schema 原顺序追加到末尾, Schema 中不存在的 Tag 被忽略.
+fn ordered_field_indices(tags: &[u8], order: &[u8]) -> Vec<usize> {
+    let mut indices = Vec::with_capacity(tags.len());
+    let mut used = vec![false; tags.len()];
+    for tag in order {
+        if let Some(idx) = tags.iter().position(|t| t == tag)
+            && !used[idx]
+        {
+            used[idx] = true;
+            indices.push(idx);
+        }
+    }
+    indices.extend((0..tags.len()).filter(|&i| !used[i]));
+    indices
+}
+
+/// 以指定编码将 `str` 写为 SimpleList.
+///
+/// 已是 `bytes` 的值原样写入.
+fn encode_text_field<W: JceWriterTrait>(
+    writer: &mut W,
+    tag: u8,
+    value: &Bound<'_, PyAny>,
+    codec: &Bound<'_, PyString>,
+) -> PyResult<()> {
+    if let Ok(bytes) = value.cast::<PyBytes>() {
+        writer.write_bytes(tag, bytes.as_bytes());
+        return Ok(());
+    }
+    let encoded = value.call_method1("encode", (codec,))?;
+    writer.write_bytes(tag, encoded.cast::<PyBytes>()?.as_bytes());
+    Ok(())
+}
+
+/// 按 Schema 字段定义编码字段值.
+///
+/// 依次处理文本 SimpleList (`codec`), 带元素/键值描述的容器, 运行时推断 (255) 和具体类型.
+fn encode_schema_field<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    field: &FieldDef,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    // 枚举成员按其 `.value` 写入
+    let member_value;
+    let value = match &field.enum_cls {
+        Some(cls) if value.is_instance(cls.bind(py))? => {
+            member_value = value.getattr(intern!(py, "value"))?;
+            &member_value
+        }
+        _ => value,
+    };
+    let wire_value;
+    let value = match &field.logical {
+        Some(logical) => {
+            wire_value = logical.to_wire(value, field.tag, options)?;
+            if *logical == LogicalType::Ipv4 {
+                // IP 地址总是网络字节序, 与数据包的字节序无关
+                writer.write_tag(field.tag, JceType::Int4);
+                writer.write_raw(&wire_value.extract::<u32>()?.to_be_bytes());
+                return Ok(());
+            }
+            if let Some(int_type) = logical.fixed_int_type() {
+                writer.write_int_fixed(field.tag, wire_value.extract()?, int_type);
+                return Ok(());
+            }
+            &wire_value
+        }
+        None => value,
+    };
+    if let Some(codec) = &field.codec {
+        return encode_text_field(writer, field.tag, value, codec.bind(py));
+    }
+    if let Some(elem) = &field.elem
+        && field.tars_type == JceType::List as u8
+    {
+        return encode_typed_list(py, writer, field.tag, value, elem, options, context, depth);
+    }
+    if field.tars_type == JceType::Map as u8 && (field.key.is_some() || field.value.is_some()) {
+        return encode_typed_map(py, writer, field, value, options, context, depth);
+    }
+    if let Some(nested) = &field.nested {
+        if field.tars_type == JceType::StructBegin as u8 {
+            return match field.little_endian {
+                Some(true) if !W::Endian::IS_LITTLE => {
+                    encode_struct_with_endian::<W, LittleEndian>(
+                        py, writer, field.tag, nested, value, options, context, depth,
+                    )
+                }
+                Some(false) if W::Endian::IS_LITTLE => encode_struct_with_endian::<W, BigEndian>(
+                    py, writer, field.tag, nested, value, options, context, depth,
+                ),
+                _ => encode_nested_struct(
+                    py, writer, field.tag, nested, value, options, context, depth,
+                ),
+            };
+        }
+        if field.tars_type == JceType::SimpleList as u8
+            && !value.is_instance_of::<PyBytes>()
+            && !value.is_instance_of::<PyString>()
+        {
+            return encode_blob(
+                py,
+                writer,
+                field.tag,
+                value,
+                Some(nested),
+                options,
+                context,
+                depth + 1,
+            );
+        }
+    }
+    if field.tars_type == TYPE_CODE_BOOL {
+        writer.write_int(field.tag, value.extract::<i64>()?);
+        return Ok(());
+    }
+    if field.tars_type == 255 {
+        return encode_generic_field(py, writer, field.tag, value, options, context, depth);
+    }
+    let jce_type = JceType::try_from(field.tars_type).unwrap_or(JceType::ZeroTag);
+    encode_field(
+        py, writer, field.tag, jce_type, value, options, context, depth,
+    )
+}
+
+/// 按键/值类型描述编码 Map.
+///
+/// 未提供描述的一侧使用运行时推断.
+fn encode_typed_map<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    field: &FieldDef,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    let dict = value.cast::<PyDict>()?;
+    encode_map(
+        py,
+        writer,
+        field.tag,
+        dict,
+        field.key.as_ref(),
+        field.value.as_ref(),
+        options,
+        context,
+        depth,
+    )
+}
+
+/// 编码 Map, 键/值按类型描述编码, 未提供描述时使用运行时推断.
+///
+/// `OPT_SORT_MAP_KEYS` 下先将各条目编码到临时缓冲区, 再按键的编码字节排序后写入,
+/// 使插入顺序不同的相等字典得到相同的输出.
+#[allow(clippy::too_many_arguments)]
+fn encode_map<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    dict: &Bound<'_, PyDict>,
+    key: Option<&TypeDesc>,
+    value: Option<&TypeDesc>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    writer.write_map_header(tag, dict.len());
+    if options & OPT_SORT_MAP_KEYS == 0 {
+        for (k, v) in dict {
+            encode_map_item(py, writer, 0, key, &k, options, context, depth + 1)?;
+            encode_map_item(py, writer, 1, value, &v, options, context, depth + 1)?;
+        }
+        return Ok(());
+    }
+    let mut entries = Vec::with_capacity(dict.len());
+    for (k, v) in dict {
+        let mut key_writer = JceWriter::<Vec<u8>, W::Endian>::with_buffer(Vec::new());
+        key_writer.set_config(writer.config());
+        encode_map_item(py, &mut key_writer, 0, key, &k, options, context, depth + 1)?;
+        let mut value_writer = JceWriter::<Vec<u8>, W::Endian>::with_buffer(Vec::new());
+        value_writer.set_config(writer.config());
+        encode_map_item(
+            py,
+            &mut value_writer,
+            1,
+            value,
+            &v,
+            options,
+            context,
+            depth + 1,
+        )?;
+        entries.push((key_writer.finish(), value_writer.finish()));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    for (k, v) in &entries {
+        writer.write_raw(k);
+        writer.write_raw(v);
+    }
+    Ok(())
+}
+
+/// 编码 Map 的键或值.
+#[allow(clippy::too_many_arguments)]
+#[inline]
+fn encode_map_item<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    desc: Option<&TypeDesc>,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    match desc {
+        Some(desc) => encode_desc_value(py, writer, tag, desc, value, options, context, depth),
+        None => encode_generic_field(py, writer, tag, value, options, context, depth),
+    }
+}
+
+/// 按元素类型描述编码 List.
+#[allow(clippy::too_many_arguments)]
+fn encode_typed_list<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    value: &Bound<'_, PyAny>,
+    elem: &TypeDesc,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    let list = list_or_tuple(value)?;
+    writer.write_list_header(tag, list.len());
+    for item in list {
+        encode_desc_value(py, writer, 0, elem, &item, options, context, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// 将 List 字段的值取为 list; tuple 复制为 list, 其他类型报告 TypeError.
+fn list_or_tuple<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyList>> {
+    match value.cast::<PyTuple>() {
+        Ok(tuple) => Ok(tuple.to_list()),
+        Err(_) => Ok(value.cast::<PyList>()?.clone()),
+    }
+}
+
+/// 按类型描述编码单个值.
+///
+/// 结构体描述使用其自身的 Schema 编码, 而非运行时推断.
+#[allow(clippy::too_many_arguments)]
+fn encode_desc_value<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    desc: &TypeDesc,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    match desc {
+        TypeDesc::Code(TYPE_CODE_BOOL) => {
+            writer.write_int(tag, value.extract::<i64>()?);
+            Ok(())
+        }
+        TypeDesc::Code(code) => match JceType::try_from(*code) {
+            Ok(jce_type) => encode_field(py, writer, tag, jce_type, value, options, context, depth),
+            Err(_) => encode_generic_field(py, writer, tag, value, options, context, depth),
+        },
+        TypeDesc::Struct(nested) => {
+            encode_nested_struct(py, writer, tag, nested, value, options, context, depth)
+        }
+    }
+}
+
+/// 按声明的嵌套 Schema 编码结构体字段.
+///
+/// 值的类型与声明一致时直接复用预编译 Schema, 不再查询 `__get_core_schema__`;
+/// 以字段名为键的 dict 按声明的 Schema 编码, 其他 dict 按通用结构体编码,
+/// 其余对象 (如子类实例) 使用其自身的 Schema.
+#[allow(clippy::too_many_arguments)]
+fn encode_nested_struct<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    nested: &NestedSchema,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    writer.write_tag(tag, JceType::StructBegin);
+    encode_struct_body(py, writer, Some(nested), value, options, context, depth + 1)?;
+    writer.write_tag(0, JceType::StructEnd);
+    Ok(())
+}
+
+/// 以字节序 `O` 编码嵌套结构体字段, 结果整体写入外层 Writer.
+///
+/// 头部 (Tag 与类型) 的编码与字节序无关, 因此只需在独立的 Writer 中编码后原样复制.
+#[allow(clippy::too_many_arguments)]
+fn encode_struct_with_endian<W: JceWriterTrait, O: Endianness>(
+    py: Python<'_>,
+    writer: &mut W,
+    tag: u8,
+    nested: &NestedSchema,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    let mut inner = JceWriter::<Vec<u8>, O>::with_buffer(Vec::new());
+    inner.set_config(writer.config());
+    let options = (options & !1) | O::IS_LITTLE as i64;
+    encode_nested_struct(py, &mut inner, tag, nested, value, options, context, depth)?;
+    writer.write_raw(&inner.finish());
+    Ok(())
+}
+
+/// 写入结构体的字段序列 (不含 StructBegin/StructEnd).
+fn encode_struct_body<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    nested: Option<&NestedSchema>,
+    value: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    if let Ok(dict) = value.cast::<PyDict>() {
+        // 含有字段名键的 dict 按声明的 Schema 编码, 否则视为以 Tag 为键的通用结构体
+        if let Some(nested) = nested {
+            let schema = nested.compiled(py)?;
+            if keyed_by_name(dict, schema) {
+                return encode_struct_compiled(py, writer, value, schema, options, context, depth);
+            }
+        }
+        encode_generic_struct(py, writer, dict, options, context, depth)
+    } else if let Some(nested) = nested
+        && nested.matches(value)
+    {
+        let schema = nested.compiled(py)?;
+        encode_struct_compiled(py, writer, value, schema, options, context, depth)
+    } else if let Some(schema_method) = schema_method(value) {
+        encode_struct(
+            py,
+            writer,
+            value,
+            &schema_method.call0()?,
+            options,
+            context,
+            depth,
         )
+    } else {
+        Err(PyTypeError::new_err("Cannot encode as struct"))
     }
 }
 
-/// JCE 写入器特征.
-///
-/// 定义了统一的写入接口，允许 `encode_struct` 等函数以泛型方式工作，
-/// 从而支持 `JceWriter<Vec<u8>, BigEndian>` 和 `JceWriter<Vec<u8>, LittleEndian>`
-/// 以及其他实现了 `BufMut` 的后端.
-pub(crate) trait JceWriterTrait {
-    fn write_tag(&mut self, tag: u8, type_id: JceType);
-    fn write_int(&mut self, tag: u8, value: i64);
-    fn write_float(&mut self, tag: u8, value: f32);
-    fn write_double(&mut self, tag: u8, value: f64);
-    fn write_string(&mut self, tag: u8, value: &str);
-    fn write_bytes(&mut self, tag: u8, value: &[u8]);
+/// 判断 dict 是否以 Schema 中的字段名 (或别名) 为键.
+fn keyed_by_name(dict: &Bound<'_, PyDict>, schema: &CompiledSchema) -> bool {
+    dict.keys().iter().any(|key| {
+        key.cast::<PyString>()
+            .ok()
+            .and_then(|name| name.to_str().ok())
+            .is_some_and(|name| schema.fields.iter().any(|f| f.matches_name(name)))
+    })
 }
 
-impl<B: bytes::BufMut, E: crate::codec::endian::Endianness> JceWriterTrait for JceWriter<B, E> {
-    #[inline]
-    fn write_tag(&mut self, tag: u8, type_id: JceType) {
-        self.write_tag(tag, type_id)
-    }
-    #[inline]
-    fn write_int(&mut self, tag: u8, value: i64) {
-        self.write_int(tag, value)
-    }
-    #[inline]
-    fn write_float(&mut self, tag: u8, value: f32) {
-        self.write_float(tag, value)
-    }
-    #[inline]
-    fn write_double(&mut self, tag: u8, value: f64) {
-        self.write_double(tag, value)
-    }
-    #[inline]
-    fn write_string(&mut self, tag: u8, value: &str) {
-        self.write_string(tag, value)
-    }
-    #[inline]
-    fn write_bytes(&mut self, tag: u8, value: &[u8]) {
-        self.write_bytes(tag, value)
+/// 按 Schema 编码的 dict 必须以字段名为键; 混入的整数 Tag 键会被静默忽略, 因此报错.
+fn check_name_keys(dict: &Bound<'_, PyDict>) -> PyResult<()> {
+    match dict.keys().iter().find(|key| key.is_instance_of::<PyInt>()) {
+        Some(tag) => Err(PyValueError::new_err(format!(
+            "Dict encoded with a schema must use field-name keys, got tag key {}",
+            tag
+        ))),
+        None => Ok(()),
     }
 }
 
-/// 编码结构体 (对象 -> bytes).
-///
-/// 根据 Schema 遍历对象属性并写入 JCE 流.
-/// 支持 `exclude_unset` 和 `omit_default` 选项.
+/// 没有嵌套 Schema 时二进制块内容的预估字节数, 按最短的长度字段宽度预留.
+const BLOB_SIZE_HINT: usize = 0;
+
+/// 将值编码为二进制块 (SimpleList) 写入.
 ///
-/// 优先使用编译后的 Schema 以获得最佳性能.
-pub(crate) fn encode_struct<W: JceWriterTrait>(
+/// 结构体与 dict 编码为字段序列, 其余值编码为 Tag 0 的单个字段.
+/// 内容直接写入当前 Writer, 完成后回填长度 (见 `JceWriter::begin_bytes`),
+/// 避免大块嵌套内容先写入临时缓冲区再整体复制. 长度字段按嵌套 Schema 的预估大小预留,
+/// 没有 Schema 时按短内容 (<128 字节) 预留; 预估的宽度不对时回填需要移动一次内容.
+#[allow(clippy::too_many_arguments)]
+fn encode_blob<W: JceWriterTrait>(
     py: Python<'_>,
     writer: &mut W,
-    obj: &Bound<'_, PyAny>,
-    schema: &Bound<'_, PyAny>,
-    options: i32,
+    tag: u8,
+    value: &Bound<'_, PyAny>,
+    nested: Option<&NestedSchema>,
+    options: i64,
     context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
-    if depth > MAX_DEPTH {
-        return Err(PyValueError::new_err("Depth exceeded"));
-    }
-    if let Some(capsule_py) = get_or_compile_schema(py, schema)? {
-        let capsule = capsule_py.bind(py);
-        let ptr = capsule
-            .pointer_checked(None)
-            .map_err(|_| PyValueError::new_err("Invalid capsule"))?;
-        let compiled = unsafe { &*(ptr.as_ptr() as *mut CompiledSchema) };
-        return encode_struct_compiled(py, writer, obj, compiled, options, context, depth);
-    }
-    let schema_list = schema.cast::<PyList>()?;
-    for item in schema_list.iter() {
-        let tuple = item.cast::<PyTuple>()?;
-        let name: String = tuple.get_item(0)?.extract()?;
-        let tag: u8 = tuple.get_item(1)?.extract()?;
-        let jce_type_code: u8 = tuple.get_item(2)?.extract()?;
-        let default_val = tuple.get_item(3)?;
-        let value = obj.getattr(&name)?;
-
-        // 1. 基础过滤: None 值总是跳过
-        if value.is_none() {
-            continue;
-        }
-
-        // 2. 选项过滤: 排除未设置的字段 (仅 Pydantic 模型)
-        if (options & OPT_EXCLUDE_UNSET) != 0
-            && let Ok(model_fields_set) = obj.getattr("model_fields_set")
-            && !model_fields_set
-                .call_method1("__contains__", (&name,))?
-                .extract::<bool>()?
-        {
-            continue;
-        }
-
-        // 3. 选项过滤: 排除等于默认值的字段
-        if (options & OPT_OMIT_DEFAULT) != 0 && value.eq(&default_val)? {
-            continue;
-        }
-
-        // 4. 类型分发: 泛型 (255) 或 具体类型
-        if jce_type_code == 255 {
-            encode_generic_field(py, writer, tag, &value, options, context, depth + 1)?;
-        } else {
-            let jce_type = JceType::try_from(jce_type_code).unwrap();
-            encode_field(
-                py,
-                writer,
-                tag,
-                jce_type,
-                &value,
-                options,
-                context,
-                depth + 1,
-            )?;
-        }
-    }
+    let size_hint = match nested {
+        Some(n) if n.matches(value) => n.compiled(py)?.size_hint,
+        _ => BLOB_SIZE_HINT,
+    };
+    let mark = writer.begin_bytes(tag, size_hint);
+    encode_blob_payload(py, writer, value, nested, options, context, depth)?;
+    writer.end_bytes(mark);
     Ok(())
 }
 
-fn encode_struct_compiled<W: JceWriterTrait>(
+/// 写入二进制块的内容.
+fn encode_blob_payload<W: JceWriterTrait>(
     py: Python<'_>,
     writer: &mut W,
-    obj: &Bound<'_, PyAny>,
-    schema: &CompiledSchema,
-    options: i32,
+    value: &Bound<'_, PyAny>,
+    nested: Option<&NestedSchema>,
+    options: i64,
     context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
-    let fields_set = if (options & OPT_EXCLUDE_UNSET) != 0 {
-        obj.getattr("model_fields_set").ok()
+    let is_struct = value.cast::<PyDict>().is_ok()
+        || nested.is_some_and(|n| n.matches(value))
+        || schema_method(value).is_some();
+    if is_struct {
+        encode_struct_body(py, writer, nested, value, options, context, depth)
     } else {
-        None
-    };
-
-    for field in &schema.fields {
-        // 2. 检查 exclude_unset
-        if let Some(fs) = &fields_set {
-            // 使用 field.py_name (Interned String) 进行快速查找
-            // contains 方法在底层通常是 O(1)
-            if !fs.contains(field.py_name.bind(py))? {
-                continue;
-            }
-        }
-        // Optimization: Use interned py_name for getattr
-        let value = obj.getattr(field.py_name.bind(py))?;
-        if value.is_none() {
-            continue;
-        }
-        if (options & OPT_OMIT_DEFAULT) != 0 && value.eq(field.default_val.bind(py))? {
-            continue;
-        }
-        if field.tars_type == 255 {
-            encode_generic_field(py, writer, field.tag, &value, options, context, depth + 1)?;
-        } else {
-            let jce_type = JceType::try_from(field.tars_type).unwrap_or(JceType::ZeroTag);
-            encode_field(
-                py,
-                writer,
-                field.tag,
-                jce_type,
-                &value,
-                options,
-                context,
-                depth + 1,
-            )?;
-        }
+        encode_generic_field(py, writer, 0, value, options, context, depth)
     }
-    Ok(())
 }
 
 /// 编码单个字段.
@@ -445,7 +3032,7 @@ fn encode_field<W: JceWriterTrait>(
     tag: u8,
     jce_type: JceType,
     value: &Bound<'_, PyAny>,
-    options: i32,
+    options: i64,
     context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
@@ -453,24 +3040,22 @@ fn encode_field<W: JceWriterTrait>(
         JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => {
             writer.write_int(tag, value.extract()?)
         }
-        JceType::Float => writer.write_float(tag, value.extract()?),
-        JceType::Double => writer.write_double(tag, value.extract()?),
+        JceType::Float => {
+            let v: f32 = value.extract()?;
+            check_finite(tag, v as f64, options)?;
+            writer.write_float(tag, v)
+        }
+        JceType::Double => writer.write_double(tag, check_finite(tag, value.extract()?, options)?),
         JceType::String1 | JceType::String4 => {
-            writer.write_string(tag, &value.extract::<String>()?)
+            write_text(py, writer, tag, &value.extract::<String>()?)?
         }
         JceType::Map => {
             let dict = value.cast::<PyDict>()?;
-            writer.write_tag(tag, JceType::Map);
-            writer.write_int(0, dict.len() as i64);
-            for (k, v) in dict {
-                encode_generic_field(py, writer, 0, &k, options, context, depth + 1)?;
-                encode_generic_field(py, writer, 1, &v, options, context, depth + 1)?;
-            }
+            encode_map(py, writer, tag, dict, None, None, options, context, depth)?;
         }
         JceType::List => {
-            let list = value.cast::<PyList>()?;
-            writer.write_tag(tag, JceType::List);
-            writer.write_int(0, list.len() as i64);
+            let list = list_or_tuple(value)?;
+            writer.write_list_header(tag, list.len());
             for item in list {
                 encode_generic_field(py, writer, 0, &item, options, context, depth + 1)?;
             }
@@ -478,116 +3063,22 @@ fn encode_field<W: JceWriterTrait>(
         JceType::SimpleList => {
             if let Ok(bytes) = value.cast::<PyBytes>() {
                 writer.write_bytes(tag, bytes.as_bytes());
+            } else if let Ok(text) = value.cast::<PyString>() {
+                // 声明为 bytes 的字段收到 str 时按 UTF-8 写入, 而不是作为二进制块编码
+                if options & OPT_STRICT_BYTES != 0 {
+                    return Err(PyTypeError::new_err(format!(
+                        "Field tag {} expects bytes, got str",
+                        tag
+                    )));
+                }
+                writer.write_bytes(tag, text.to_str()?.as_bytes());
             } else {
-                let inner_bytes = if options & 1 == 0 {
-                    let mut bytes_out = Vec::new();
-                    let mut done = false;
-                    TLS_WRITER.with(|cell| {
-                        if let Ok(mut writer) = cell.try_borrow_mut() {
-                            writer.clear();
-                            if let Ok(dict) = value.cast::<PyDict>() {
-                                encode_generic_struct(
-                                    py,
-                                    &mut *writer,
-                                    dict,
-                                    options,
-                                    context,
-                                    depth + 1,
-                                )?;
-                            } else if let Ok(schema_method) = value.getattr("__get_core_schema__") {
-                                encode_struct(
-                                    py,
-                                    &mut *writer,
-                                    value,
-                                    &schema_method.call0()?,
-                                    options,
-                                    context,
-                                    depth + 1,
-                                )?;
-                            } else {
-                                encode_generic_field(
-                                    py,
-                                    &mut *writer,
-                                    0,
-                                    value,
-                                    options,
-                                    context,
-                                    depth + 1,
-                                )?;
-                            }
-                            bytes_out = writer.get_buffer().to_vec();
-                            done = true;
-                        }
-                        Ok::<(), PyErr>(())
-                    })?;
-                    if !done {
-                        let mut w = JceWriter::<Vec<u8>, BigEndian>::new();
-                        if let Ok(dict) = value.cast::<PyDict>() {
-                            encode_generic_struct(py, &mut w, dict, options, context, depth + 1)?;
-                        } else if let Ok(schema_method) = value.getattr("__get_core_schema__") {
-                            encode_struct(
-                                py,
-                                &mut w,
-                                value,
-                                &schema_method.call0()?,
-                                options,
-                                context,
-                                depth + 1,
-                            )?;
-                        } else {
-                            encode_generic_field(
-                                py,
-                                &mut w,
-                                0,
-                                value,
-                                options,
-                                context,
-                                depth + 1,
-                            )?;
-                        }
-                        bytes_out = w.get_buffer().to_vec();
-                    }
-                    bytes_out
-                } else {
-                    let mut w =
-                        JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::with_capacity(128));
-                    if let Ok(dict) = value.cast::<PyDict>() {
-                        encode_generic_struct(py, &mut w, dict, options, context, depth + 1)?;
-                    } else if let Ok(schema_method) = value.getattr("__get_core_schema__") {
-                        encode_struct(
-                            py,
-                            &mut w,
-                            value,
-                            &schema_method.call0()?,
-                            options,
-                            context,
-                            depth + 1,
-                        )?;
-                    } else {
-                        encode_generic_field(py, &mut w, 0, value, options, context, depth + 1)?;
-                    }
-                    w.get_buffer().to_vec()
-                };
-                writer.write_bytes(tag, &inner_bytes);
+                encode_blob(py, writer, tag, value, None, options, context, depth + 1)?;
             }
         }
         JceType::StructBegin => {
             writer.write_tag(tag, JceType::StructBegin);
-            if let Ok(schema_method) = value.getattr("__get_core_schema__") {
-                encode_struct(
-                    py,
-                    writer,
-                    value,
-                    &schema_method.call0()?,
-                    options,
-                    context,
-                    depth + 1,
-                )?;
-            } else if let Ok(dict) = value.cast::<PyDict>() {
-                encode_generic_struct(py, writer, dict, options, context, depth + 1)?;
-            } else {
-                return Err(PyTypeError::new_err("Cannot encode as struct"));
-            }
+            encode_struct_body(py, writer, None, value, options, context, depth + 1)?;
             writer.write_tag(0, JceType::StructEnd);
         }
         _ => return Err(PyValueError::new_err("Unsupported type")),
@@ -597,7 +3088,7 @@ fn encode_field<W: JceWriterTrait>(
 
 /// 编码通用结构体 (dict -> bytes).
 ///
-/// 遍历字典，按 Tag 顺序写入每个字段.
+/// 遍历字典，按 Tag 顺序写入每个字段. `OPT_OMIT_EMPTY` 下跳过值为空的字段.
 ///
 /// Args:
 ///     py: Python 解释器.
@@ -610,11 +3101,11 @@ pub(crate) fn encode_generic_struct<W: JceWriterTrait>(
     py: Python<'_>,
     writer: &mut W,
     data: &Bound<'_, PyDict>,
-    options: i32,
+    options: i64,
     context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
-    if depth > MAX_DEPTH {
+    if depth > max_depth() {
         return Err(PyValueError::new_err("Depth exceeded"));
     }
     let mut items: Vec<(u8, Bound<'_, PyAny>)> = Vec::with_capacity(data.len());
@@ -638,6 +3129,48 @@ pub(crate) fn encode_generic_struct<W: JceWriterTrait>(
     // JCE 要求字段按 Tag 升序写入
     items.sort_by_key(|(t, _)| *t);
     for (tag, value) in items {
+        if options & OPT_OMIT_EMPTY != 0 && is_empty_value(&value)? {
+            continue;
+        }
+        encode_generic_field(py, writer, tag, &value, options, context, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// 将 `[(tag, value), ...]` 形式的列表解析为结构体字段.
+///
+/// 所有元素均为首项是合法 Tag 的二元组时返回字段列表, 否则返回 None.
+fn struct_pairs<'py>(list: &Bound<'py, PyList>) -> Option<Vec<(u8, Bound<'py, PyAny>)>> {
+    list.iter()
+        .map(|item| {
+            let pair = item.cast::<PyTuple>().ok()?;
+            if pair.len() != 2 {
+                return None;
+            }
+            Some((
+                pair.get_item(0).ok()?.extract().ok()?,
+                pair.get_item(1).ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// 按给定顺序编码结构体字段, 不排序也不去重; `OPT_OMIT_EMPTY` 下跳过值为空的字段.
+fn encode_generic_pairs<W: JceWriterTrait>(
+    py: Python<'_>,
+    writer: &mut W,
+    pairs: Vec<(u8, Bound<'_, PyAny>)>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
+    }
+    for (tag, value) in pairs {
+        if options & OPT_OMIT_EMPTY != 0 && is_empty_value(&value)? {
+            continue;
+        }
         encode_generic_field(py, writer, tag, &value, options, context, depth + 1)?;
     }
     Ok(())
@@ -646,30 +3179,61 @@ pub(crate) fn encode_generic_struct<W: JceWriterTrait>(
 /// 编码通用字段.
 ///
 /// 根据值的 Python 类型推断 JCE 类型并写入.
-/// 支持 int, float, str, bytes, list, dict 等.
+/// `OPT_ORDERED_PAIRS` 下非空的 `(tag, value)` 列表按结构体编码.
+/// 支持 int, float, str, bytes, list, dict 等; `Decimal` 按字符串写入,
+/// `datetime`/`date` 按 Int8 的 Unix 时间戳写入.
 pub(crate) fn encode_generic_field<W: JceWriterTrait>(
     py: Python<'_>,
     writer: &mut W,
     tag: u8,
     value: &Bound<'_, PyAny>,
-    options: i32,
+    options: i64,
     context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
     if let Ok(v) = value.extract::<i64>() {
         writer.write_int(tag, v);
-    } else if let Ok(v) = value.extract::<f64>() {
-        writer.write_double(tag, v);
+    } else if let Ok(f) = value.cast::<PyFloat>() {
+        writer.write_double(tag, check_finite(tag, f.value(), options)?);
     } else if let Ok(b) = value.cast::<PyBytes>() {
         writer.write_bytes(tag, b.as_bytes());
-    } else if let Ok(s) = value.extract::<String>() {
-        writer.write_string(tag, &s);
+    } else if let Ok(s) = value.cast::<PyString>() {
+        write_text(py, writer, tag, s.to_str()?)?;
+    } else if value.is_instance(decimal_type(py)?)? {
+        // Decimal 以字符串写入, 避免转换为 Double 时丢失精度; 须先于 `__float__` 检查
+        write_text(py, writer, tag, value.str()?.to_str()?)?;
+    } else if let Ok(v) = value.extract::<f64>() {
+        writer.write_double(tag, check_finite(tag, v, options)?);
     } else if let Ok(l) = value.cast::<PyList>() {
-        writer.write_tag(tag, JceType::List);
-        writer.write_int(0, l.len() as i64);
+        if options & OPT_ORDERED_PAIRS != 0
+            && !l.is_empty()
+            && let Some(pairs) = struct_pairs(l)
+        {
+            writer.write_tag(tag, JceType::StructBegin);
+            encode_generic_pairs(py, writer, pairs, options, context, depth + 1)?;
+            writer.write_tag(0, JceType::StructEnd);
+            return Ok(());
+        }
+        writer.write_list_header(tag, l.len());
         for item in l {
             encode_generic_field(py, writer, 0, &item, options, context, depth + 1)?;
         }
+    } else if let Ok(t) = value.cast::<PyTuple>() {
+        writer.write_list_header(tag, t.len());
+        for item in t {
+            encode_generic_field(py, writer, 0, &item, options, context, depth + 1)?;
+        }
+    } else if value.is_instance_of::<PySet>() || value.is_instance_of::<PyFrozenSet>() {
+        // 集合无序, 排序后写入以保证输出确定
+        let items = PyList::empty(py);
+        for item in value.try_iter()? {
+            items.append(item?)?;
+        }
+        items.sort()?;
+        writer.write_list_header(tag, items.len());
+        for item in items {
+            encode_generic_field(py, writer, 0, &item, options, context, depth + 1)?;
+        }
     } else if let Ok(d) = value.cast::<PyDict>() {
         let type_name = value.get_type().name()?;
         // 特殊处理: StructDict (作为 Struct 编码) vs 普通 Dict (作为 Map 编码)
@@ -678,14 +3242,13 @@ pub(crate) fn encode_generic_field<W: JceWriterTrait>(
             encode_generic_struct(py, writer, d, options, context, depth + 1)?;
             writer.write_tag(0, JceType::StructEnd);
         } else {
-            writer.write_tag(tag, JceType::Map);
-            writer.write_int(0, d.len() as i64);
-            for (k, v) in d {
-                encode_generic_field(py, writer, 0, &k, options, context, depth + 1)?;
-                encode_generic_field(py, writer, 1, &v, options, context, depth + 1)?;
-            }
+            encode_map(py, writer, tag, d, None, None, options, context, depth)?;
         }
-    } else if let Ok(schema_method) = value.getattr("__get_core_schema__") {
+    } else if let Ok(s) = value.cast::<JceStr>() {
+        write_text(py, writer, tag, &s.get().to_text(py)?)?;
+    } else if let Some((v, int_type)) = extract_fixed(value) {
+        writer.write_int_fixed(tag, v, int_type);
+    } else if let Some(schema_method) = schema_method(value) {
         writer.write_tag(tag, JceType::StructBegin);
         encode_struct(
             py,
@@ -697,12 +3260,76 @@ pub(crate) fn encode_generic_field<W: JceWriterTrait>(
             depth + 1,
         )?;
         writer.write_tag(0, JceType::StructEnd);
+    } else if value.is_instance(ARRAY_TYPE.import(py, "array", "array")?)? {
+        encode_int_array(writer, tag, value)?;
+    } else if let Ok(encode_method) = value.getattr(intern!(py, "__tars_encode__")) {
+        // 自定义对象: 由 `__tars_encode__(context)` 返回可编码的基础值
+        let encoded = encode_method.call1((context,))?;
+        encode_generic_field(py, writer, tag, &encoded, options, context, depth + 1)?;
+    } else if let Some(timestamp) =
+        datetime_to_timestamp(value, TimeUnit::from_options(options), tag, options)?
+    {
+        writer.write_int_fixed(tag, timestamp, JceType::Int8);
     } else {
         return Err(PyTypeError::new_err("Cannot infer type"));
     }
     Ok(())
 }
 
+/// 在 `OPT_REJECT_NONFINITE` 下拒绝 NaN 与无穷大.
+#[inline]
+fn check_finite(tag: u8, v: f64, options: i64) -> PyResult<f64> {
+    if options & OPT_REJECT_NONFINITE != 0 && !v.is_finite() {
+        return Err(PyValueError::new_err(format!(
+            "Non-finite float value {} at tag {}",
+            v, tag
+        )));
+    }
+    Ok(v)
+}
+
+/// 将浮点数转换为 Python 对象; `OPT_REJECT_NONFINITE` 下 NaN 与无穷大转换为 None.
+#[inline]
+fn float_to_py(py: Python<'_>, v: f64, options: i64) -> PyResult<Py<PyAny>> {
+    if options & OPT_REJECT_NONFINITE != 0 && !v.is_finite() {
+        return Ok(py.None());
+    }
+    Ok(v.into_pyobject(py)?.unbind().into_any())
+}
+
+/// 将 `array.array` 整数数组编码为定宽整数 SimpleList.
+///
+/// 有符号类型按数组元素宽度写入; 无符号类型提升一级宽度以容纳全部取值.
+fn encode_int_array<W: JceWriterTrait>(
+    writer: &mut W,
+    tag: u8,
+    array: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let typecode: String = array.getattr("typecode")?.extract()?;
+    let itemsize: usize = array.getattr("itemsize")?.extract()?;
+    let width = match typecode.as_str() {
+        "b" | "h" | "i" | "l" | "q" => itemsize,
+        "B" | "H" | "I" | "L" | "Q" => (itemsize * 2).min(8),
+        _ => {
+            return Err(PyTypeError::new_err(format!(
+                "Cannot encode array of typecode '{}' as SimpleList",
+                typecode
+            )));
+        }
+    };
+    let element_type = match width {
+        1 => JceType::Int1,
+        2 => JceType::Int2,
+        4 => JceType::Int4,
+        _ => JceType::Int8,
+    };
+    // 超出 i64 的无符号值在提取时即抛出 OverflowError
+    let values: Vec<i64> = array.call_method0("tolist")?.extract()?;
+    writer
+        .write_simple_list(tag, element_type, &values)
+        .map_err(|e| PyOverflowError::new_err(e.to_string()))
+}
+
 /// 解码结构体 (bytes -> dict).
 ///
 /// 根据 Schema 解析输入流，生成包含字段值的字典.
@@ -717,118 +3344,259 @@ pub(crate) fn decode_struct<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
     schema: &Bound<'_, PyAny>,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
-    if depth > MAX_DEPTH {
+    if depth > max_depth() {
         return Err(PyValueError::new_err("Depth exceeded"));
     }
-    if let Some(capsule_py) = get_or_compile_schema(py, schema)? {
-        let capsule = capsule_py.bind(py);
-        let ptr = capsule
-            .pointer_checked(None)
-            .map_err(|_| PyValueError::new_err("Invalid capsule"))?;
-        let compiled = unsafe { &*(ptr.as_ptr() as *mut CompiledSchema) };
-        return decode_struct_compiled(py, reader, compiled, options, depth);
-    }
-    let schema_list = schema.cast::<PyList>()?;
-    let result_dict = PyDict::new(py);
+    with_compiled_schema(py, schema, |compiled| {
+        decode_struct_compiled(py, reader, compiled, options, context, depth)
+    })
+}
 
-    // 构建 Tag -> FieldInfo 的映射 (O(N))
-    let mut tag_map = std::collections::HashMap::new();
-    let schema_items: Vec<Bound<'_, PyTuple>> = schema_list
-        .iter()
-        .map(|item| item.cast_into::<PyTuple>())
-        .collect::<Result<Vec<_>, _>>()?;
-    for tuple in &schema_items {
-        tag_map.insert(tuple.get_item(1)?.extract::<u8>()?, tuple);
+/// 使用预编译 Schema 解码结构体 (Fast Path).
+///
+/// 利用 `CompiledSchema` 中的 Tag 查找表 (O(1)) 加速字段定位.
+fn decode_struct_compiled<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    schema: &CompiledSchema,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    if depth > max_depth() {
+        return Err(PyValueError::new_err("Depth exceeded"));
     }
+    let result_dict = PyDict::new(py);
+    decode_struct_fields(
+        py,
+        reader,
+        schema,
+        options,
+        context,
+        depth,
+        |field, value| result_dict.set_item(field.py_name.bind(py), value),
+    )?;
+    Ok(result_dict.into())
+}
 
-    // 遍历数据流解码字段
+/// 按 Schema 解码结构体的各个字段, 通过 `store` 交给调用方保存.
+///
+/// 数据中缺失的字段以默认值调用 `store`; `OPT_NO_DEFAULT_FILL` 下仅保存数据中实际存在的字段.
+fn decode_struct_fields<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    schema: &CompiledSchema,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+    mut store: impl FnMut(&FieldDef, Py<PyAny>) -> PyResult<()>,
+) -> PyResult<()> {
+    let hooks = tag_hooks(py);
+    let max_fields = max_fields();
+    let mut fields = 0usize;
+    let mut seen = vec![false; schema.fields.len()];
+    // 遍历 reader 直到遇到 StructEnd 或流结束
     while !reader.is_end() {
+        let offset = reader.position() as usize;
         let (tag, jce_type) = reader.read_head()?;
         if jce_type == JceType::StructEnd {
+            check_struct_end(options, depth, offset)?;
             break;
         }
+        count_struct_field(&mut fields, max_fields, offset)?;
+        // 在 Schema 中查找对应的 Tag (O(1) 查找)
+        if let Some(field_idx) = schema.tag_lookup[tag as usize] {
+            let field = &schema.fields[field_idx];
+            // 递归解码字段值
+            let value =
+                decode_schema_field(py, reader, field, jce_type, options, context, depth + 1)?;
+            let value = to_enum_member(py, field, value, options)?;
+            let value = from_logical(py, field, value, options)?;
+            let value = deserialize_hook(py, schema, field, value, context)?;
+            let value = apply_tag_hook(hooks.as_ref(), tag, value, context)?;
+            store(field, value)?;
+            seen[field_idx] = true;
+        } else {
+            // 未知 Tag，跳过该字段 (向前兼容)
+            reader.skip_field(jce_type)?;
+        }
+    }
+    if options & OPT_NO_DEFAULT_FILL != 0 {
+        return Ok(());
+    }
+    for (field, _) in schema.fields.iter().zip(seen).filter(|(_, seen)| !seen) {
+        if !skip_backfill(py, field, options) {
+            store(field, fresh_default(py, field)?.unbind())?;
+        }
+    }
+    Ok(())
+}
 
-        // 查找当前 Tag 是否在 Schema 中定义
-        if let Some(tuple) = tag_map.get(&tag) {
-            let name: String = tuple.get_item(0)?.extract()?;
-            let jce_type_code: u8 = tuple.get_item(2)?.extract()?;
+/// `OPT_SLOTS` 下将顶层结构体直接解码为目标类的实例.
+///
+/// 通过 `target.__new__(target)` 创建实例 (不调用 `__init__`), 再以驻留的字段名逐个 `setattr`,
+/// 不创建中间字典, 适用于定义了 `__slots__` 的普通类. 嵌套结构体仍解码为字典.
+fn decode_struct_slots<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    target: &Bound<'_, PyAny>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+) -> PyResult<Py<PyAny>> {
+    let instance = target.call_method1(intern!(py, "__new__"), (target,))?;
+    with_compiled_schema(py, target, |compiled| {
+        decode_struct_fields(py, reader, compiled, options, context, 0, |field, value| {
+            instance.setattr(field.py_name.bind(py), value)
+        })
+    })?;
+    Ok(instance.unbind())
+}
 
-            // 解码值: 泛型 (255) 或 具体类型
-            let value = if jce_type_code == 255 {
-                decode_generic_field(py, reader, jce_type, options, BytesMode::Auto, depth + 1)?
+/// 将声明了枚举类型的字段值转换为枚举成员.
+///
+/// 未知的值默认抛出 ValueError, `OPT_ENUM_PASSTHROUGH` 下保留原始值.
+fn to_enum_member(
+    py: Python<'_>,
+    field: &FieldDef,
+    raw: Py<PyAny>,
+    options: i64,
+) -> PyResult<Py<PyAny>> {
+    let Some(cls) = &field.enum_cls else {
+        return Ok(raw);
+    };
+    match cls.bind(py).call1((raw.bind(py),)) {
+        Ok(member) => Ok(member.unbind()),
+        Err(e) if e.is_instance_of::<PyValueError>(py) => {
+            if options & OPT_ENUM_PASSTHROUGH != 0 {
+                Ok(raw)
             } else {
-                decode_field(
-                    py,
-                    reader,
-                    jce_type,
-                    JceType::try_from(jce_type_code).unwrap(),
-                    options,
-                    depth + 1,
-                )?
-            };
-            result_dict.set_item(name, value)?;
-        } else {
-            // 未知 Tag，跳过 (向前兼容)
-            reader.skip_field(jce_type)?;
+                Err(PyValueError::new_err(format!(
+                    "Unknown {} value {} at tag {}",
+                    cls.bind(py).name()?,
+                    raw.bind(py).repr()?,
+                    field.tag
+                )))
+            }
         }
+        Err(e) => Err(e),
+    }
+}
+
+/// 将声明了逻辑类型的字段值从基础值还原 (如字符串还原为 `Decimal`).
+#[inline]
+fn from_logical(
+    py: Python<'_>,
+    field: &FieldDef,
+    raw: Py<PyAny>,
+    options: i64,
+) -> PyResult<Py<PyAny>> {
+    match &field.logical {
+        Some(logical) => logical.from_wire(py, raw, field.tag, options),
+        None => Ok(raw),
+    }
+}
+
+/// 对声明了解码钩子的字段调用 `__tars_deserialize_field__(name, raw, context)`.
+///
+/// 未声明钩子的字段原样返回, 不产生额外开销.
+fn deserialize_hook(
+    py: Python<'_>,
+    schema: &CompiledSchema,
+    field: &FieldDef,
+    raw: Py<PyAny>,
+    context: &Bound<'_, PyAny>,
+) -> PyResult<Py<PyAny>> {
+    match &schema.owner {
+        Some(owner) if field.has_deserializer => Ok(owner
+            .bind(py)
+            .call_method1(
+                intern!(py, "__tars_deserialize_field__"),
+                (field.py_name.bind(py), raw, context),
+            )?
+            .unbind()),
+        _ => Ok(raw),
+    }
+}
+
+/// 以零分配的结构扫描校验整个数据包, 用于在构建任何 Python 对象之前拒绝结构非法的数据.
+///
+/// 只检查类型码, 长度与嵌套是否合法, 字段类型与 Schema 是否匹配等仍由随后的解码检查.
+/// 判定与解码器保持一致: 顶层 StructEnd 之后的数据不参与校验, 嵌套结构体可在数据末尾结束.
+fn validate_structure(bytes: &[u8], options: i64, max_depth: usize) -> PyResult<()> {
+    if options & 1 == 0 {
+        JceScanner::<BigEndian>::new(bytes)
+            .with_max_depth(max_depth)
+            .allow_open_structs(true)
+            .validate_struct()?;
+    } else {
+        JceScanner::<LittleEndian>::new(bytes)
+            .with_max_depth(max_depth)
+            .allow_open_structs(true)
+            .validate_struct()?;
     }
+    Ok(())
+}
 
-    // 填充缺失字段的默认值
-    for tuple in &schema_items {
-        let name: String = tuple.get_item(0)?.extract()?;
-        if !result_dict.contains(&name)? {
-            result_dict.set_item(name, tuple.get_item(3)?)?;
-        }
+/// 检查结构体字段循环中遇到的 StructEnd 是否合法.
+///
+/// 顶层 (depth 为 0) 没有与之配对的 StructBegin, `OPT_STRICT_STRUCT_END` 下视为数据损坏;
+/// 默认仍按旧行为在此处停止解码.
+fn check_struct_end(options: i64, depth: usize, offset: usize) -> PyResult<()> {
+    if depth == 0 && options & OPT_STRICT_STRUCT_END != 0 {
+        return Err(CodecError::new(offset, "Unexpected StructEnd at top level").into());
     }
-    Ok(result_dict.into())
+    Ok(())
 }
 
-/// 使用预编译 Schema 解码结构体 (Fast Path).
+/// 使用预编译 Schema 进行投影解码.
 ///
-/// 利用 `CompiledSchema` 中的 Tag 查找表 (O(1)) 加速字段定位.
-fn decode_struct_compiled<'a, E: crate::codec::endian::Endianness>(
+/// 与 `decode_struct_compiled` 相同的 O(1) Tag 查找, 但额外检查 Tag 是否在 `tags` 中,
+/// 不在集合中的字段直接 `skip_field`. 仅为请求的字段回填默认值.
+fn decode_struct_projected<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
     schema: &CompiledSchema,
-    options: i32,
+    tags: &HashSet<u8>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
+    let hooks = tag_hooks(py);
     let result_dict = PyDict::new(py);
-    // 遍历 reader 直到遇到 StructEnd 或流结束
     while !reader.is_end() {
+        let offset = reader.position() as usize;
         let (tag, jce_type) = reader.read_head()?;
         if jce_type == JceType::StructEnd {
+            check_struct_end(options, depth, offset)?;
             break;
         }
-        // 在 Schema 中查找对应的 Tag (O(1) 查找)
-        if let Some(field_idx) = schema.tag_lookup[tag as usize] {
-            let field = &schema.fields[field_idx];
-            // 递归解码字段值
-            let value = if field.tars_type == 255 {
-                decode_generic_field(py, reader, jce_type, options, BytesMode::Auto, depth + 1)?
-            } else {
-                decode_field(
-                    py,
-                    reader,
-                    jce_type,
-                    JceType::try_from(field.tars_type).unwrap(),
-                    options,
-                    depth + 1,
-                )?
-            };
-            result_dict.set_item(field.py_name.bind(py), value)?;
-        } else {
-            // 未知 Tag，跳过该字段 (向前兼容)
-            reader.skip_field(jce_type)?;
+        match schema.tag_lookup[tag as usize] {
+            Some(field_idx) if tags.contains(&tag) => {
+                let field = &schema.fields[field_idx];
+                let value =
+                    decode_schema_field(py, reader, field, jce_type, options, context, depth + 1)?;
+                let value = to_enum_member(py, field, value, options)?;
+                let value = from_logical(py, field, value, options)?;
+                let value = deserialize_hook(py, schema, field, value, context)?;
+                let value = apply_tag_hook(hooks.as_ref(), tag, value, context)?;
+                result_dict.set_item(field.py_name.bind(py), value)?;
+            }
+            _ => reader.skip_field(jce_type)?,
         }
     }
-    // 填充缺失的字段为默认值
+    if options & OPT_NO_DEFAULT_FILL != 0 {
+        return Ok(result_dict.into());
+    }
     for field in &schema.fields {
-        if !result_dict.contains(field.py_name.bind(py))? {
-            result_dict.set_item(field.py_name.bind(py), field.default_val.bind(py))?;
+        if tags.contains(&field.tag)
+            && !skip_backfill(py, field, options)
+            && !result_dict.contains(field.py_name.bind(py))?
+        {
+            result_dict.set_item(field.py_name.bind(py), fresh_default(py, field)?)?;
         }
     }
     Ok(result_dict.into())
@@ -842,23 +3610,21 @@ fn decode_field<'a, E: crate::codec::endian::Endianness>(
     reader: &mut JceReader<'a, E>,
     actual_type: JceType,
     expected_type: JceType,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
-    let is_compatible = match expected_type {
-        JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => matches!(
+    let widen_floats = options & OPT_WIDEN_FLOATS != 0;
+    if !expected_type.accepts_with(actual_type, widen_floats) && actual_type != JceType::StructEnd {
+        return decode_generic_field(
+            py,
+            reader,
             actual_type,
-            JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 | JceType::ZeroTag
-        ),
-        JceType::Float => actual_type == JceType::Float,
-        JceType::Double => actual_type == JceType::Double || actual_type == JceType::Float,
-        JceType::String1 | JceType::String4 => {
-            matches!(actual_type, JceType::String1 | JceType::String4)
-        }
-        _ => actual_type == expected_type,
-    };
-    if !is_compatible && actual_type != JceType::StructEnd {
-        return decode_generic_field(py, reader, actual_type, options, BytesMode::Auto, depth);
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        );
     }
     match expected_type {
         JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => Ok(reader
@@ -866,56 +3632,430 @@ fn decode_field<'a, E: crate::codec::endian::Endianness>(
             .into_pyobject(py)?
             .unbind()
             .into_any()),
-        JceType::Float => Ok(reader.read_float()?.into_pyobject(py)?.unbind().into_any()),
-        JceType::Double => Ok(reader.read_double()?.into_pyobject(py)?.unbind().into_any()),
-        JceType::String1 | JceType::String4 => Ok(reader
-            .read_string(actual_type)?
-            .into_pyobject(py)?
-            .unbind()
-            .into_any()),
-        JceType::Map => decode_map(py, reader, options, BytesMode::Auto, depth),
-        JceType::List => decode_list(py, reader, options, BytesMode::Auto, depth),
+        JceType::Float | JceType::Double => {
+            // 声明为 Double 时线上也可能是 Float, 按实际类型读取;
+            // `OPT_WIDEN_FLOATS` 下声明为 Float 的字段读取 Double 后截断为 f32
+            let v = if actual_type == JceType::Float {
+                reader.read_float()? as f64
+            } else if expected_type == JceType::Float {
+                reader.read_double()? as f32 as f64
+            } else {
+                reader.read_double()?
+            };
+            float_to_py(py, v, options)
+        }
+        JceType::String1 | JceType::String4 => match str_encoding(py) {
+            Some(encoding) => read_encoded_text(py, reader, actual_type, &encoding),
+            None => Ok(string_to_py(py, &reader.read_string(actual_type)?, options)),
+        },
+        JceType::Map => decode_map(py, reader, options, context, BytesMode::Auto, depth),
+        JceType::List => decode_list(py, reader, options, context, BytesMode::Auto, depth),
         JceType::SimpleList => {
-            let (_, t) = reader.read_head()?;
-            if t != JceType::Int1 {
-                reader.skip_field(JceType::SimpleList)?;
-                return Ok(py.None());
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem != JceType::Int1 {
+                return decode_int_simple_list(py, reader, elem, count);
             }
-            let size = reader.read_size()?;
-            Ok(PyBytes::new(py, reader.read_bytes(size as usize)?).into())
+            Ok(PyBytes::new(py, reader.read_bytes(count)?).into())
+        }
+        JceType::StructBegin => {
+            decode_generic_struct(py, reader, options, context, BytesMode::Auto, depth)
         }
-        JceType::StructBegin => decode_generic_struct(py, reader, options, BytesMode::Auto, depth),
         _ => Err(PyValueError::new_err("Unsupported type")),
     }
 }
 
+/// 将定宽整数 SimpleList (元素类型 Int2/Int4/Int8) 解码为 `list[int]`.
+fn decode_int_simple_list<E: Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'_, E>,
+    elem: JceType,
+    count: usize,
+) -> PyResult<Py<PyAny>> {
+    let values = reader.read_simple_list_ints(elem, count)?;
+    Ok(PyList::new(py, values)?.into_any().unbind())
+}
+
+/// 按 Schema 字段定义解码字段值.
+///
+/// 与 `encode_schema_field` 对称; 线上类型与声明不符时由各分支回退到通用解码.
+fn decode_schema_field<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    field: &FieldDef,
+    jce_type: JceType,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    if field.logical == Some(LogicalType::Ipv4) && jce_type == JceType::Int4 {
+        let bytes = reader.read_bytes(4)?;
+        let value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        return Ok(value.into_pyobject(py)?.into_any().unbind());
+    }
+    if let Some(codec) = &field.codec {
+        return decode_text_field(
+            py,
+            reader,
+            jce_type,
+            codec.bind(py),
+            options,
+            context,
+            depth,
+        );
+    }
+    if let Some(elem) = &field.elem
+        && jce_type == JceType::List
+    {
+        return decode_typed_list(py, reader, elem, options, context, depth);
+    }
+    if jce_type == JceType::Map && (field.key.is_some() || field.value.is_some()) {
+        return decode_typed_map(py, reader, field, options, context, depth);
+    }
+    if let Some(nested) = &field.nested
+        && field.tars_type == JceType::StructBegin as u8
+        && jce_type == JceType::StructBegin
+    {
+        let schema = nested.compiled(py)?;
+        return match field.little_endian {
+            Some(true) if !E::IS_LITTLE => decode_struct_with_endian::<E, LittleEndian>(
+                py, reader, schema, options, context, depth,
+            ),
+            Some(false) if E::IS_LITTLE => decode_struct_with_endian::<E, BigEndian>(
+                py, reader, schema, options, context, depth,
+            ),
+            _ => decode_struct_compiled(py, reader, schema, options, context, depth),
+        };
+    }
+    if field.tars_type == TYPE_CODE_BOOL {
+        return decode_bool_field(py, reader, jce_type, options, context, depth);
+    }
+    if field.tars_type == 255 {
+        return decode_generic_field(
+            py,
+            reader,
+            jce_type,
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        );
+    }
+    match JceType::try_from(field.tars_type) {
+        Ok(expected) => decode_field(py, reader, jce_type, expected, options, context, depth),
+        Err(_) => decode_generic_field(
+            py,
+            reader,
+            jce_type,
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        ),
+    }
+}
+
+/// 以字节序 `O` 解码嵌套结构体的字段, 完成后将 `reader` 移动到该结构体之后.
+///
+/// 用于外层与嵌套结构体字节序不同的混合协议; 错误中的偏移量相对于嵌套结构体的起始位置.
+fn decode_struct_with_endian<'a, E: Endianness, O: Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    schema: &CompiledSchema,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    let start = reader.position();
+    let mut inner = JceReader::<O>::new(reader.remaining_bytes()).with_max_depth(max_depth());
+    let options = (options & !1) | O::IS_LITTLE as i64;
+    let value = decode_struct_compiled(py, &mut inner, schema, options, context, depth)?;
+    reader.set_position(start + inner.position())?;
+    Ok(value)
+}
+
+/// 解码布尔字段: 整数按非零判断为 `True`/`False`, 其余线上类型回退到通用解码.
+fn decode_bool_field<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    jce_type: JceType,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    match jce_type {
+        JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 | JceType::ZeroTag => {
+            let value = reader.read_int(jce_type)? != 0;
+            Ok(PyBool::new(py, value).to_owned().into_any().unbind())
+        }
+        _ => decode_generic_field(
+            py,
+            reader,
+            jce_type,
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        ),
+    }
+}
+
+/// 按键/值类型描述解码 Map.
+///
+/// 值为 `StructBegin` 时按声明的 Schema 解析; 键会被强制转换为声明的类型
+/// (如线上为 Int 而声明为 str 时转换为 str).
+fn decode_typed_map<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    field: &FieldDef,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    let offset = reader.position() as usize;
+    let size = reader.read_container_size()?;
+    count_elements(size, offset)?;
+    let map = new_map(py)?;
+    for _ in 0..size {
+        let (_, ktype) = reader.read_head()?;
+        let key = match &field.key {
+            Some(desc) => {
+                let key = decode_desc_value(py, reader, ktype, desc, options, context, depth + 1)?;
+                coerce_key(py, key.into_bound(py), desc)?
+            }
+            None => decode_generic_field(
+                py,
+                reader,
+                ktype,
+                options,
+                context,
+                BytesMode::Auto,
+                depth + 1,
+            )?
+            .into_bound(py),
+        };
+        let (_, vtype) = reader.read_head()?;
+        let value = match &field.value {
+            Some(desc) => decode_desc_value(py, reader, vtype, desc, options, context, depth + 1)?,
+            None => decode_generic_field(
+                py,
+                reader,
+                vtype,
+                options,
+                context,
+                BytesMode::Auto,
+                depth + 1,
+            )?,
+        };
+        map_set_item(&map, key, value)?;
+    }
+    Ok(map.unbind())
+}
+
+/// 将 Map 键转换为声明的基础类型 (str / int).
+fn coerce_key<'py>(
+    py: Python<'py>,
+    key: Bound<'py, PyAny>,
+    desc: &TypeDesc,
+) -> PyResult<Bound<'py, PyAny>> {
+    let TypeDesc::Code(code) = desc else {
+        return Ok(key);
+    };
+    match JceType::try_from(*code) {
+        Ok(JceType::String1 | JceType::String4) if !key.is_instance_of::<PyString>() => {
+            Ok(key.str()?.into_any())
+        }
+        Ok(JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8)
+            if key.is_instance_of::<PyString>() =>
+        {
+            py.get_type::<pyo3::types::PyInt>().call1((key,))
+        }
+        _ => Ok(key),
+    }
+}
+
+/// 按元素类型描述解码 List.
+///
+/// `StructBegin` 元素按描述中的 Schema 解码为字段名字典, 而非通用 Tag 字典.
+/// `OPT_STRICT_LIST` 下元素的线上类型必须与描述兼容, 否则报告首个不符元素的索引与偏移量.
+fn decode_typed_list<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    elem: &TypeDesc,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    let offset = reader.position() as usize;
+    let size = reader.read_container_size()?;
+    count_elements(size, offset)?;
+    let list = PyList::empty(py);
+    for index in 0..size {
+        let offset = reader.position() as usize;
+        let (_, t) = reader.read_head()?;
+        if options & OPT_STRICT_LIST != 0 {
+            check_list_element(elem, t, index, offset, options)?;
+        }
+        list.append(decode_desc_value(
+            py,
+            reader,
+            t,
+            elem,
+            options,
+            context,
+            depth + 1,
+        )?)?;
+    }
+    Ok(list.into())
+}
+
+/// 检查 List 元素的线上类型能否按声明的元素类型解码.
+fn check_list_element(
+    elem: &TypeDesc,
+    actual: JceType,
+    index: usize,
+    offset: usize,
+    options: i64,
+) -> PyResult<()> {
+    let expected = match elem {
+        TypeDesc::Code(TYPE_CODE_BOOL) => JceType::Int1,
+        TypeDesc::Code(code) => match JceType::try_from(*code) {
+            Ok(expected) => expected,
+            // 运行时推断的元素类型接受任何线上类型
+            Err(_) => return Ok(()),
+        },
+        TypeDesc::Struct(_) => JceType::StructBegin,
+    };
+    if expected.accepts_with(actual, options & OPT_WIDEN_FLOATS != 0) {
+        return Ok(());
+    }
+    Err(CodecError::new(
+        offset,
+        format!(
+            "List element {} has wire type {:?}, expected {:?}",
+            index, actual, expected
+        ),
+    )
+    .into())
+}
+
+/// 按类型描述解码单个值.
+///
+/// 线上类型与描述不符时回退到通用解码.
+fn decode_desc_value<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    actual_type: JceType,
+    desc: &TypeDesc,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    match desc {
+        TypeDesc::Code(TYPE_CODE_BOOL) => {
+            decode_bool_field(py, reader, actual_type, options, context, depth)
+        }
+        TypeDesc::Code(code) => match JceType::try_from(*code) {
+            Ok(expected) => {
+                decode_field(py, reader, actual_type, expected, options, context, depth)
+            }
+            Err(_) => decode_generic_field(
+                py,
+                reader,
+                actual_type,
+                options,
+                context,
+                BytesMode::Auto,
+                depth,
+            ),
+        },
+        TypeDesc::Struct(nested) if actual_type == JceType::StructBegin => decode_struct_compiled(
+            py,
+            reader,
+            nested.compiled(py)?,
+            options,
+            context,
+            depth + 1,
+        ),
+        TypeDesc::Struct(_) => decode_generic_field(
+            py,
+            reader,
+            actual_type,
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        ),
+    }
+}
+
+/// 按指定编码将 SimpleList 解码为 `str`.
+///
+/// 不做 UTF-8 或嵌套结构探测. 线上类型不是 SimpleList 时回退到通用解码.
+fn decode_text_field<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    actual_type: JceType,
+    codec: &Bound<'_, PyString>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<Py<PyAny>> {
+    if actual_type != JceType::SimpleList {
+        return decode_generic_field(
+            py,
+            reader,
+            actual_type,
+            options,
+            context,
+            BytesMode::Auto,
+            depth,
+        );
+    }
+    let (elem, count) = reader.read_simple_list_header()?;
+    if elem != JceType::Int1 {
+        return Err(PyValueError::new_err(format!(
+            "SimpleList must contain Byte (0), got {:?}",
+            elem
+        )));
+    }
+    let bytes = PyBytes::new(py, reader.read_bytes(count)?);
+    Ok(bytes.call_method1("decode", (codec,))?.unbind())
+}
+
 fn decode_map<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     bytes_mode: BytesMode,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
-    let size = reader.read_size()?;
-    let dict = PyDict::new(py);
+    let offset = reader.position() as usize;
+    let size = reader.read_container_size()?;
+    count_elements(size, offset)?;
+    let map = new_map(py)?;
     for _ in 0..size {
         let (_, ktype) = reader.read_head()?;
-        let key = decode_generic_field(py, reader, ktype, options, bytes_mode, depth + 1)?;
+        let key = decode_generic_field(py, reader, ktype, options, context, bytes_mode, depth + 1)?;
         let (_, vtype) = reader.read_head()?;
-        let value = decode_generic_field(py, reader, vtype, options, bytes_mode, depth + 1)?;
-        dict.set_item(key, value)?;
+        let value =
+            decode_generic_field(py, reader, vtype, options, context, bytes_mode, depth + 1)?;
+        map_set_item(&map, key, value)?;
     }
-    Ok(dict.into())
+    Ok(map.unbind())
 }
 
 fn decode_list<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     bytes_mode: BytesMode,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
-    let size = reader.read_size()?;
+    let offset = reader.position() as usize;
+    let size = reader.read_container_size()?;
+    count_elements(size, offset)?;
     let list = PyList::empty(py);
     for _ in 0..size {
         let (_, t) = reader.read_head()?;
@@ -924,74 +4064,194 @@ fn decode_list<'a, E: crate::codec::endian::Endianness>(
             reader,
             t,
             options,
+            context,
             bytes_mode,
             depth + 1,
         )?)?;
     }
+    // `OPT_LIST_AS_TUPLE` 下返回不可变的 tuple, 便于作为字典键或缓存
+    if options & OPT_LIST_AS_TUPLE != 0 {
+        return Ok(list.to_tuple().into_any().unbind());
+    }
     Ok(list.into())
 }
 
 /// 解码通用结构体 (bytes -> dict).
 ///
 /// 在没有 Schema 的情况下，将 JCE 数据流解析为 Tag -> Value 的字典.
-/// 递归解析嵌套结构.
+/// 递归解析嵌套结构. `OPT_ORDERED_PAIRS` 下返回按线上顺序排列的 `(tag, value)` 列表,
+/// 重复的 Tag 原样保留.
 pub(crate) fn decode_generic_struct<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     bytes_mode: BytesMode,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
-    if depth > MAX_DEPTH {
+    let mut failure = None;
+    let (value, result) = decode_generic_struct_partial(
+        py,
+        reader,
+        options,
+        context,
+        bytes_mode,
+        depth,
+        &mut failure,
+    );
+    result.map(|_| value)
+}
+
+/// 通用解码结构体, 出错时仍返回出错前已解码的字段.
+///
+/// 返回已解码字段构成的容器与解码结果. 出错时 `failure` 记录出错字段头部的偏移量
+/// 与 Tag (读取头部本身失败时 Tag 为 None).
+fn decode_generic_struct_partial<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    bytes_mode: BytesMode,
+    depth: usize,
+    failure: &mut Option<(usize, Option<u8>)>,
+) -> (Py<PyAny>, PyResult<()>) {
+    let pairs = (options & OPT_ORDERED_PAIRS != 0).then(|| PyList::empty(py));
+    let dict = PyDict::new(py);
+    let mut current = (reader.position() as usize, None);
+    let result = decode_generic_fields(
+        py,
+        reader,
+        options,
+        context,
+        bytes_mode,
+        depth,
+        &dict,
+        pairs.as_ref(),
+        &mut current,
+    );
+    if result.is_err() {
+        *failure = Some(current);
+    }
+    match pairs {
+        Some(pairs) => (pairs.into_any().unbind(), result),
+        None => (dict.into_any().unbind(), result),
+    }
+}
+
+/// 逐个解码结构体字段并写入 `dict` (或 `pairs`), `current` 跟踪正在解码字段的偏移量与 Tag.
+#[allow(clippy::too_many_arguments)]
+fn decode_generic_fields<'a, E: crate::codec::endian::Endianness>(
+    py: Python<'_>,
+    reader: &mut JceReader<'a, E>,
+    options: i64,
+    context: &Bound<'_, PyAny>,
+    bytes_mode: BytesMode,
+    depth: usize,
+    dict: &Bound<'_, PyDict>,
+    pairs: Option<&Bound<'_, PyList>>,
+    current: &mut (usize, Option<u8>),
+) -> PyResult<()> {
+    if depth > max_depth() {
         return Err(PyValueError::new_err("Depth exceeded"));
     }
-    let dict = PyDict::new(py);
+    let hooks = tag_hooks(py);
+    let max_fields = max_fields();
+    let mut fields = 0usize;
+    // OPT_DUP_TAG_LIST 下已收集为列表的 Tag
+    let mut collected = [false; 256];
     while !reader.is_end() {
+        let offset = reader.position() as usize;
+        *current = (offset, None);
         let (tag, jce_type) = reader.read_head()?;
         if jce_type == JceType::StructEnd {
+            check_struct_end(options, depth, offset)?;
             break;
         }
-        dict.set_item(
-            tag,
-            decode_generic_field(py, reader, jce_type, options, bytes_mode, depth + 1)?,
+        current.1 = Some(tag);
+        count_struct_field(&mut fields, max_fields, offset)?;
+        let value = decode_generic_field(
+            py,
+            reader,
+            jce_type,
+            options,
+            context,
+            bytes_mode,
+            depth + 1,
         )?;
+        let value = apply_tag_hook(hooks.as_ref(), tag, value, context)?;
+        if let Some(pairs) = pairs {
+            pairs.append((tag, value))?;
+            continue;
+        }
+        // 同一层出现重复 Tag 时默认后者覆盖前者
+        if options & (OPT_DUP_TAG_ERROR | OPT_DUP_TAG_LIST) != 0
+            && let Some(prev) = dict.get_item(tag)?
+        {
+            if options & OPT_DUP_TAG_ERROR != 0 {
+                return Err(CodecError::new(offset, format!("Duplicate tag {}", tag)).into());
+            }
+            if collected[tag as usize] {
+                prev.cast::<PyList>()?.append(value)?;
+            } else {
+                collected[tag as usize] = true;
+                dict.set_item(tag, PyList::new(py, [prev.unbind(), value])?)?;
+            }
+            continue;
+        }
+        dict.set_item(tag, value)?;
     }
-    Ok(dict.into())
+    Ok(())
 }
 
 fn decode_generic_field<'a, E: crate::codec::endian::Endianness>(
     py: Python<'_>,
     reader: &mut JceReader<'a, E>,
     jce_type: JceType,
-    options: i32,
+    options: i64,
+    context: &Bound<'_, PyAny>,
     bytes_mode: BytesMode,
     depth: usize,
 ) -> PyResult<Py<PyAny>> {
     match jce_type {
+        // `OPT_KEEP_INT_WIDTH` 下保留线上宽度, 重新编码时写回相同的类型 (值为 0 的 Int1 也不会变为 ZeroTag)
+        JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8
+            if options & OPT_KEEP_INT_WIDTH != 0 =>
+        {
+            fixed_to_py(py, reader.read_int(jce_type)?, jce_type)
+        }
         JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => Ok(reader
             .read_int(jce_type)?
             .into_pyobject(py)?
             .unbind()
             .into_any()),
-        JceType::Float => Ok(reader.read_float()?.into_pyobject(py)?.unbind().into_any()),
-        JceType::Double => Ok(reader.read_double()?.into_pyobject(py)?.unbind().into_any()),
-        JceType::String1 | JceType::String4 => Ok(reader
-            .read_string(jce_type)?
-            .into_pyobject(py)?
-            .unbind()
-            .into_any()),
-        JceType::Map => decode_map(py, reader, options, bytes_mode, depth),
-        JceType::List => decode_list(py, reader, options, bytes_mode, depth),
+        JceType::Float => float_to_py(py, reader.read_float()? as f64, options),
+        JceType::Double => float_to_py(py, reader.read_double()?, options),
+        JceType::String1 | JceType::String4 => {
+            if let Some(encoding) = str_encoding(py) {
+                return read_encoded_text(py, reader, jce_type, &encoding);
+            }
+            let s = reader.read_string(jce_type)?;
+            if options & OPT_STR_VIEW != 0
+                && let Some(view) = view_slice(py, s.as_bytes())?
+            {
+                return Ok(Bound::new(py, JceStr::new(view))?.into_any().unbind());
+            }
+            Ok(string_to_py(py, &s, options))
+        }
+        JceType::Map => decode_map(py, reader, options, context, bytes_mode, depth),
+        JceType::List => decode_list(py, reader, options, context, bytes_mode, depth),
         JceType::SimpleList => {
-            let (_, t) = reader.read_head()?;
-            if t != JceType::Int1 {
-                reader.skip_field(JceType::SimpleList)?;
-                return Ok(py.None());
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem != JceType::Int1 {
+                return decode_int_simple_list(py, reader, elem, count);
             }
-            let size = reader.read_size()?;
-            let bytes = reader.read_bytes(size as usize)?;
+            let bytes = reader.read_bytes(count)?;
             match bytes_mode {
                 BytesMode::Raw => Ok(PyBytes::new(py, bytes).into()),
+                BytesMode::View => match view_slice(py, bytes)? {
+                    Some(view) => Ok(view),
+                    None => Ok(PyBytes::new(py, bytes).into()),
+                },
                 BytesMode::String => {
                     if let Ok(s) = std::str::from_utf8(bytes) {
                         Ok(s.into_pyobject(py)?.unbind().into_any())
@@ -1007,17 +4267,23 @@ fn decode_generic_field<'a, E: crate::codec::endian::Endianness>(
                             .into_any())
                     } else {
                         // Optimization: Use JceScanner for zero-allocation probing
-                        let mut scanner = crate::codec::scanner::JceScanner::<E>::new(bytes);
+                        let mut scanner = JceScanner::<E>::new(bytes).with_max_depth(max_depth());
                         if scanner.validate_struct().is_ok() && scanner.is_end() {
-                            let mut probe = JceReader::<E>::new(bytes);
-                            if let Ok(obj) = decode_generic_struct(
+                            let mut probe = JceReader::<E>::new(bytes).with_max_depth(max_depth());
+                            // 试探失败时撤销其登记的元素, 回退的 bytes 不计入嵌套内容
+                            let total = element_total();
+                            CALLBACK_FAILED.with(|flag| flag.set(false));
+                            match decode_generic_struct(
                                 py,
                                 &mut probe,
                                 options,
+                                context,
                                 BytesMode::Auto,
                                 depth + 1,
                             ) {
-                                return Ok(obj);
+                                Ok(obj) => return Ok(obj),
+                                Err(err) if CALLBACK_FAILED.with(Cell::get) => return Err(err),
+                                Err(_) => restore_element_total(total),
                             }
                         }
                         Ok(PyBytes::new(py, bytes).into())
@@ -1025,7 +4291,9 @@ fn decode_generic_field<'a, E: crate::codec::endian::Endianness>(
                 }
             }
         }
-        JceType::StructBegin => decode_generic_struct(py, reader, options, bytes_mode, depth),
+        JceType::StructBegin => {
+            decode_generic_struct(py, reader, options, context, bytes_mode, depth)
+        }
         JceType::ZeroTag => Ok(0i64.into_pyobject(py)?.unbind().into_any()),
         JceType::StructEnd => Ok(py.None()),
     }
@@ -0,0 +1,111 @@
+use crate::bindings::serde::InputData;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBytes, PyString};
+
+/// 指向输入缓冲区的字符串视图.
+///
+/// 由 `OPT_STR_VIEW` 下的通用解码返回, 持有输入数据的 memoryview 切片而不拷贝内容,
+/// 仅在调用 `str()` 时才创建 Python 字符串. 视图存活期间输入对象不会被释放;
+/// 输入为可变缓冲区时, 对输入的修改会反映到视图中, 此时视图不可哈希.
+#[pyclass(frozen, module = "tarsio._core")]
+pub struct JceStr {
+    view: Py<PyAny>,
+    hash: PyOnceLock<isize>,
+}
+
+impl JceStr {
+    /// 包装一个指向 UTF-8 字符串内容的 memoryview 切片.
+    pub(crate) fn new(view: Py<PyAny>) -> Self {
+        Self {
+            view,
+            hash: PyOnceLock::new(),
+        }
+    }
+
+    /// 以字节切片访问视图内容.
+    fn with_bytes<R>(&self, py: Python<'_>, f: impl FnOnce(&[u8]) -> R) -> PyResult<R> {
+        let input = InputData::new(self.view.bind(py))?;
+        Ok(f(input.as_bytes()))
+    }
+
+    /// 将视图内容解码为 Rust 字符串.
+    ///
+    /// 输入缓冲区被修改为非法 UTF-8 时抛出 `ValueError`.
+    pub(crate) fn to_text(&self, py: Python<'_>) -> PyResult<String> {
+        self.with_bytes(py, |bytes| {
+            std::str::from_utf8(bytes)
+                .map(str::to_owned)
+                .map_err(|e| PyValueError::new_err(format!("Invalid UTF-8 string: {}", e)))
+        })?
+    }
+}
+
+#[pymethods]
+impl JceStr {
+    /// 指向输入数据的 memoryview 切片.
+    #[getter]
+    fn view(&self, py: Python<'_>) -> Py<PyAny> {
+        self.view.clone_ref(py)
+    }
+
+    fn __str__(&self, py: Python<'_>) -> PyResult<String> {
+        self.to_text(py)
+    }
+
+    fn __bytes__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        self.with_bytes(py, |bytes| PyBytes::new(py, bytes))
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let text = PyString::new(py, &self.to_text(py)?);
+        Ok(format!("JceStr({})", text.repr()?))
+    }
+
+    /// 与同内容的 `str` 哈希值一致, 因此可以直接用于查找以 `str` 为键的字典.
+    ///
+    /// 哈希值在首次计算后缓存. 指向可变缓冲区的视图内容可能改变, 因此不可哈希.
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        self.hash
+            .get_or_try_init(py, || {
+                if !self
+                    .view
+                    .bind(py)
+                    .getattr(intern!(py, "readonly"))?
+                    .is_truthy()?
+                {
+                    return Err(PyTypeError::new_err(
+                        "unhashable type: 'JceStr' (view of a mutable buffer)",
+                    ));
+                }
+                PyString::new(py, &self.to_text(py)?).hash()
+            })
+            .copied()
+    }
+
+    fn __richcmp__(
+        &self,
+        py: Python<'_>,
+        other: &Bound<'_, PyAny>,
+        op: CompareOp,
+    ) -> PyResult<Py<PyAny>> {
+        // 合法 UTF-8 的字节相等即字符串相等, 无需解码
+        let equal = if let Ok(s) = other.cast::<PyString>() {
+            let other_bytes = s.to_str()?.as_bytes();
+            self.with_bytes(py, |bytes| bytes == other_bytes)?
+        } else if let Ok(s) = other.cast::<JceStr>() {
+            let other_input = InputData::new(s.get().view.bind(py))?;
+            self.with_bytes(py, |bytes| bytes == other_input.as_bytes())?
+        } else {
+            return Ok(py.NotImplemented());
+        };
+        match op {
+            CompareOp::Eq => Ok(equal.into_pyobject(py)?.to_owned().into_any().unbind()),
+            CompareOp::Ne => Ok((!equal).into_pyobject(py)?.to_owned().into_any().unbind()),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use crate::bindings::serde::InputData;
 use crate::bindings::serde::{
-    BytesMode, decode_generic_struct, decode_struct, encode_generic_field, encode_generic_struct,
-    encode_struct,
+    BytesMode, DecodeLimitGuard, DepthLimitGuard, MapFactoryGuard, StrEncodingGuard, TagHooksGuard,
+    TextPolicyGuard, check_length_tag, decode_generic_struct, decode_struct, encode_generic_field,
+    encode_generic_struct, encode_struct, schema_method, string_writer_config, text_control_mask,
 };
+use crate::codec::consts::MAX_DEPTH;
 use crate::codec::endian::Endianness;
-use crate::codec::framing::JceFramer;
+use crate::codec::framing::{FrameError, JceFramer};
 use crate::codec::reader::JceReader;
-use crate::codec::writer::JceWriter;
+use crate::codec::writer::{JceWriter, WriterConfig};
 use byteorder::{BigEndian, LittleEndian};
 use bytes::{BufMut, BytesMut};
 use pyo3::prelude::*;
@@ -18,18 +21,24 @@ use pyo3::types::{PyBytes, PyDict, PyList};
 pub struct LengthPrefixedReader {
     buffer: BytesMut,
     framer: JceFramer,
-    options: i32,
+    options: i64,
     bytes_mode: BytesMode,
     target_schema: Option<Py<PyList>>,
     target_cls: Option<Py<PyAny>>,
     context: Option<Py<PyAny>>,
     max_buffer_size: usize,
+    skip_bad_frames: bool,
+    max_fields: Option<usize>,
+    max_total_elements: Option<usize>,
+    text_control_mask: u128,
+    verify_length_tag: Option<u8>,
+    max_depth: Option<usize>,
 }
 
 #[pymethods]
 impl LengthPrefixedReader {
     #[new]
-    #[pyo3(signature = (target, option=0, max_buffer_size=10485760, context=None, length_type=4, inclusive_length=true, little_endian_length=false, bytes_mode=2))]
+    #[pyo3(signature = (target, option=0, max_buffer_size=10485760, context=None, length_type=4, inclusive_length=true, little_endian_length=false, bytes_mode=2, skip_bad_frames=false, max_fields=None, max_total_elements=None, allowed_control_bytes=None, verify_length_tag=None, max_depth=None))]
     #[allow(clippy::too_many_arguments)]
     /// 创建一个新的 LengthPrefixedReader.
     ///
@@ -42,16 +51,31 @@ impl LengthPrefixedReader {
     ///     inclusive_length (bool): 长度是否包含头部本身.
     ///     little_endian_length (bool): 长度头是否为小端序.
     ///     bytes_mode (int): 字节处理模式 (0=Raw, 1=String, 2=Auto).
+    ///     skip_bad_frames (bool): 数据体解码失败时抛出携带原始数据的 `JceFrameDecodeError`.
+    ///     max_fields (int | None): 通用解码时单个结构体允许的最大字段数, 默认不限制.
+    ///     max_total_elements (int | None): 通用解码时每个数据包中结构体字段,
+    ///         List 元素与 Map 键值对的最大总数, 默认不限制.
+    ///     allowed_control_bytes (bytes | None): Auto 模式下文本中允许出现的 ASCII 控制字符,
+    ///         默认为 `b"\t\n\r"`.
+    ///     verify_length_tag (int | None): 声明数据体总长度的字段 Tag,
+    ///         指定时校验其值与数据体实际解码的字节数一致.
+    ///     max_depth (int | None): 最大嵌套深度, 默认为 100.
     fn new(
         _py: Python<'_>,
         target: &Bound<'_, PyAny>,
-        option: i32,
+        option: i64,
         max_buffer_size: usize,
         context: Option<Py<PyAny>>,
         length_type: u8,
         inclusive_length: bool,
         little_endian_length: bool,
         bytes_mode: u8,
+        skip_bad_frames: bool,
+        max_fields: Option<usize>,
+        max_total_elements: Option<usize>,
+        allowed_control_bytes: Option<&[u8]>,
+        verify_length_tag: Option<u8>,
+        max_depth: Option<usize>,
     ) -> PyResult<Self> {
         if ![1, 2, 4].contains(&length_type) {
             return Err(pyo3::exceptions::PyValueError::new_err(
@@ -62,7 +86,7 @@ impl LengthPrefixedReader {
         let mut target_schema = None;
         let target_cls = Some(target.clone().unbind());
 
-        if let Ok(schema_method) = target.getattr("__get_core_schema__")
+        if let Some(schema_method) = schema_method(target)
             && let Ok(schema) = schema_method.call0()
             && let Ok(schema) = schema.cast::<PyList>()
         {
@@ -83,18 +107,25 @@ impl LengthPrefixedReader {
             target_cls,
             context,
             max_buffer_size,
+            skip_bad_frames,
+            max_fields,
+            max_total_elements,
+            text_control_mask: text_control_mask(allowed_control_bytes)?,
+            verify_length_tag,
+            max_depth,
         })
     }
 
     /// 向缓冲区追加数据.
     ///
     /// Args:
-    ///     data (bytes): 要追加的二进制数据.
+    ///     data (bytes | bytearray | memoryview): 要追加的二进制数据.
     ///
     /// Raises:
     ///     BufferError: 如果缓冲区超过最大大小.
-    fn feed(&mut self, data: &Bound<'_, PyBytes>) -> PyResult<()> {
-        let data = data.as_bytes();
+    fn feed(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        let input = InputData::new(data)?;
+        let data = input.as_bytes();
         if self.buffer.len() + data.len() > self.max_buffer_size {
             return Err(pyo3::exceptions::PyBufferError::new_err(
                 "Reader buffer exceeded max size",
@@ -104,6 +135,41 @@ impl LengthPrefixedReader {
         Ok(())
     }
 
+    /// 一次追加多个数据块.
+    ///
+    /// 按总大小一次性检查 `max_buffer_size` 并预留空间, 适合积攒了大量小块数据的场景.
+    /// 超出限制时不追加任何数据.
+    ///
+    /// Args:
+    ///     chunks (list[bytes | bytearray | memoryview]): 要追加的数据块.
+    ///
+    /// Raises:
+    ///     BufferError: 如果缓冲区超过最大大小.
+    fn feed_many(&mut self, chunks: Vec<Bound<'_, PyAny>>) -> PyResult<()> {
+        let inputs = chunks
+            .iter()
+            .map(InputData::new)
+            .collect::<PyResult<Vec<_>>>()?;
+        let total: usize = inputs.iter().map(|c| c.as_bytes().len()).sum();
+        if self.buffer.len() + total > self.max_buffer_size {
+            return Err(pyo3::exceptions::PyBufferError::new_err(
+                "Reader buffer exceeded max size",
+            ));
+        }
+        self.buffer.reserve(total);
+        for input in &inputs {
+            self.buffer.extend_from_slice(input.as_bytes());
+        }
+        Ok(())
+    }
+
+    /// 压缩缓冲区.
+    ///
+    /// 将尚未取出的数据复制到大小恰好的新缓冲区, 释放已取出数据包占用的空间.
+    fn compact(&mut self) {
+        self.buffer = BytesMut::from(&self.buffer[..]);
+    }
+
     fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
     }
@@ -115,30 +181,82 @@ impl LengthPrefixedReader {
     ///
     /// Raises:
     ///     ValueError: 如果数据包格式错误.
+    ///     DecodeError: `verify_length_tag` 字段缺失或与数据体长度不符.
+    ///     JceFrameDecodeError: 启用 `skip_bad_frames` 且数据体解码失败.
+    ///         该数据包已被移除, 可以继续读取.
     fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Py<PyAny>>> {
-        let framer = slf.framer;
-        match framer.check_frame(&slf.buffer) {
-            Ok(Some(packet_size)) => {
-                let header_len = framer.length_type as usize;
-                let packet = slf.buffer.split_to(packet_size);
-                let body_data = &packet[header_len..];
-                let py = slf.py();
-
-                if slf.options & 1 == 0 {
-                    let mut reader = JceReader::<BigEndian>::new(body_data);
-                    Self::decode_packet(py, &mut slf, &mut reader)
-                } else {
-                    let mut reader = JceReader::<LittleEndian>::new(body_data);
-                    Self::decode_packet(py, &mut slf, &mut reader)
-                }
+        // 数据包在解码前即从缓冲区移除, 解码失败不会阻塞后续数据包
+        let Some(packet) = slf.take_frame()? else {
+            return Ok(None);
+        };
+        let body_data = &packet[slf.framer.length_type as usize..];
+        let py = slf.py();
+        let _factory = MapFactoryGuard::new(None);
+        let _hooks = TagHooksGuard::new(None);
+        let _encoding = StrEncodingGuard::new(py, None)?;
+        let _limit = DepthLimitGuard::new(slf.max_depth);
+        let _limits = DecodeLimitGuard::new(slf.max_fields, slf.max_total_elements);
+        let _text = TextPolicyGuard::new(slf.text_control_mask);
+        let limit = slf.max_depth.unwrap_or(MAX_DEPTH);
+
+        let result = if slf.options & 1 == 0 {
+            let mut reader = JceReader::<BigEndian>::new(body_data).with_max_depth(limit);
+            Self::decode_packet(py, &mut slf, &mut reader).map(|r| (r, reader.position()))
+        } else {
+            let mut reader = JceReader::<LittleEndian>::new(body_data).with_max_depth(limit);
+            Self::decode_packet(py, &mut slf, &mut reader).map(|r| (r, reader.position()))
+        };
+        // 校验数据体内声明的总长度与实际解码的字节数一致
+        let result = result.and_then(|(obj, consumed)| {
+            if let Some(tag) = slf.verify_length_tag {
+                let body = &body_data[..consumed as usize];
+                check_length_tag(body, tag, slf.options & 1 != 0)?;
             }
-            Ok(None) => Ok(None),
-            Err(e) => Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "JCE frame error: {e}"
-            ))),
+            Ok(obj)
+        });
+        match result {
+            Err(err) if slf.skip_bad_frames => Err(frame_decode_error(py, err, body_data)),
+            result => result,
         }
     }
 
+    /// 获取下一个完整数据包的原始数据体, 不进行解码.
+    ///
+    /// 数据包同样会从缓冲区中移除, 适用于只需转发数据的代理场景.
+    ///
+    /// Returns:
+    ///     bytes | None: 去除长度前缀后的数据体, 或者 None (如果数据不足).
+    ///
+    /// Raises:
+    ///     ValueError: 如果数据包格式错误.
+    fn next_raw(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyBytes>>> {
+        let header_len = self.framer.length_type as usize;
+        Ok(self
+            .take_frame()?
+            .map(|packet| PyBytes::new(py, &packet[header_len..]).unbind()))
+    }
+
+    /// 预览缓冲区开头下一个完整数据包的总长度 (含长度前缀), 不取出任何数据.
+    ///
+    /// 与 `__next__` 使用相同的分帧规则, 可用于在解码前按数据包大小做限流等决策.
+    ///
+    /// Returns:
+    ///     int | None: 数据包的总字节数, 或者 None (如果数据不足).
+    ///
+    /// Raises:
+    ///     ValueError: 如果数据包格式错误.
+    fn peek_frame_length(&self, py: Python<'_>) -> PyResult<Option<usize>> {
+        self.framer
+            .check_frame(&self.buffer)
+            .map_err(|e| frame_error(py, &e, None))
+    }
+
+    /// 缓冲区中尚未取出的字节数.
+    #[getter]
+    fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
     /// 清空缓冲区.
     fn clear(&mut self) {
         self.buffer.clear();
@@ -146,6 +264,15 @@ impl LengthPrefixedReader {
 }
 
 impl LengthPrefixedReader {
+    /// 从缓冲区取出下一个完整数据包 (含长度前缀), 数据不足时返回 None.
+    fn take_frame(&mut self) -> PyResult<Option<BytesMut>> {
+        match self.framer.check_frame(&self.buffer) {
+            Ok(Some(packet_size)) => Ok(Some(self.buffer.split_to(packet_size))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(Python::attach(|py| frame_error(py, &e, None))),
+        }
+    }
+
     /// 解码单个数据包.
     ///
     /// 内部使用，处理粘包后的完整数据体.
@@ -155,8 +282,12 @@ impl LengthPrefixedReader {
         slf: &mut LengthPrefixedReader,
         reader: &mut JceReader<E>,
     ) -> PyResult<Option<Py<PyAny>>> {
+        let context = match &slf.context {
+            Some(ctx) => ctx.bind(py).clone(),
+            None => PyDict::new(py).into_any(),
+        };
         if let Some(schema) = &slf.target_schema {
-            let dict = decode_struct(py, reader, schema.bind(py), slf.options, 0)?;
+            let dict = decode_struct(py, reader, schema.bind(py), slf.options, &context, 0)?;
             let kwargs = PyDict::new(py);
             if let Some(ctx) = &slf.context {
                 kwargs.set_item("context", ctx.bind(py))?;
@@ -171,7 +302,7 @@ impl LengthPrefixedReader {
             return Ok(Some(dict));
         }
 
-        let result = decode_generic_struct(py, reader, slf.options, slf.bytes_mode, 0);
+        let result = decode_generic_struct(py, reader, slf.options, &context, slf.bytes_mode, 0);
         match result {
             Ok(obj) => {
                 if let Some(target_cls) = &slf.target_cls {
@@ -185,20 +316,197 @@ impl LengthPrefixedReader {
     }
 }
 
+/// 将分帧错误转换为对应的 Python 异常 (`FrameTooLarge` 或 `InvalidLength`, 均为 `ValueError` 子类).
+///
+/// `index` 为出错数据包在输入中的序号 (从 0 开始), 仅批量切分时提供.
+fn frame_error(py: Python<'_>, err: &FrameError, index: Option<usize>) -> PyErr {
+    let msg = match index {
+        Some(index) => format!("JCE frame error: {err} (frame {index})"),
+        None => format!("JCE frame error: {err}"),
+    };
+    let (name, length, limit) = match *err {
+        FrameError::InvalidLength(length, header_len) => ("InvalidLength", length, header_len),
+        FrameError::FrameTooLarge(length, limit) => ("FrameTooLarge", length, limit),
+    };
+    let typed = py
+        .import("tarsio.exceptions")
+        .and_then(|m| m.getattr(name))
+        .and_then(|cls| cls.call1((msg.clone(), length, limit, index)));
+    match typed {
+        Ok(obj) => PyErr::from_value(obj),
+        Err(_) => pyo3::exceptions::PyValueError::new_err(msg),
+    }
+}
+
+/// 长度前缀分帧器, 只识别数据包边界而不解码数据体.
+///
+/// 分帧规则与 `LengthPrefixedReader` 一致, 可用于将拼接的抓包数据切分为独立的数据包.
+#[pyclass(frozen, name = "JceFramer", module = "tarsio._core")]
+pub struct PyJceFramer {
+    framer: JceFramer,
+}
+
+#[pymethods]
+impl PyJceFramer {
+    #[new]
+    #[pyo3(signature = (length_type=4, inclusive_length=true, little_endian_length=false, max_buffer_size=10485760))]
+    /// 创建分帧器.
+    ///
+    /// Args:
+    ///     length_type (int): 长度头字节数 (1, 2, 4).
+    ///     inclusive_length (bool): 长度是否包含头部本身.
+    ///     little_endian_length (bool): 长度头是否为小端序.
+    ///     max_buffer_size (int): 允许的最大数据包大小 (含长度头, 默认 10MB).
+    ///
+    /// Raises:
+    ///     ValueError: `length_type` 不是 1, 2 或 4.
+    fn new(
+        length_type: u8,
+        inclusive_length: bool,
+        little_endian_length: bool,
+        max_buffer_size: usize,
+    ) -> PyResult<Self> {
+        if ![1, 2, 4].contains(&length_type) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "length_type must be 1, 2, or 4",
+            ));
+        }
+        Ok(Self {
+            framer: JceFramer::new(
+                length_type,
+                inclusive_length,
+                little_endian_length,
+                max_buffer_size,
+            ),
+        })
+    }
+
+    /// 检查缓冲区开头是否为完整的数据包.
+    ///
+    /// Args:
+    ///     buffer (bytes | bytearray | memoryview): 以长度头开始的数据.
+    ///
+    /// Returns:
+    ///     int | None: 完整数据包的总长度 (含长度头), 数据不足时为 None.
+    ///
+    /// Raises:
+    ///     FrameTooLarge: 数据包超过 `max_buffer_size`.
+    ///     InvalidLength: 长度小于长度头本身.
+    fn check_frame(&self, py: Python<'_>, buffer: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+        let input = InputData::new(buffer)?;
+        self.framer
+            .check_frame(input.as_bytes())
+            .map_err(|e| frame_error(py, &e, None))
+    }
+
+    /// 长度头字节数.
+    #[getter]
+    fn length_type(&self) -> u8 {
+        self.framer.length_type
+    }
+
+    /// 长度是否包含头部本身.
+    #[getter]
+    fn inclusive_length(&self) -> bool {
+        self.framer.inclusive_length
+    }
+
+    /// 长度头是否为小端序.
+    #[getter]
+    fn little_endian_length(&self) -> bool {
+        self.framer.little_endian
+    }
+
+    /// 允许的最大数据包大小.
+    #[getter]
+    fn max_buffer_size(&self) -> usize {
+        self.framer.max_frame_size
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (buffer, length_type=4, inclusive_length=true, little_endian_length=false, max_buffer_size=10485760))]
+/// 将拼接的带长度前缀数据切分为独立数据包的数据体.
+///
+/// 按 `JceFramer` 的规则依次识别数据包, 不解码数据体.
+///
+/// Args:
+///     buffer (bytes | bytearray | memoryview): 由多个数据包拼接而成的数据.
+///     length_type (int): 长度头字节数 (1, 2, 4).
+///     inclusive_length (bool): 长度是否包含头部本身.
+///     little_endian_length (bool): 长度头是否为小端序.
+///     max_buffer_size (int): 允许的最大数据包大小 (含长度头, 默认 10MB).
+///
+/// Returns:
+///     tuple[list[bytes], int]: 去除长度前缀后的数据体列表, 以及末尾不完整数据包的剩余字节数.
+///
+/// Raises:
+///     FrameTooLarge: 某个数据包超过 `max_buffer_size`, 异常的 `index` 为其序号.
+///     InvalidLength: 某个数据包的长度小于长度头本身, 异常的 `index` 为其序号.
+pub fn split_frames(
+    py: Python<'_>,
+    buffer: &Bound<'_, PyAny>,
+    length_type: u8,
+    inclusive_length: bool,
+    little_endian_length: bool,
+    max_buffer_size: usize,
+) -> PyResult<(Py<PyList>, usize)> {
+    let framer = PyJceFramer::new(
+        length_type,
+        inclusive_length,
+        little_endian_length,
+        max_buffer_size,
+    )?
+    .framer;
+    let input = InputData::new(buffer)?;
+    let mut data = input.as_bytes();
+    let frames = PyList::empty(py);
+    loop {
+        match framer.check_frame(data) {
+            Ok(Some(size)) => {
+                frames.append(PyBytes::new(py, &data[length_type as usize..size]))?;
+                data = &data[size..];
+            }
+            Ok(None) => break,
+            Err(e) => return Err(frame_error(py, &e, Some(frames.len()))),
+        }
+    }
+    Ok((frames.unbind(), data.len()))
+}
+
+/// 将数据体解码错误包装为携带原始数据的 `JceFrameDecodeError`, 原异常作为其 `__cause__`.
+fn frame_decode_error(py: Python<'_>, err: PyErr, frame: &[u8]) -> PyErr {
+    let wrapped = py
+        .import("tarsio.exceptions")
+        .and_then(|m| m.getattr("JceFrameDecodeError"))
+        .and_then(|cls| cls.call1((err.value(py).str()?, PyBytes::new(py, frame))));
+    match wrapped {
+        Ok(obj) => {
+            let new_err = PyErr::from_value(obj);
+            new_err.set_cause(py, Some(err));
+            new_err
+        }
+        Err(_) => err,
+    }
+}
+
 #[pyclass(subclass)]
 pub struct LengthPrefixedWriter {
     buffer: BytesMut,
     length_type: u8,
     inclusive_length: bool,
     little_endian: bool,
-    options: i32,
+    options: i64,
+    config: WriterConfig,
     context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
 }
 
 #[pymethods]
 impl LengthPrefixedWriter {
     #[new]
-    #[pyo3(signature = (length_type=4, inclusive_length=true, little_endian_length=false, options=0, context=None))]
+    #[pyo3(signature = (length_type=4, inclusive_length=true, little_endian_length=false, options=0, context=None, max_depth=None, string1_max=255, force_string4=false))]
+    #[allow(clippy::too_many_arguments)]
     /// 创建一个新的 LengthPrefixedWriter.
     ///
     /// Args:
@@ -207,12 +515,18 @@ impl LengthPrefixedWriter {
     ///     little_endian_length (bool): 长度头是否为小端序.
     ///     options (int): JCE 选项.
     ///     context (dict | None): 序列化上下文.
+    ///     max_depth (int | None): 最大嵌套深度, 默认为 100.
+    ///     string1_max (int): 使用 String1 的最大字符串字节长度, 默认为 255.
+    ///     force_string4 (bool): 是否所有字符串都使用 String4.
     fn new(
         length_type: u8,
         inclusive_length: bool,
         little_endian_length: bool,
-        options: i32,
+        options: i64,
         context: Option<Py<PyAny>>,
+        max_depth: Option<usize>,
+        string1_max: i64,
+        force_string4: bool,
     ) -> PyResult<Self> {
         if ![1, 2, 4].contains(&length_type) {
             return Err(pyo3::exceptions::PyValueError::new_err(
@@ -225,7 +539,9 @@ impl LengthPrefixedWriter {
             inclusive_length,
             little_endian: little_endian_length,
             options,
+            config: string_writer_config(options, string1_max, force_string4)?,
             context,
+            max_depth,
         })
     }
 
@@ -239,24 +555,27 @@ impl LengthPrefixedWriter {
     }
 
     fn write(&mut self, py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<()> {
+        let _encoding = StrEncodingGuard::new(py, None)?;
+        let _limit = DepthLimitGuard::new(self.max_depth);
         let options = self.options;
         let context_bound = match &self.context {
             Some(ctx) => ctx.bind(py).clone(),
             None => PyDict::new(py).into_any(),
         };
 
-        let payload = if options & 1 == 0 {
+        // 直接从 Writer 的缓冲区追加到输出, 不复制中间 payload
+        if options & 1 == 0 {
             let mut writer = JceWriter::<Vec<u8>, BigEndian>::new();
+            writer.set_config(self.config);
             Self::encode_obj(py, &mut writer, obj, options, &context_bound)?;
-            writer.get_buffer().to_vec()
+            self.append_packet(writer.get_buffer())
         } else {
             let mut writer =
                 JceWriter::<Vec<u8>, LittleEndian>::with_buffer(Vec::with_capacity(128));
+            writer.set_config(self.config);
             Self::encode_obj(py, &mut writer, obj, options, &context_bound)?;
-            writer.get_buffer().to_vec()
-        };
-
-        self.append_packet(&payload)
+            self.append_packet(writer.get_buffer())
+        }
     }
 
     /// 将原始字节作为数据包写入.
@@ -285,14 +604,14 @@ impl LengthPrefixedWriter {
     /// 编码单个对象到 writer.
     ///
     /// 自动推断对象类型 (Struct vs Dict vs Generic) 并调用相应的编码函数.
-    fn encode_obj<B: BufMut, E: Endianness>(
+    fn encode_obj<E: Endianness>(
         py: Python<'_>,
-        writer: &mut JceWriter<B, E>,
+        writer: &mut JceWriter<Vec<u8>, E>,
         obj: &Bound<'_, PyAny>,
-        options: i32,
+        options: i64,
         context: &Bound<'_, PyAny>,
     ) -> PyResult<()> {
-        if let Ok(schema_method) = obj.getattr("__get_core_schema__") {
+        if let Some(schema_method) = schema_method(obj) {
             let schema = schema_method.call0()?.cast_into::<PyList>()?;
             encode_struct(py, writer, obj, &schema, options, context, 0)
         } else if let Ok(type_name) = obj.get_type().name() {
@@ -308,56 +627,73 @@ impl LengthPrefixedWriter {
     }
 
     /// 为 Payload 添加长度前缀并写入缓冲区.
-    ///
-    /// 处理长度计算 (Inclusive/Exclusive) 和字节序 (Big/Little).
     fn append_packet(&mut self, payload: &[u8]) -> PyResult<()> {
-        let header_len = self.length_type as usize;
-        let total_len = if self.inclusive_length {
-            payload.len() + header_len
-        } else {
-            payload.len()
-        };
+        write_length_prefixed(
+            &mut self.buffer,
+            payload,
+            self.length_type,
+            self.inclusive_length,
+            self.little_endian,
+        )
+    }
+}
 
-        match self.length_type {
-            1 => {
-                if total_len > 255 {
-                    return Err(pyo3::exceptions::PyValueError::new_err(
-                        "Packet too large for 1-byte length",
-                    ));
-                }
-                self.buffer.put_u8(total_len as u8);
+/// 为 Payload 添加长度前缀并写入 `buffer`.
+///
+/// 处理长度计算 (Inclusive/Exclusive) 和字节序 (Big/Little).
+pub(crate) fn write_length_prefixed<B: BufMut>(
+    buffer: &mut B,
+    payload: &[u8],
+    length_type: u8,
+    inclusive_length: bool,
+    little_endian: bool,
+) -> PyResult<()> {
+    let header_len = length_type as usize;
+    let total_len = if inclusive_length {
+        payload.len() + header_len
+    } else {
+        payload.len()
+    };
+
+    match length_type {
+        1 => {
+            if total_len > 255 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Packet too large for 1-byte length",
+                ));
             }
-            2 => {
-                if total_len > 65535 {
-                    return Err(pyo3::exceptions::PyValueError::new_err(
-                        "Packet too large for 2-byte length",
-                    ));
-                }
-                if self.little_endian {
-                    self.buffer.put_u16_le(total_len as u16);
-                } else {
-                    self.buffer.put_u16(total_len as u16);
-                }
+            buffer.put_u8(total_len as u8);
+        }
+        2 => {
+            if total_len > 65535 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Packet too large for 2-byte length",
+                ));
             }
-            4 => {
-                if total_len > 4294967295 {
-                    return Err(pyo3::exceptions::PyValueError::new_err(
-                        "Packet too large for 4-byte length",
-                    ));
-                }
-                if self.little_endian {
-                    self.buffer.put_u32_le(total_len as u32);
-                } else {
-                    self.buffer.put_u32(total_len as u32);
-                }
+            if little_endian {
+                buffer.put_u16_le(total_len as u16);
+            } else {
+                buffer.put_u16(total_len as u16);
             }
-            _ => {
+        }
+        4 => {
+            if total_len > 4294967295 {
                 return Err(pyo3::exceptions::PyValueError::new_err(
-                    "Invalid length type",
+                    "Packet too large for 4-byte length",
                 ));
             }
+            if little_endian {
+                buffer.put_u32_le(total_len as u32);
+            } else {
+                buffer.put_u32(total_len as u32);
+            }
+        }
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Invalid length type",
+            ));
         }
-        self.buffer.put_slice(payload);
-        Ok(())
     }
+    buffer.put_slice(payload);
+    Ok(())
 }
@@ -0,0 +1,126 @@
+use crate::bindings::reader::jce_type;
+use crate::bindings::serde::InputData;
+use crate::codec::writer::JceWriter;
+use byteorder::{BigEndian, LittleEndian};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// 手动构造 JCE 数据的写入器.
+///
+/// 按调用顺序逐个写入字段, 与 `JceReader` 对应, 适合只改写部分字段的代理或转发场景.
+#[pyclass(name = "JceWriter")]
+pub struct Writer {
+    buffer: Vec<u8>,
+    little_endian: bool,
+}
+
+impl Writer {
+    /// 以当前字节序在缓冲区末尾执行写入操作.
+    fn write(
+        &mut self,
+        op_be: impl FnOnce(&mut JceWriter<&mut Vec<u8>, BigEndian>),
+        op_le: impl FnOnce(&mut JceWriter<&mut Vec<u8>, LittleEndian>),
+    ) {
+        if self.little_endian {
+            op_le(&mut JceWriter::with_buffer(&mut self.buffer));
+        } else {
+            op_be(&mut JceWriter::with_buffer(&mut self.buffer));
+        }
+    }
+}
+
+#[pymethods]
+impl Writer {
+    #[new]
+    #[pyo3(signature = (little_endian=false))]
+    /// 创建写入器.
+    ///
+    /// Args:
+    ///     little_endian (bool): 是否按小端序写入.
+    fn new(little_endian: bool) -> Self {
+        Self {
+            buffer: Vec::with_capacity(128),
+            little_endian,
+        }
+    }
+
+    /// 已写入的字节数.
+    fn __len__(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// 返回已写入数据的副本.
+    fn getvalue<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.buffer)
+    }
+
+    /// 清空已写入的数据, 以便复用写入器.
+    fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// 写入字段头部.
+    ///
+    /// Args:
+    ///     tag (int): 字段 Tag (0-255).
+    ///     type_id (int): 类型码.
+    fn write_head(&mut self, tag: u8, type_id: u8) -> PyResult<()> {
+        let t = jce_type(type_id)?;
+        self.write(|w| w.write_tag(tag, t), |w| w.write_tag(tag, t));
+        Ok(())
+    }
+
+    /// 写入整数字段, 按最小宽度编码.
+    ///
+    /// Args:
+    ///     tag (int): 字段 Tag.
+    ///     value (int): 整数值.
+    fn write_int(&mut self, tag: u8, value: i64) {
+        self.write(|w| w.write_int(tag, value), |w| w.write_int(tag, value));
+    }
+
+    /// 写入字符串字段.
+    ///
+    /// Args:
+    ///     tag (int): 字段 Tag.
+    ///     value (str): 字符串值.
+    fn write_string(&mut self, tag: u8, value: &str) {
+        self.write(
+            |w| w.write_string(tag, value),
+            |w| w.write_string(tag, value),
+        );
+    }
+
+    /// 写入字节数组字段 (SimpleList).
+    ///
+    /// Args:
+    ///     tag (int): 字段 Tag.
+    ///     value (bytes | bytearray | memoryview): 字节数据.
+    fn write_bytes(&mut self, tag: u8, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let data = InputData::new(value)?;
+        let bytes = data.as_bytes();
+        self.write(|w| w.write_bytes(tag, bytes), |w| w.write_bytes(tag, bytes));
+        Ok(())
+    }
+
+    /// 写入字段头部后原样追加已编码的值, 用于逐字节透传捕获的字段.
+    ///
+    /// `body` 为不含头部的值编码 (例如 `JceReader.measure_field` 测得范围中头部之后的部分),
+    /// 整数宽度与字节序均保持原样. 不校验 `body` 是否为 `type_id` 的合法编码,
+    /// 由调用方保证; 不合法的内容会破坏之后所有字段的解析.
+    ///
+    /// Args:
+    ///     tag (int): 字段 Tag.
+    ///     type_id (int): 类型码.
+    ///     body (bytes | bytearray | memoryview): 已编码的值.
+    fn write_raw_field(&mut self, tag: u8, type_id: u8, body: &Bound<'_, PyAny>) -> PyResult<()> {
+        let t = jce_type(type_id)?;
+        let data = InputData::new(body)?;
+        let body = data.as_bytes();
+        self.write(
+            |w| w.write_raw_field(tag, t, body),
+            |w| w.write_raw_field(tag, t, body),
+        );
+        Ok(())
+    }
+}
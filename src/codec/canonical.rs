@@ -0,0 +1,220 @@
+use crate::codec::consts::JceType;
+use crate::codec::endian::Endianness;
+use crate::codec::error::{Error, Result};
+use crate::codec::reader::JceReader;
+use crate::codec::writer::JceWriter;
+
+/// 将 JCE 结构体数据重编码为规范形式.
+///
+/// 规范形式满足:
+/// 1. 整数使用最小宽度, 字符串按长度选择 String1/String4.
+/// 2. 结构体字段按 Tag 升序排列 (重复 Tag 保留最后一个), Map 条目按键的规范编码排序.
+/// 3. Float 提升为 Double, `-0.0` 归一为 `0.0`, NaN 归一为同一位模式.
+///
+/// 语义相同但编码不同的数据会得到相同的输出. SimpleList 内容视为不透明数据.
+/// 嵌套深度超过 `max_depth` 时返回错误.
+pub fn canonicalize<E: Endianness>(data: &[u8], max_depth: usize) -> Result<Vec<u8>> {
+    let mut reader = JceReader::<E>::new(data).with_max_depth(max_depth);
+    let mut writer = JceWriter::<Vec<u8>, E>::with_buffer(Vec::with_capacity(data.len()));
+    canonical_struct(&mut reader, &mut writer, 0)?;
+    Ok(writer.finish())
+}
+
+/// 规范化结构体字段序列, 直到 StructEnd 或数据结束.
+///
+/// 只有顶层 (depth 为 0) 可以在数据结束时终止; 嵌套结构体必须以 StructEnd 结束,
+/// 否则截断的数据会与补全了 StructEnd 的数据得到相同的规范形式.
+fn canonical_struct<E: Endianness>(
+    reader: &mut JceReader<'_, E>,
+    writer: &mut JceWriter<Vec<u8>, E>,
+    depth: usize,
+) -> Result<()> {
+    let mut fields: Vec<(u8, Vec<u8>)> = Vec::new();
+    let mut closed = false;
+    while !reader.is_end() {
+        let (tag, jce_type) = reader.read_head()?;
+        if jce_type == JceType::StructEnd {
+            closed = true;
+            break;
+        }
+        let mut field = JceWriter::<Vec<u8>, E>::with_buffer(Vec::new());
+        canonical_field(reader, &mut field, tag, jce_type, depth + 1)?;
+        fields.push((tag, field.finish()));
+    }
+    if depth > 0 && !closed {
+        return Err(Error::new(
+            reader.position() as usize,
+            "Missing StructEnd in nested struct",
+        ));
+    }
+    // 稳定排序后, 同一 Tag 仅保留最后出现的值 (与解码语义一致)
+    fields.sort_by_key(|(tag, _)| *tag);
+    for (i, (tag, bytes)) in fields.iter().enumerate() {
+        if fields.get(i + 1).is_some_and(|(next, _)| next == tag) {
+            continue;
+        }
+        writer.write_raw(bytes);
+    }
+    Ok(())
+}
+
+/// 读取一个字段并以规范形式写入 `writer`.
+fn canonical_field<E: Endianness>(
+    reader: &mut JceReader<'_, E>,
+    writer: &mut JceWriter<Vec<u8>, E>,
+    tag: u8,
+    jce_type: JceType,
+    depth: usize,
+) -> Result<()> {
+    let pos = reader.position() as usize;
+    if depth > reader.max_depth() {
+        return Err(Error::new(pos, "Max recursion depth exceeded"));
+    }
+    match jce_type {
+        JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 | JceType::ZeroTag => {
+            writer.write_int(tag, reader.read_int(jce_type)?)
+        }
+        JceType::Float => writer.write_double(tag, normalize_float(reader.read_float()? as f64)),
+        JceType::Double => writer.write_double(tag, normalize_float(reader.read_double()?)),
+        JceType::String1 | JceType::String4 => {
+            writer.write_string_bytes(tag, reader.read_string_bytes(jce_type)?)
+        }
+        JceType::Map => {
+            let size = reader.read_container_size()?;
+            let mut entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+            for _ in 0..size {
+                let mut key = JceWriter::<Vec<u8>, E>::with_buffer(Vec::new());
+                let (_, ktype) = reader.read_head()?;
+                canonical_field(reader, &mut key, 0, ktype, depth + 1)?;
+                let mut value = JceWriter::<Vec<u8>, E>::with_buffer(Vec::new());
+                let (_, vtype) = reader.read_head()?;
+                canonical_field(reader, &mut value, 1, vtype, depth + 1)?;
+                entries.push((key.finish(), value.finish()));
+            }
+            // 稳定排序后, 重复的键仅保留最后一个
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut unique: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(entries.len());
+            for entry in entries {
+                match unique.last_mut() {
+                    Some(last) if last.0 == entry.0 => *last = entry,
+                    _ => unique.push(entry),
+                }
+            }
+            writer.write_map_header(tag, unique.len());
+            for (key, value) in &unique {
+                writer.write_raw(key);
+                writer.write_raw(value);
+            }
+        }
+        JceType::List => {
+            let size = reader.read_container_size()?;
+            writer.write_list_header(tag, size);
+            for _ in 0..size {
+                let (_, t) = reader.read_head()?;
+                canonical_field(reader, writer, 0, t, depth + 1)?;
+            }
+        }
+        JceType::SimpleList => {
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem == JceType::Int1 {
+                writer.write_bytes(tag, reader.read_bytes(count)?);
+            } else {
+                // 定宽整数数组: 仅规范化长度编码, 元素保持原宽度
+                let width = elem.simple_list_width().unwrap_or(1);
+                let payload = reader.read_bytes(count * width)?;
+                writer.write_tag(tag, JceType::SimpleList);
+                writer.write_raw(&[elem as u8]);
+                writer.write_int(0, count as i64);
+                writer.write_raw(payload);
+            }
+        }
+        JceType::StructBegin => {
+            writer.write_tag(tag, JceType::StructBegin);
+            canonical_struct(reader, writer, depth + 1)?;
+            writer.write_tag(0, JceType::StructEnd);
+        }
+        JceType::StructEnd => return Err(Error::new(pos, "Unexpected StructEnd")),
+    }
+    Ok(())
+}
+
+/// 归一化浮点数: `-0.0` 视为 `0.0`, 所有 NaN 使用同一位模式.
+fn normalize_float(v: f64) -> f64 {
+    if v == 0.0 {
+        0.0
+    } else if v.is_nan() {
+        f64::NAN
+    } else {
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::consts::MAX_DEPTH;
+    use byteorder::BigEndian;
+
+    #[test]
+    fn test_canonicalize_is_encoding_independent() {
+        // Tag 1 = "a" (String1), Tag 0 = 1 (Int4, 非最小宽度)
+        let loose = b"\x16\x01a\x02\x00\x00\x00\x01";
+        // Tag 0 = 1 (Int1), Tag 1 = "a"
+        let tight = b"\x00\x01\x16\x01a";
+        assert_eq!(
+            canonicalize::<BigEndian>(loose, MAX_DEPTH).unwrap(),
+            canonicalize::<BigEndian>(tight, MAX_DEPTH).unwrap()
+        );
+        assert_eq!(canonicalize::<BigEndian>(loose, MAX_DEPTH).unwrap(), tight);
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_map_and_widens_float() {
+        // Map {2: 0, 1: 0}
+        let unsorted = b"\x08\x00\x02\x00\x02\x1c\x00\x01\x1c";
+        let sorted = b"\x08\x00\x02\x00\x01\x1c\x00\x02\x1c";
+        assert_eq!(
+            canonicalize::<BigEndian>(unsorted, MAX_DEPTH).unwrap(),
+            canonicalize::<BigEndian>(sorted, MAX_DEPTH).unwrap()
+        );
+
+        // Float 1.5 与 Double 1.5 等价
+        let float = b"\x04\x3f\xc0\x00\x00";
+        let double = b"\x05\x3f\xf8\x00\x00\x00\x00\x00\x00";
+        assert_eq!(canonicalize::<BigEndian>(float, MAX_DEPTH).unwrap(), double);
+    }
+
+    #[test]
+    fn test_canonicalize_is_idempotent() {
+        // Tag 2 = Struct { Tag 1 = Float 1.5, Tag 0 = Int2 1 }, Tag 0 = Map {"b": 1, "a": 2}
+        let data = b"\x2a\x14\x3f\xc0\x00\x00\x01\x00\x01\x0b\x08\x00\x02\x06\x01b\x10\x01\x06\x01a\x10\x02";
+        let once = canonicalize::<BigEndian>(data, MAX_DEPTH).unwrap();
+        let twice = canonicalize::<BigEndian>(&once, MAX_DEPTH).unwrap();
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_canonicalize_respects_max_depth() {
+        // Tag 0 = Struct { Tag 0 = Struct {} }
+        let data = b"\x0a\x0a\x0b\x0b";
+        assert!(canonicalize::<BigEndian>(data, MAX_DEPTH).is_ok());
+        assert!(canonicalize::<BigEndian>(data, 1).is_err());
+    }
+
+    #[test]
+    fn test_canonicalize_requires_nested_struct_end() {
+        // Tag 0 = Struct { Tag 0 = 1 }, 缺少 StructEnd
+        let data = b"\x0a\x00\x01";
+        assert!(canonicalize::<BigEndian>(data, MAX_DEPTH).is_err());
+        assert!(canonicalize::<BigEndian>(b"\x0a\x00\x01\x0b", MAX_DEPTH).is_ok());
+        // 顶层字段序列可以直接在数据末尾结束
+        assert!(canonicalize::<BigEndian>(b"\x00\x01", MAX_DEPTH).is_ok());
+    }
+
+    #[test]
+    fn test_canonicalize_rejects_negative_size() {
+        // List, size = -1
+        let data = b"\x09\x00\xff";
+        assert!(canonicalize::<BigEndian>(data, MAX_DEPTH).is_err());
+    }
+}
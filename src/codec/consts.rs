@@ -1,3 +1,6 @@
+/// 默认的最大嵌套深度, 各编解码入口未指定 `max_depth` 时使用.
+pub const MAX_DEPTH: usize = 100;
+
 /// JCE 数据类型枚举.
 ///
 /// 遵循 JCE 协议规范，使用 `#[repr(u8)]` 以匹配协议中的字节值.
@@ -45,6 +48,86 @@ impl TryFrom<u8> for JceType {
     }
 }
 
+impl JceType {
+    /// SimpleList 元素类型对应的单个元素字节宽度.
+    ///
+    /// 仅支持整数类型, 其他类型返回 `None`.
+    #[inline]
+    pub fn simple_list_width(self) -> Option<usize> {
+        match self {
+            JceType::Int1 => Some(1),
+            JceType::Int2 => Some(2),
+            JceType::Int4 => Some(4),
+            JceType::Int8 => Some(8),
+            _ => None,
+        }
+    }
+
+    /// 声明类型为 `self` 的字段能否接受线上类型为 `actual` 的数据.
+    ///
+    /// 各宽度的整数 (含 ZeroTag) 互通, String1 与 String4 互通, Double 可接受 Float;
+    /// 其余类型必须完全一致.
+    #[inline]
+    pub fn accepts(self, actual: JceType) -> bool {
+        match self {
+            JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => matches!(
+                actual,
+                JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 | JceType::ZeroTag
+            ),
+            JceType::Double => actual == JceType::Double || actual == JceType::Float,
+            JceType::String1 | JceType::String4 => {
+                matches!(actual, JceType::String1 | JceType::String4)
+            }
+            _ => actual == self,
+        }
+    }
+
+    /// 按类型码升序排列的全部类型, 用于向 Python 导出类型常量.
+    pub const ALL: [JceType; 14] = [
+        JceType::Int1,
+        JceType::Int2,
+        JceType::Int4,
+        JceType::Int8,
+        JceType::Float,
+        JceType::Double,
+        JceType::String1,
+        JceType::String4,
+        JceType::Map,
+        JceType::List,
+        JceType::StructBegin,
+        JceType::StructEnd,
+        JceType::ZeroTag,
+        JceType::SimpleList,
+    ];
+
+    /// Python 端使用的常量名 (如 `SIMPLE_LIST`).
+    pub fn name(self) -> &'static str {
+        match self {
+            JceType::Int1 => "INT1",
+            JceType::Int2 => "INT2",
+            JceType::Int4 => "INT4",
+            JceType::Int8 => "INT8",
+            JceType::Float => "FLOAT",
+            JceType::Double => "DOUBLE",
+            JceType::String1 => "STRING1",
+            JceType::String4 => "STRING4",
+            JceType::Map => "MAP",
+            JceType::List => "LIST",
+            JceType::StructBegin => "STRUCT_BEGIN",
+            JceType::StructEnd => "STRUCT_END",
+            JceType::ZeroTag => "ZERO_TAG",
+            JceType::SimpleList => "SIMPLE_LIST",
+        }
+    }
+
+    /// 与 `accepts` 相同, `widen_floats` 为真时 Float 还可接受 Double (读取后截断为 f32).
+    #[inline]
+    pub fn accepts_with(self, actual: JceType, widen_floats: bool) -> bool {
+        self.accepts(actual)
+            || (widen_floats && self == JceType::Float && actual == JceType::Double)
+    }
+}
+
 // 常量定义，与 Python 端保持一致
 pub const JCE_INT1: u8 = 0;
 pub const JCE_INT2: u8 = 1;
@@ -83,6 +166,31 @@ mod tests {
         assert_eq!(JceType::SimpleList as u8, 13);
     }
 
+    #[test]
+    fn test_all_types_in_code_order() {
+        for (code, jce_type) in JceType::ALL.into_iter().enumerate() {
+            assert_eq!(jce_type as usize, code);
+            assert_eq!(JceType::try_from(code as u8), Ok(jce_type));
+        }
+        assert_eq!(JceType::try_from(JceType::ALL.len() as u8), Err(14));
+        assert_eq!(JceType::SimpleList.name(), "SIMPLE_LIST");
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert!(JceType::Int1.accepts(JceType::Int8));
+        assert!(JceType::Int8.accepts(JceType::ZeroTag));
+        assert!(JceType::Double.accepts(JceType::Float));
+        assert!(!JceType::Float.accepts(JceType::Double));
+        assert!(JceType::String4.accepts(JceType::String1));
+        assert!(!JceType::String1.accepts(JceType::Int1));
+        assert!(!JceType::List.accepts(JceType::SimpleList));
+        assert!(JceType::Float.accepts_with(JceType::Double, true));
+        assert!(!JceType::Float.accepts_with(JceType::Double, false));
+        assert!(JceType::Double.accepts_with(JceType::Float, false));
+        assert!(!JceType::Int4.accepts_with(JceType::Double, true));
+    }
+
     #[test]
     fn test_try_from_u8() {
         assert_eq!(JceType::try_from(0), Ok(JceType::Int1));
@@ -0,0 +1,162 @@
+use crate::codec::consts::JceType;
+use crate::codec::endian::Endianness;
+use crate::codec::error::{Error, Result};
+use crate::codec::reader::JceReader;
+use std::fmt::Write;
+
+/// 生成 JCE 数据的结构化文本转储, 用于调试与逆向未知数据包.
+///
+/// 每个字段输出一行, 形如 `offset=0x00 tag=1 type=Int4 value=256`,
+/// 结构体, List 与 Map 的子元素按层级缩进.
+/// 遇到非法数据时不会报错, 而是追加 `<parse error at 0xNN>` 标记并停止解析,
+/// 嵌套深度超过 `max_depth` 同样视为非法数据.
+pub fn debug_dump<E: Endianness>(data: &[u8], max_depth: usize) -> String {
+    let mut reader = JceReader::<E>::new(data).with_max_depth(max_depth);
+    let mut out = String::new();
+    while !reader.is_end() {
+        if let Err(e) = dump_field(&mut reader, &mut out, 0) {
+            let _ = writeln!(out, "<parse error at 0x{:02x}>", e.offset());
+            break;
+        }
+    }
+    out
+}
+
+/// 读取一个字段 (含头部) 并写入对应的文本行.
+fn dump_field<E: Endianness>(
+    reader: &mut JceReader<'_, E>,
+    out: &mut String,
+    depth: usize,
+) -> Result<()> {
+    let offset = reader.position() as usize;
+    if depth > reader.max_depth() {
+        return Err(Error::new(offset, "Max recursion depth exceeded"));
+    }
+    let (tag, jce_type) = reader.read_head()?;
+    let indent = "  ".repeat(depth);
+    let head = format!("{indent}offset=0x{offset:02x} tag={tag} type={jce_type:?}");
+    match jce_type {
+        JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 | JceType::ZeroTag => {
+            let value = reader.read_int(jce_type)?;
+            let _ = writeln!(out, "{head} value={value}");
+        }
+        JceType::Float => {
+            let value = reader.read_float()?;
+            let _ = writeln!(out, "{head} value={value}");
+        }
+        JceType::Double => {
+            let value = reader.read_double()?;
+            let _ = writeln!(out, "{head} value={value}");
+        }
+        JceType::String1 | JceType::String4 => {
+            let bytes = reader.read_string_bytes(jce_type)?;
+            match std::str::from_utf8(bytes) {
+                Ok(s) => {
+                    let _ = writeln!(out, "{head} len={} value={:?}", bytes.len(), s);
+                }
+                Err(_) => {
+                    let _ = writeln!(out, "{head} len={} hex={}", bytes.len(), hex(bytes));
+                }
+            }
+        }
+        JceType::SimpleList => {
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem == JceType::Int1 {
+                let bytes = reader.read_bytes(count)?;
+                let _ = writeln!(out, "{head} len={} hex={}", count, hex(bytes));
+            } else {
+                let values = reader.read_simple_list_ints(elem, count)?;
+                let _ = writeln!(out, "{head} elem={elem:?} size={count} value={values:?}");
+            }
+        }
+        JceType::Map => {
+            let size = reader.read_container_size()?;
+            let _ = writeln!(out, "{head} size={size}");
+            for _ in 0..size {
+                dump_field(reader, out, depth + 1)?;
+                dump_field(reader, out, depth + 1)?;
+            }
+        }
+        JceType::List => {
+            let size = reader.read_container_size()?;
+            let _ = writeln!(out, "{head} size={size}");
+            for _ in 0..size {
+                dump_field(reader, out, depth + 1)?;
+            }
+        }
+        JceType::StructBegin => {
+            let _ = writeln!(out, "{head}");
+            loop {
+                if reader.is_end() {
+                    return Err(Error::BufferOverflow {
+                        offset: reader.position() as usize,
+                    });
+                }
+                let (_, t) = reader.peek_head()?;
+                if t == JceType::StructEnd {
+                    let end = reader.position() as usize;
+                    reader.read_head()?;
+                    let _ = writeln!(out, "{indent}offset=0x{end:02x} type=StructEnd");
+                    break;
+                }
+                dump_field(reader, out, depth + 1)?;
+            }
+        }
+        JceType::StructEnd => {
+            // 顶层多余的 StructEnd 仅作标注, 不中断转储
+            let _ = writeln!(out, "{indent}offset=0x{offset:02x} type=StructEnd");
+        }
+    }
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::consts::MAX_DEPTH;
+    use byteorder::BigEndian;
+
+    #[test]
+    fn test_debug_dump_nested() {
+        // Tag 0 = 256 (Int2), Tag 1 = Struct { Tag 0 = "a" }, Tag 2 = [1]
+        let data = b"\x01\x01\x00\x1a\x06\x01a\x0b\x29\x00\x01\x00\x01";
+        let expected = "\
+offset=0x00 tag=0 type=Int2 value=256
+offset=0x03 tag=1 type=StructBegin
+  offset=0x04 tag=0 type=String1 len=1 value=\"a\"
+offset=0x07 type=StructEnd
+offset=0x08 tag=2 type=List size=1
+  offset=0x0b tag=0 type=Int1 value=1
+";
+        assert_eq!(debug_dump::<BigEndian>(data, MAX_DEPTH), expected);
+    }
+
+    #[test]
+    fn test_debug_dump_typed_simple_list() {
+        // Tag 0 = SimpleList<Int2> [1, -1]
+        let data = b"\x0d\x01\x00\x02\x00\x01\xff\xff";
+        assert_eq!(
+            debug_dump::<BigEndian>(data, MAX_DEPTH),
+            "offset=0x00 tag=0 type=SimpleList elem=Int2 size=2 value=[1, -1]\n"
+        );
+    }
+
+    #[test]
+    fn test_debug_dump_stops_at_parse_error() {
+        // Tag 0 = 1, Tag 1 = String1 长度 5 但只有 2 字节
+        let data = b"\x00\x01\x16\x05ab";
+        assert_eq!(
+            debug_dump::<BigEndian>(data, MAX_DEPTH),
+            "offset=0x00 tag=0 type=Int1 value=1\n<parse error at 0x04>\n"
+        );
+    }
+}
@@ -19,6 +19,15 @@ impl Error {
             msg: msg.into(),
         }
     }
+
+    /// 错误发生的字节偏移量.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::Custom { offset, .. }
+            | Self::BufferOverflow { offset }
+            | Self::InvalidType { offset, .. } => *offset,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
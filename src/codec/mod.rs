@@ -1,7 +1,11 @@
+pub mod canonical;
 pub mod consts;
+pub mod debug;
 pub mod endian;
 pub mod error;
 pub mod framing;
 pub mod reader;
+#[cfg(test)]
+mod roundtrip;
 pub mod scanner;
 pub mod writer;
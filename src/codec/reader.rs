@@ -1,4 +1,4 @@
-use crate::codec::consts::JceType;
+use crate::codec::consts::{JceType, MAX_DEPTH};
 use crate::codec::endian::Endianness;
 use crate::codec::error::{Error, Result};
 use byteorder::ReadBytesExt;
@@ -10,6 +10,7 @@ use std::marker::PhantomData;
 pub struct JceReader<'a, E: Endianness> {
     cursor: Cursor<&'a [u8]>,
     depth: usize,
+    max_depth: usize,
     _phantom: PhantomData<E>,
 }
 
@@ -19,22 +20,62 @@ impl<'a, E: Endianness> JceReader<'a, E> {
         Self {
             cursor: Cursor::new(bytes),
             depth: 0,
+            max_depth: MAX_DEPTH,
             _phantom: PhantomData,
         }
     }
 
+    /// 设置 `skip_field` 允许的最大递归深度 (默认为 `MAX_DEPTH`).
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// 允许的最大递归深度, 供递归遍历数据的调用方共用同一限制.
+    #[inline]
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     /// 获取当前偏移量.
     #[inline]
     pub fn position(&self) -> u64 {
         self.cursor.position()
     }
 
+    /// 移动到指定偏移量.
+    ///
+    /// 偏移量超出数据长度时返回错误, 读取器位置保持不变.
+    pub fn set_position(&mut self, pos: u64) -> Result<()> {
+        if pos > self.cursor.get_ref().len() as u64 {
+            return Err(Error::new(pos as usize, "Position out of range"));
+        }
+        self.cursor.set_position(pos);
+        Ok(())
+    }
+
     /// 检查是否已到达末尾.
     #[inline]
     pub fn is_end(&self) -> bool {
         self.cursor.position() >= self.cursor.get_ref().len() as u64
     }
 
+    /// 剩余未读取的字节数.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.cursor
+            .get_ref()
+            .len()
+            .saturating_sub(self.cursor.position() as usize)
+    }
+
+    /// 剩余未读取的数据.
+    #[inline]
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        let data = *self.cursor.get_ref();
+        &data[(self.cursor.position() as usize).min(data.len())..]
+    }
+
     /// 读取头部信息 (Tag 和 Type).
     #[inline]
     pub fn read_head(&mut self) -> Result<(u8, JceType)> {
@@ -47,9 +88,11 @@ impl<'a, E: Endianness> JceReader<'a, E> {
         let mut tag = (b & 0xF0) >> 4;
 
         if tag == 15 {
-            tag = self.cursor.read_u8().map_err(|_| Error::BufferOverflow {
-                offset: self.position() as usize,
-            })?;
+            // 扩展 Tag 的第二个字节缺失时单独报告, 与普通的数据截断区分
+            tag = self
+                .cursor
+                .read_u8()
+                .map_err(|_| Error::new(pos as usize, "Truncated extended tag header"))?;
         }
 
         let jce_type = JceType::try_from(type_id).map_err(|id| Error::InvalidType {
@@ -68,6 +111,28 @@ impl<'a, E: Endianness> JceReader<'a, E> {
         res
     }
 
+    /// 在当前层级向前查找指定 Tag 的字段.
+    ///
+    /// 依次读取头部并跳过不匹配的字段 (不进入嵌套结构体). 找到时定位到该字段的头部之前,
+    /// 随后 `read_head` 即可得到其类型; 遇到 StructEnd 或数据结束时恢复原位置并返回 false.
+    pub fn seek_to_tag(&mut self, tag: u8) -> Result<bool> {
+        let start = self.position();
+        while !self.is_end() {
+            let pos = self.position();
+            let (t, jce_type) = self.read_head()?;
+            if jce_type == JceType::StructEnd {
+                break;
+            }
+            if t == tag {
+                self.cursor.set_position(pos);
+                return Ok(true);
+            }
+            self.skip_field(jce_type)?;
+        }
+        self.cursor.set_position(start);
+        Ok(false)
+    }
+
     /// 读取整数.
     #[inline]
     pub fn read_int(&mut self, type_id: JceType) -> Result<i64> {
@@ -138,20 +203,21 @@ impl<'a, E: Endianness> JceReader<'a, E> {
 
     /// 读取字符串 (零拷贝).
     pub fn read_string(&mut self, type_id: JceType) -> Result<Cow<'a, str>> {
+        let slice = self.read_string_bytes(type_id)?;
+        let start = self.position() as usize - slice.len();
+        let s = std::str::from_utf8(slice)
+            .map_err(|e| Error::new(start, format!("Invalid UTF-8 string: {}", e)))?;
+        Ok(Cow::Borrowed(s))
+    }
+
+    /// 读取字符串的原始字节 (零拷贝, 不校验 UTF-8).
+    pub fn read_string_bytes(&mut self, type_id: JceType) -> Result<&'a [u8]> {
         let pos = self.position();
         let len = match type_id {
             JceType::String1 => self.cursor.read_u8().map_err(|_| Error::BufferOverflow {
                 offset: pos as usize,
             })? as usize,
-            JceType::String4 => {
-                let len = self
-                    .cursor
-                    .read_u32::<E>()
-                    .map_err(|_| Error::BufferOverflow {
-                        offset: pos as usize,
-                    })?;
-                len as usize
-            }
+            JceType::String4 => self.read_string4_len()?,
             _ => {
                 return Err(Error::new(
                     pos as usize,
@@ -159,26 +225,37 @@ impl<'a, E: Endianness> JceReader<'a, E> {
                 ));
             }
         };
+        self.read_bytes(len)
+    }
 
-        let start = self.cursor.position() as usize;
-        let end = start + len;
-        let data = self.cursor.get_ref();
-
-        if end > data.len() {
-            return Err(Error::BufferOverflow { offset: start });
+    /// 读取 String4 的长度字段.
+    ///
+    /// 声明的长度超过剩余字节数时报告该长度, 便于识别误判为 String4 的数据.
+    fn read_string4_len(&mut self) -> Result<usize> {
+        let pos = self.position();
+        let len = self
+            .cursor
+            .read_u32::<E>()
+            .map_err(|_| Error::BufferOverflow {
+                offset: pos as usize,
+            })? as usize;
+        let start = self.position() as usize;
+        let remaining = self.remaining();
+        if len > remaining {
+            return Err(Error::new(
+                start,
+                format!(
+                    "String4 declares {} bytes but only {} remain",
+                    len, remaining
+                ),
+            ));
         }
-
-        let slice = &data[start..end];
-        let s = std::str::from_utf8(slice)
-            .map_err(|e| Error::new(start, format!("Invalid UTF-8 string: {}", e)))?;
-
-        self.cursor.set_position(end as u64);
-        Ok(Cow::Borrowed(s))
+        Ok(len)
     }
 
     /// 跳过当前字段.
     pub fn skip_field(&mut self, type_id: JceType) -> Result<()> {
-        if self.depth > 100 {
+        if self.depth > self.max_depth {
             return Err(Error::new(
                 self.position() as usize,
                 "Max recursion depth exceeded in skip_field",
@@ -210,24 +287,21 @@ impl<'a, E: Endianness> JceReader<'a, E> {
                 self.skip(len as u64)
             }
             JceType::String4 => {
-                let len = self
-                    .cursor
-                    .read_u32::<E>()
-                    .map_err(|_| Error::BufferOverflow {
-                        offset: pos as usize,
-                    })?;
+                let len = self.read_string4_len()?;
                 self.skip(len as u64)
             }
             JceType::Map => {
-                let size = self.read_size()?;
-                for _ in 0..size * 2 {
-                    let (_, t) = self.read_head()?;
-                    self.skip_field(t)?;
+                let size = self.read_container_size()?;
+                for _ in 0..size {
+                    let (_, kt) = self.read_head()?;
+                    self.skip_field(kt)?;
+                    let (_, vt) = self.read_head()?;
+                    self.skip_field(vt)?;
                 }
                 Ok(())
             }
             JceType::List => {
-                let size = self.read_size()?;
+                let size = self.read_container_size()?;
                 for _ in 0..size {
                     let (_, t) = self.read_head()?;
                     self.skip_field(t)?;
@@ -235,15 +309,9 @@ impl<'a, E: Endianness> JceReader<'a, E> {
                 Ok(())
             }
             JceType::SimpleList => {
-                let t = self.read_u8()?;
-                if t != 0 {
-                    return Err(Error::new(
-                        self.position() as usize,
-                        format!("SimpleList must contain Byte (0), got {}", t),
-                    ));
-                }
-                let len = self.read_size()?;
-                self.skip(len as u64)
+                let (elem, count) = self.read_simple_list_header()?;
+                let width = elem.simple_list_width().unwrap_or(1);
+                self.skip((count * width) as u64)
             }
             JceType::StructBegin => {
                 loop {
@@ -290,6 +358,48 @@ impl<'a, E: Endianness> JceReader<'a, E> {
         Ok(())
     }
 
+    /// 读取 SimpleList 的元素类型与元素个数 (紧随 SimpleList 头部之后).
+    ///
+    /// 元素类型为 Int1 时即为普通字节数组; Int2/Int4/Int8 表示定宽整数数组,
+    /// 此时个数为元素个数而非字节数.
+    pub fn read_simple_list_header(&mut self) -> Result<(JceType, usize)> {
+        let pos = self.position() as usize;
+        let t = self.read_u8()?;
+        let elem = JceType::try_from(t)
+            .ok()
+            .filter(|elem| elem.simple_list_width().is_some())
+            .ok_or_else(|| Error::new(pos, format!("Unsupported SimpleList element type {}", t)))?;
+        let size_pos = self.position() as usize;
+        let size = self.read_size()?;
+        let count = usize::try_from(size)
+            .map_err(|_| Error::new(size_pos, format!("Invalid SimpleList size {}", size)))?;
+        Ok((elem, count))
+    }
+
+    /// 读取定宽整数 SimpleList 的内容.
+    pub fn read_simple_list_ints(&mut self, elem: JceType, count: usize) -> Result<Vec<i64>> {
+        let pos = self.position() as usize;
+        let width = elem.simple_list_width().ok_or_else(|| {
+            Error::new(
+                pos,
+                format!("Unsupported SimpleList element type {:?}", elem),
+            )
+        })?;
+        let len = count
+            .checked_mul(width)
+            .ok_or(Error::BufferOverflow { offset: pos })?;
+        let bytes = self.read_bytes(len)?;
+        Ok(bytes
+            .chunks_exact(width)
+            .map(|chunk| match width {
+                1 => chunk[0] as i8 as i64,
+                2 => E::read_i16(chunk) as i64,
+                4 => E::read_i32(chunk) as i64,
+                _ => E::read_i64(chunk),
+            })
+            .collect())
+    }
+
     /// 读取一个字节.
     #[inline]
     pub fn read_u8(&mut self) -> Result<u8> {
@@ -302,12 +412,38 @@ impl<'a, E: Endianness> JceReader<'a, E> {
     /// 读取 JCE 容器的大小 (List/Map/SimpleList 长度).
     /// 读取容器大小 (Size).
     ///
-    /// JCE 中大小也是一个 Tag 为 0 的整数，但类型可能是 Int1/2/4.
-    /// 此方法自动解析并返回 i32 大小.
+    /// JCE 中大小也是一个 Tag 为 0 的整数，但类型可能是 ZeroTag/Int1/2/4.
+    /// 此方法自动解析并返回 i32 大小, 接受的类型与 `JceScanner` 保持一致.
     #[inline]
     pub fn read_size(&mut self) -> Result<i32> {
         let (_, t) = self.read_head()?;
-        self.read_int(t).map(|v| v as i32)
+        match t {
+            JceType::ZeroTag => Ok(0),
+            JceType::Int1 | JceType::Int2 | JceType::Int4 => self.read_int(t).map(|v| v as i32),
+            _ => Err(Error::new(self.position() as usize, "Invalid size type")),
+        }
+    }
+
+    /// 读取 List/Map 的元素个数.
+    ///
+    /// 拒绝负数, 以及超过剩余字节数的大小 (每个元素至少占用 1 字节),
+    /// 避免畸形数据导致空循环或超大的循环次数.
+    pub fn read_container_size(&mut self) -> Result<usize> {
+        let pos = self.position() as usize;
+        let size = self.read_size()?;
+        let size = usize::try_from(size)
+            .map_err(|_| Error::new(pos, format!("Invalid container size {}", size)))?;
+        let remaining = self.remaining();
+        if size > remaining {
+            return Err(Error::new(
+                pos,
+                format!(
+                    "Container size {} exceeds remaining {} bytes",
+                    size, remaining
+                ),
+            ));
+        }
+        Ok(size)
     }
 }
 
@@ -316,6 +452,24 @@ mod tests {
     use super::*;
     use byteorder::{BigEndian, LittleEndian};
 
+    #[test]
+    fn test_truncated_extended_tag_header() {
+        let mut reader = JceReader::<BigEndian>::new(b"\xf0");
+        assert_eq!(
+            reader.read_head(),
+            Err(Error::new(0, "Truncated extended tag header"))
+        );
+
+        let mut reader = JceReader::<BigEndian>::new(b"\x00\x01\xf0");
+        reader.read_head().unwrap();
+        reader.read_int(JceType::Int1).unwrap();
+        assert_eq!(
+            reader.read_head().unwrap_err().offset(),
+            2,
+            "offset should point at the header start"
+        );
+    }
+
     #[test]
     fn test_read_head() {
         // Tag 1, Type Int1 (0)
@@ -333,6 +487,34 @@ mod tests {
         assert_eq!(t, JceType::Int1);
     }
 
+    #[test]
+    fn test_peek_head_restores_extended_tag() {
+        // Tag 20 (2 字节头部), Type Int1, 值 7
+        let data = b"\xF0\x14\x07";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        assert_eq!(reader.peek_head().unwrap(), (20, JceType::Int1));
+        assert_eq!(reader.position(), 0);
+        assert_eq!(reader.read_head().unwrap(), (20, JceType::Int1));
+        assert_eq!(reader.read_int(JceType::Int1).unwrap(), 7);
+        assert!(reader.set_position(4).is_err());
+        assert!(reader.set_position(1).is_ok());
+    }
+
+    #[test]
+    fn test_seek_to_tag_stays_on_current_level() {
+        // Tag 0 = Struct { Tag 2 = 1 }, Tag 1 = 2, Tag 2 = 3
+        let data = b"\x0a\x20\x01\x0b\x10\x02\x20\x03";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        assert!(reader.seek_to_tag(2).unwrap());
+        assert_eq!(reader.position(), 6);
+        assert_eq!(reader.read_head().unwrap(), (2, JceType::Int1));
+        assert_eq!(reader.read_int(JceType::Int1).unwrap(), 3);
+
+        reader.set_position(0).unwrap();
+        assert!(!reader.seek_to_tag(5).unwrap());
+        assert_eq!(reader.position(), 0);
+    }
+
     #[test]
     fn test_read_int() {
         // Int1: 0
@@ -367,6 +549,25 @@ mod tests {
         assert!(reader.is_end());
     }
 
+    #[test]
+    fn test_skip_typed_simple_list() {
+        // Tag 0 = SimpleList<Int4> [1, 2], Tag 1 = 1
+        let data = b"\x0d\x02\x00\x02\x00\x00\x00\x01\x00\x00\x00\x02\x10\x01";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        let (_, t) = reader.read_head().unwrap();
+        reader.skip_field(t).unwrap();
+        assert_eq!(reader.read_head().unwrap(), (1, JceType::Int1));
+
+        let mut reader = JceReader::<BigEndian>::new(data);
+        reader.read_head().unwrap();
+        let (elem, count) = reader.read_simple_list_header().unwrap();
+        assert_eq!((elem, count), (JceType::Int4, 2));
+        assert_eq!(
+            reader.read_simple_list_ints(elem, count).unwrap(),
+            vec![1, 2]
+        );
+    }
+
     #[test]
     fn test_little_endian() {
         // Int2: 1 in Little Endian (0x01 0x00)
@@ -384,4 +585,56 @@ mod tests {
         let mut reader = JceReader::<LittleEndian>::new(data);
         assert_eq!(reader.read_string(JceType::String4).unwrap(), "A");
     }
+
+    #[test]
+    fn test_string4_length_exceeds_buffer() {
+        let data = b"\xff\xff\xff\xffabc";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        let err = reader.read_string(JceType::String4).unwrap_err();
+        assert_eq!(err.offset(), 4);
+        assert_eq!(
+            err.to_string(),
+            "Error at offset 4: String4 declares 4294967295 bytes but only 3 remain"
+        );
+
+        let mut reader = JceReader::<BigEndian>::new(data);
+        let err = reader.skip_field(JceType::String4).unwrap_err();
+        assert!(err.to_string().contains("declares 4294967295 bytes"));
+    }
+
+    #[test]
+    fn test_read_size_zero_tag() {
+        // Map 大小以 ZeroTag 编码 (空 Map)
+        let data = b"\x0c";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        assert_eq!(reader.read_size().unwrap(), 0);
+        assert!(reader.is_end());
+
+        // 与 JceScanner 一致, 不接受非整数的大小类型
+        let data = b"\x06\x00";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        assert!(reader.read_size().is_err());
+    }
+
+    #[test]
+    fn test_read_container_size_rejects_invalid() {
+        // Int4 大小 -1
+        let data = b"\x02\xff\xff\xff\xff";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        let err = reader.read_container_size().unwrap_err();
+        assert!(err.to_string().contains("Invalid container size -1"));
+
+        // 声明 100 个元素, 但没有剩余数据
+        let data = b"\x00\x64";
+        let mut reader = JceReader::<BigEndian>::new(data);
+        let err = reader.read_container_size().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Container size 100 exceeds remaining 0 bytes")
+        );
+
+        // skip_field 同样拒绝负数大小
+        let mut reader = JceReader::<BigEndian>::new(b"\x02\xff\xff\xff\xff");
+        assert!(reader.skip_field(JceType::Map).is_err());
+    }
 }
@@ -0,0 +1,206 @@
+//! `JceWriter` 与 `JceReader` 的往返属性测试.
+//!
+//! 随机生成 `(tag, 值)` 序列, 覆盖所有 `JceType` (含有限深度的嵌套 Map/List/Struct),
+//! 在大端与小端下编码后读回, 断言得到相同的逻辑值.
+
+use crate::codec::consts::JceType;
+use crate::codec::endian::Endianness;
+use crate::codec::error::Result;
+use crate::codec::reader::JceReader;
+use crate::codec::writer::{JceWriter, WriterConfig};
+use byteorder::{BigEndian, LittleEndian};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// 与线格式一一对应的逻辑值.
+///
+/// 浮点数以位模式保存, 以便 NaN 也能按值比较.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    /// ZeroTag/Int1/Int2/Int4/Int8, 由写入器按最小宽度选择.
+    Int(i64),
+    Float(u32),
+    Double(u64),
+    /// String1/String4, 由长度与 `WriterConfig` 决定.
+    Str(String),
+    /// 元素类型为 Int1 的 SimpleList.
+    Bytes(Vec<u8>),
+    /// 元素类型为 Int2/Int4/Int8 的 SimpleList.
+    Ints(JceType, Vec<i64>),
+    List(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Struct(Vec<(u8, Value)>),
+}
+
+fn write_value<E: Endianness>(writer: &mut JceWriter<Vec<u8>, E>, tag: u8, value: &Value) {
+    match value {
+        Value::Int(v) => writer.write_int(tag, *v),
+        Value::Float(bits) => writer.write_float(tag, f32::from_bits(*bits)),
+        Value::Double(bits) => writer.write_double(tag, f64::from_bits(*bits)),
+        Value::Str(s) => writer.write_string(tag, s),
+        Value::Bytes(b) => writer.write_bytes(tag, b),
+        Value::Ints(elem, values) => writer
+            .write_simple_list(tag, *elem, values)
+            .expect("generated values fit the element width"),
+        Value::List(items) => {
+            writer.write_list_header(tag, items.len());
+            for item in items {
+                write_value(writer, 0, item);
+            }
+        }
+        Value::Map(pairs) => {
+            writer.write_map_header(tag, pairs.len());
+            for (k, v) in pairs {
+                write_value(writer, 0, k);
+                write_value(writer, 1, v);
+            }
+        }
+        Value::Struct(fields) => {
+            writer.write_tag(tag, JceType::StructBegin);
+            for (t, v) in fields {
+                write_value(writer, *t, v);
+            }
+            writer.write_tag(0, JceType::StructEnd);
+        }
+    }
+}
+
+fn read_value<E: Endianness>(reader: &mut JceReader<'_, E>, type_id: JceType) -> Result<Value> {
+    Ok(match type_id {
+        JceType::ZeroTag | JceType::Int1 | JceType::Int2 | JceType::Int4 | JceType::Int8 => {
+            Value::Int(reader.read_int(type_id)?)
+        }
+        JceType::Float => Value::Float(reader.read_float()?.to_bits()),
+        JceType::Double => Value::Double(reader.read_double()?.to_bits()),
+        JceType::String1 | JceType::String4 => Value::Str(reader.read_string(type_id)?.into()),
+        JceType::SimpleList => {
+            let (elem, count) = reader.read_simple_list_header()?;
+            if elem == JceType::Int1 {
+                Value::Bytes(reader.read_bytes(count)?.to_vec())
+            } else {
+                Value::Ints(elem, reader.read_simple_list_ints(elem, count)?)
+            }
+        }
+        JceType::List => {
+            let size = reader.read_container_size()?;
+            let mut items = Vec::with_capacity(size);
+            for _ in 0..size {
+                let (_, t) = reader.read_head()?;
+                items.push(read_value(reader, t)?);
+            }
+            Value::List(items)
+        }
+        JceType::Map => {
+            let size = reader.read_container_size()?;
+            let mut pairs = Vec::with_capacity(size);
+            for _ in 0..size {
+                let (_, kt) = reader.read_head()?;
+                let k = read_value(reader, kt)?;
+                let (_, vt) = reader.read_head()?;
+                pairs.push((k, read_value(reader, vt)?));
+            }
+            Value::Map(pairs)
+        }
+        JceType::StructBegin => {
+            let mut fields = Vec::new();
+            loop {
+                let (t, ft) = reader.read_head()?;
+                if ft == JceType::StructEnd {
+                    break;
+                }
+                fields.push((t, read_value(reader, ft)?));
+            }
+            Value::Struct(fields)
+        }
+        JceType::StructEnd => unreachable!("StructEnd is consumed by the struct loop"),
+    })
+}
+
+fn encode<E: Endianness>(config: WriterConfig, fields: &[(u8, Value)]) -> Vec<u8> {
+    let mut writer = JceWriter::<Vec<u8>, E>::with_buffer(Vec::new());
+    writer.set_config(config);
+    for (tag, value) in fields {
+        write_value(&mut writer, *tag, value);
+    }
+    writer.finish()
+}
+
+fn decode<E: Endianness>(bytes: &[u8]) -> Result<Vec<(u8, Value)>> {
+    let mut reader = JceReader::<E>::new(bytes);
+    let mut fields = Vec::new();
+    while !reader.is_end() {
+        let (tag, t) = reader.read_head()?;
+        fields.push((tag, read_value(&mut reader, t)?));
+    }
+    Ok(fields)
+}
+
+/// 整数取值偏向各宽度的边界, 确保每种整数类型都能被选中.
+fn int_strategy() -> impl Strategy<Value = i64> {
+    prop_oneof![
+        Just(0i64),
+        any::<i8>().prop_map(i64::from),
+        any::<i16>().prop_map(i64::from),
+        any::<i32>().prop_map(i64::from),
+        any::<i64>(),
+    ]
+}
+
+fn simple_list_strategy() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        vec(any::<i16>(), 0..16)
+            .prop_map(|v| Value::Ints(JceType::Int2, v.into_iter().map(i64::from).collect())),
+        vec(any::<i32>(), 0..16)
+            .prop_map(|v| Value::Ints(JceType::Int4, v.into_iter().map(i64::from).collect())),
+        vec(any::<i64>(), 0..16).prop_map(|v| Value::Ints(JceType::Int8, v)),
+    ]
+}
+
+fn value_strategy() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        int_strategy().prop_map(Value::Int),
+        any::<u32>().prop_map(Value::Float),
+        any::<u64>().prop_map(Value::Double),
+        // 超过 255 字节时覆盖 String4
+        vec(any::<char>(), 0..300).prop_map(|c| Value::Str(c.into_iter().collect())),
+        vec(any::<u8>(), 0..300).prop_map(Value::Bytes),
+        simple_list_strategy(),
+    ];
+    leaf.prop_recursive(3, 64, 8, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..8).prop_map(Value::List),
+            vec((inner.clone(), inner.clone()), 0..8).prop_map(Value::Map),
+            vec((any::<u8>(), inner), 0..8).prop_map(Value::Struct),
+        ]
+    })
+}
+
+fn config_strategy() -> impl Strategy<Value = WriterConfig> {
+    (any::<u8>(), any::<bool>(), any::<bool>()).prop_map(
+        |(string1_max, force_string4, fixed_simple_list_len)| WriterConfig {
+            string1_max,
+            force_string4,
+            fixed_simple_list_len,
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn test_roundtrip_big_endian(
+        config in config_strategy(),
+        fields in vec((any::<u8>(), value_strategy()), 0..8),
+    ) {
+        let bytes = encode::<BigEndian>(config, &fields);
+        prop_assert_eq!(decode::<BigEndian>(&bytes), Ok(fields));
+    }
+
+    #[test]
+    fn test_roundtrip_little_endian(
+        config in config_strategy(),
+        fields in vec((any::<u8>(), value_strategy()), 0..8),
+    ) {
+        let bytes = encode::<LittleEndian>(config, &fields);
+        prop_assert_eq!(decode::<LittleEndian>(&bytes), Ok(fields));
+    }
+}
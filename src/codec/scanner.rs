@@ -1,15 +1,17 @@
-use crate::codec::consts::JceType;
+use crate::codec::consts::{JceType, MAX_DEPTH};
 use crate::codec::endian::Endianness;
 use crate::codec::error::{Error, Result};
 use byteorder::ReadBytesExt;
 use std::io::Cursor;
 use std::marker::PhantomData;
+use std::ops::Range;
 
 /// 一个轻量级的 JCE 结构扫描器，仅用于验证二进制数据的结构合法性，不分配任何内存。
 pub struct JceScanner<'a, E: Endianness> {
     cursor: Cursor<&'a [u8]>,
     depth: usize,
     max_depth: usize,
+    open_structs: bool,
     _phantom: PhantomData<E>,
 }
 
@@ -18,16 +20,60 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
         Self {
             cursor: Cursor::new(bytes),
             depth: 0,
-            max_depth: 100,
+            max_depth: MAX_DEPTH,
+            open_structs: false,
             _phantom: PhantomData,
         }
     }
 
+    /// 设置允许的最大结构体嵌套深度 (默认 100).
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// 设置是否允许嵌套结构体在数据末尾缺少 StructEnd (默认不允许).
+    ///
+    /// 解码器遇到数据结束时会直接结束当前结构体, 开启后 `validate_struct` 的判定与其一致.
+    pub fn allow_open_structs(mut self, allow: bool) -> Self {
+        self.open_structs = allow;
+        self
+    }
+
     #[inline]
     pub fn is_end(&self) -> bool {
         self.cursor.position() >= self.cursor.get_ref().len() as u64
     }
 
+    /// 当前偏移量.
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    /// 移动到指定偏移量.
+    ///
+    /// 偏移量超出数据长度时返回错误, 扫描器位置保持不变.
+    pub fn set_position(&mut self, pos: u64) -> Result<()> {
+        if pos > self.cursor.get_ref().len() as u64 {
+            return Err(Error::new(pos as usize, "Position out of range"));
+        }
+        self.cursor.set_position(pos);
+        Ok(())
+    }
+
+    /// 跳过下一个完整字段, 返回其 Tag、类型与值 (不含头部) 所在的字节范围.
+    ///
+    /// 头部长度包含扩展 Tag 的第二个字节; 嵌套的容器与结构体会被整体跳过,
+    /// 且嵌套结构体必须以 StructEnd 结束.
+    pub fn next_field(&mut self) -> Result<(u8, JceType, Range<usize>)> {
+        let (tag, jce_type) = self.read_head()?;
+        let start = self.cursor.position() as usize;
+        // 视为位于根结构体内部, 使截断的嵌套结构体报错
+        self.depth = self.depth.max(1);
+        self.skip_field(jce_type)?;
+        Ok((tag, jce_type, start..self.cursor.position() as usize))
+    }
+
     /// 验证整个 Struct 结构 (零分配).
     ///
     /// 递归遍历 JCE 结构，确保：
@@ -57,7 +103,8 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
 
         // If we reached end without StructEnd, it's only okay if we are at root depth 1
         // (for raw packets that are just a sequence of fields)
-        if self.depth == 1 {
+        if self.depth == 1 || self.open_structs {
+            self.depth -= 1;
             Ok(())
         } else {
             Err(Error::BufferOverflow {
@@ -75,9 +122,10 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
         let type_id = b & 0x0F;
         let mut tag = (b & 0xF0) >> 4;
         if tag == 15 {
-            tag = self.cursor.read_u8().map_err(|_| Error::BufferOverflow {
-                offset: self.cursor.position() as usize,
-            })?;
+            tag = self
+                .cursor
+                .read_u8()
+                .map_err(|_| Error::new(pos as usize, "Truncated extended tag header"))?;
         }
         let jce_type = JceType::try_from(type_id).map_err(|id| Error::InvalidType {
             offset: pos as usize,
@@ -106,19 +154,32 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
                     .read_u32::<E>()
                     .map_err(|_| Error::BufferOverflow {
                         offset: self.cursor.position() as usize,
-                    })?;
+                    })? as usize;
+                let start = self.cursor.position() as usize;
+                let remaining = self.cursor.get_ref().len().saturating_sub(start);
+                if len > remaining {
+                    return Err(Error::new(
+                        start,
+                        format!(
+                            "String4 declares {} bytes but only {} remain",
+                            len, remaining
+                        ),
+                    ));
+                }
                 self.skip(len as u64)
             }
             JceType::Map => {
-                let size = self.read_size()?;
-                for _ in 0..size * 2 {
-                    let (_, t) = self.read_head()?;
-                    self.skip_field(t)?;
+                let size = self.read_container_size()?;
+                for _ in 0..size {
+                    let (_, kt) = self.read_head()?;
+                    self.skip_field(kt)?;
+                    let (_, vt) = self.read_head()?;
+                    self.skip_field(vt)?;
                 }
                 Ok(())
             }
             JceType::List => {
-                let size = self.read_size()?;
+                let size = self.read_container_size()?;
                 for _ in 0..size {
                     let (_, t) = self.read_head()?;
                     self.skip_field(t)?;
@@ -126,17 +187,22 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
                 Ok(())
             }
             JceType::SimpleList => {
-                let t = self.cursor.read_u8().map_err(|_| Error::BufferOverflow {
-                    offset: self.cursor.position() as usize,
-                })?;
-                if t != 0 {
-                    return Err(Error::new(
-                        self.cursor.position() as usize,
-                        "SimpleList must contain Byte",
-                    ));
-                }
+                let pos = self.cursor.position() as usize;
+                let t = self
+                    .cursor
+                    .read_u8()
+                    .map_err(|_| Error::BufferOverflow { offset: pos })?;
+                let width = JceType::try_from(t)
+                    .ok()
+                    .and_then(JceType::simple_list_width)
+                    .ok_or_else(|| {
+                        Error::new(pos, format!("Unsupported SimpleList element type {}", t))
+                    })?;
                 let len = self.read_size()?;
-                self.skip(len as u64)
+                if len < 0 {
+                    return Err(Error::new(pos, format!("Invalid SimpleList size {}", len)));
+                }
+                self.skip(len as u64 * width as u64)
             }
             JceType::StructBegin => self.validate_struct(),
             JceType::StructEnd => Ok(()),
@@ -157,6 +223,29 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
         Ok(())
     }
 
+    /// 读取 List/Map 的元素个数, 规则与 `JceReader::read_container_size` 一致.
+    fn read_container_size(&mut self) -> Result<usize> {
+        let pos = self.cursor.position() as usize;
+        let size = self.read_size()?;
+        let size = usize::try_from(size)
+            .map_err(|_| Error::new(pos, format!("Invalid container size {}", size)))?;
+        let remaining = self
+            .cursor
+            .get_ref()
+            .len()
+            .saturating_sub(self.cursor.position() as usize);
+        if size > remaining {
+            return Err(Error::new(
+                pos,
+                format!(
+                    "Container size {} exceeds remaining {} bytes",
+                    size, remaining
+                ),
+            ));
+        }
+        Ok(size)
+    }
+
     fn read_size(&mut self) -> Result<i32> {
         let (_, t) = self.read_head()?;
         match t {
@@ -185,3 +274,42 @@ impl<'a, E: Endianness> JceScanner<'a, E> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::BigEndian;
+
+    #[test]
+    fn test_next_field_ranges() {
+        // tag 0 Int1, tag 20 (扩展头部) String1 "ab", tag 1 嵌套结构体 { tag 0 ZeroTag }
+        let data = b"\x00\x05\xf6\x14\x02ab\x1a\x0c\x0b";
+        let mut scanner = JceScanner::<BigEndian>::new(data);
+        assert_eq!(scanner.next_field(), Ok((0, JceType::Int1, 1..2)));
+        assert_eq!(scanner.next_field(), Ok((20, JceType::String1, 4..7)));
+        assert_eq!(scanner.next_field(), Ok((1, JceType::StructBegin, 8..10)));
+        assert!(scanner.is_end());
+    }
+
+    #[test]
+    fn test_next_field_truncated_struct() {
+        let mut scanner = JceScanner::<BigEndian>::new(b"\x1a\x0c");
+        assert!(scanner.next_field().is_err());
+    }
+
+    #[test]
+    fn test_validate_open_structs() {
+        // 嵌套结构体缺少 StructEnd 直接到达数据末尾
+        let data = b"\x00\x05\x1a\x0c";
+        assert!(
+            JceScanner::<BigEndian>::new(data)
+                .validate_struct()
+                .is_err()
+        );
+        let mut scanner = JceScanner::<BigEndian>::new(data).allow_open_structs(true);
+        assert_eq!(scanner.validate_struct(), Ok(()));
+        // 顶层 StructEnd 之后的数据不参与校验
+        let mut scanner = JceScanner::<BigEndian>::new(b"\x00\x05\x0b\xff");
+        assert_eq!(scanner.validate_struct(), Ok(()));
+    }
+}
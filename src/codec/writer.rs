@@ -1,12 +1,44 @@
 use crate::codec::consts::JceType;
 use crate::codec::endian::Endianness;
+use crate::codec::error::{Error, Result};
 use byteorder::BigEndian;
 use bytes::BufMut;
 use std::marker::PhantomData;
 
+/// `begin_bytes` 返回的待回填 SimpleList 的位置, 交给 `end_bytes` 使用.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BytesMark {
+    /// 长度字段头部的位置.
+    head: usize,
+    /// 内容的起始位置.
+    start: usize,
+}
+
+/// 写入器配置, 用于兼容对字符串类型有特殊要求的对端实现.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriterConfig {
+    /// 使用 String1 的最大字节长度, 超出时使用 String4. 规范值为 255.
+    pub string1_max: u8,
+    /// 是否所有字符串都使用 String4, 优先于 `string1_max`.
+    pub force_string4: bool,
+    /// SimpleList 的元素个数是否固定以 Int4 写入, 而非最小宽度.
+    pub fixed_simple_list_len: bool,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            string1_max: u8::MAX,
+            force_string4: false,
+            fixed_simple_list_len: false,
+        }
+    }
+}
+
 /// JCE 编码器，用于将数据序列化为二进制格式.
 pub struct JceWriter<B = Vec<u8>, E = BigEndian> {
     buffer: B,
+    config: WriterConfig,
     _phantom: PhantomData<E>,
 }
 
@@ -21,6 +53,7 @@ impl JceWriter<Vec<u8>, BigEndian> {
     pub fn new() -> Self {
         Self {
             buffer: Vec::with_capacity(128),
+            config: WriterConfig::default(),
             _phantom: PhantomData,
         }
     }
@@ -31,10 +64,23 @@ impl<B: BufMut, E: Endianness> JceWriter<B, E> {
     pub fn with_buffer(buffer: B) -> Self {
         Self {
             buffer,
+            config: WriterConfig::default(),
             _phantom: PhantomData,
         }
     }
 
+    /// 当前的写入器配置.
+    #[inline]
+    pub fn config(&self) -> WriterConfig {
+        self.config
+    }
+
+    /// 设置写入器配置, 影响之后写入的数据.
+    #[inline]
+    pub fn set_config(&mut self, config: WriterConfig) {
+        self.config = config;
+    }
+
     /// 获取编码后的字节流.
     #[inline]
     pub fn get_buffer(&self) -> &[u8]
@@ -92,6 +138,44 @@ impl<B: BufMut, E: Endianness> JceWriter<B, E> {
         }
     }
 
+    /// 以指定宽度写入整数 (不做最小宽度压缩).
+    ///
+    /// `int_type` 应为 Int1/Int2/Int4/Int8, 调用方需保证 `value` 在该宽度范围内,
+    /// 超出范围时按截断写入; 其他类型按 Int8 写入.
+    #[inline]
+    pub fn write_int_fixed(&mut self, tag: u8, value: i64, int_type: JceType) {
+        match int_type {
+            JceType::Int1 => {
+                self.write_tag(tag, JceType::Int1);
+                self.buffer.put_u8(value as u8);
+            }
+            JceType::Int2 => {
+                self.write_tag(tag, JceType::Int2);
+                if E::IS_LITTLE {
+                    self.buffer.put_i16_le(value as i16);
+                } else {
+                    self.buffer.put_i16(value as i16);
+                }
+            }
+            JceType::Int4 => {
+                self.write_tag(tag, JceType::Int4);
+                if E::IS_LITTLE {
+                    self.buffer.put_i32_le(value as i32);
+                } else {
+                    self.buffer.put_i32(value as i32);
+                }
+            }
+            _ => {
+                self.write_tag(tag, JceType::Int8);
+                if E::IS_LITTLE {
+                    self.buffer.put_i64_le(value);
+                } else {
+                    self.buffer.put_i64(value);
+                }
+            }
+        }
+    }
+
     /// 写入单精度浮点数.
     #[inline]
     pub fn write_float(&mut self, tag: u8, value: f32) {
@@ -117,9 +201,16 @@ impl<B: BufMut, E: Endianness> JceWriter<B, E> {
     /// 写入字符串.
     #[inline]
     pub fn write_string(&mut self, tag: u8, value: &str) {
-        let bytes = value.as_bytes();
+        self.write_string_bytes(tag, value.as_bytes());
+    }
+
+    /// 以字符串类型写入原始字节 (不校验 UTF-8).
+    ///
+    /// 长度不超过 `WriterConfig::string1_max` 时使用 String1, 否则使用 String4.
+    #[inline]
+    pub fn write_string_bytes(&mut self, tag: u8, bytes: &[u8]) {
         let len = bytes.len();
-        if len <= 255 {
+        if !self.config.force_string4 && len <= self.config.string1_max as usize {
             self.write_tag(tag, JceType::String1);
             self.buffer.put_u8(len as u8);
         } else {
@@ -133,6 +224,40 @@ impl<B: BufMut, E: Endianness> JceWriter<B, E> {
         self.buffer.put_slice(bytes);
     }
 
+    /// 直接追加已编码的字节 (不写入头部).
+    #[inline]
+    pub fn write_raw(&mut self, bytes: &[u8]) {
+        self.buffer.put_slice(bytes);
+    }
+
+    /// 写入字段头部后原样追加已编码的值, 用于逐字节透传捕获的字段.
+    ///
+    /// 不校验 `body` 是否为 `type_id` 的合法编码 (含字节序与整数宽度), 由调用方保证;
+    /// 不合法的内容会破坏之后所有字段的解析.
+    #[inline]
+    pub fn write_raw_field(&mut self, tag: u8, type_id: JceType, body: &[u8]) {
+        self.write_tag(tag, type_id);
+        self.buffer.put_slice(body);
+    }
+
+    /// 写入 Map 头部: 容器类型与 Tag 0 的键值对个数.
+    ///
+    /// 调用方随后需依次写入恰好 `size` 组键 (Tag 0) 和值 (Tag 1), 共 `2 * size` 个元素.
+    #[inline]
+    pub fn write_map_header(&mut self, tag: u8, size: usize) {
+        self.write_tag(tag, JceType::Map);
+        self.write_int(0, size as i64);
+    }
+
+    /// 写入 List 头部: 容器类型与 Tag 0 的元素个数.
+    ///
+    /// 调用方随后需写入恰好 `size` 个 Tag 0 的元素.
+    #[inline]
+    pub fn write_list_header(&mut self, tag: u8, size: usize) {
+        self.write_tag(tag, JceType::List);
+        self.write_int(0, size as i64);
+    }
+
     /// 写入字节数组 (SimpleList).
     #[inline]
     pub fn write_bytes(&mut self, tag: u8, value: &[u8]) {
@@ -140,9 +265,21 @@ impl<B: BufMut, E: Endianness> JceWriter<B, E> {
         // Element type byte: 0 for Byte
         self.buffer.put_u8(0);
         // 写入长度，使用 write_int (Tag 0)
-        self.write_int(0, value.len() as i64);
+        self.write_simple_list_len(value.len());
         self.buffer.put_slice(value);
     }
+
+    /// 写入 SimpleList 的元素个数 (Tag 0).
+    ///
+    /// 默认按最小宽度写入; `WriterConfig::fixed_simple_list_len` 下固定为 Int4.
+    #[inline]
+    fn write_simple_list_len(&mut self, len: usize) {
+        if self.config.fixed_simple_list_len {
+            self.write_int_fixed(0, len as i64, JceType::Int4);
+        } else {
+            self.write_int(0, len as i64);
+        }
+    }
 }
 
 impl<E: Endianness> JceWriter<Vec<u8>, E> {
@@ -150,6 +287,134 @@ impl<E: Endianness> JceWriter<Vec<u8>, E> {
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
+
+    /// 预留至少 `additional` 字节的额外容量.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional);
+    }
+
+    /// 写入定宽整数数组 (SimpleList).
+    ///
+    /// 依次写入元素类型字节, 元素个数 (`write_int(0, len)`) 与定宽的元素.
+    /// `element_type` 不是 Int1/Int2/Int4/Int8, 或某个值超出元素宽度时返回错误,
+    /// 此时不写入任何数据, 错误的偏移量为该字段本应开始的位置.
+    pub fn write_simple_list(
+        &mut self,
+        tag: u8,
+        element_type: JceType,
+        values: &[i64],
+    ) -> Result<()> {
+        let offset = self.buffer.len();
+        let Some(width) = element_type.simple_list_width() else {
+            return Err(Error::new(
+                offset,
+                format!("Unsupported SimpleList element type {:?}", element_type),
+            ));
+        };
+        if width < 8 {
+            let bound = 1i64 << (width * 8 - 1);
+            if let Some(v) = values.iter().find(|&&v| v < -bound || v >= bound) {
+                return Err(Error::new(
+                    offset,
+                    format!("Value {} out of range for {:?} SimpleList", v, element_type),
+                ));
+            }
+        }
+        self.write_tag(tag, JceType::SimpleList);
+        self.buffer.put_u8(element_type as u8);
+        self.write_simple_list_len(values.len());
+        for &v in values {
+            match element_type {
+                JceType::Int1 => self.buffer.put_i8(v as i8),
+                JceType::Int2 if E::IS_LITTLE => self.buffer.put_i16_le(v as i16),
+                JceType::Int2 => self.buffer.put_i16(v as i16),
+                JceType::Int4 if E::IS_LITTLE => self.buffer.put_i32_le(v as i32),
+                JceType::Int4 => self.buffer.put_i32(v as i32),
+                _ if E::IS_LITTLE => self.buffer.put_i64_le(v),
+                _ => self.buffer.put_i64(v),
+            }
+        }
+        Ok(())
+    }
+
+    /// 以 Int4 定宽写入 Tag 0 的长度占位, 返回 4 字节长度值的位置.
+    ///
+    /// 写完内容后以返回值调用 `patch_len` 回填实际长度.
+    pub fn write_bytes_len_placeholder(&mut self) -> usize {
+        self.write_tag(0, JceType::Int4);
+        let pos = self.buffer.len();
+        self.buffer.put_bytes(0, 4);
+        pos
+    }
+
+    /// 回填 `write_bytes_len_placeholder` 预留的长度.
+    ///
+    /// `pos` 不指向缓冲区内的 4 字节空间或 `value` 超出 Int4 范围时返回错误.
+    pub fn patch_len(&mut self, pos: usize, value: usize) -> Result<()> {
+        let value = i32::try_from(value)
+            .map_err(|_| Error::new(pos, format!("Length {value} exceeds Int4 range")))?;
+        let slot = pos
+            .checked_add(4)
+            .and_then(|end| self.buffer.get_mut(pos..end))
+            .ok_or(Error::BufferOverflow { offset: pos })?;
+        if E::IS_LITTLE {
+            slot.copy_from_slice(&value.to_le_bytes());
+        } else {
+            slot.copy_from_slice(&value.to_be_bytes());
+        }
+        Ok(())
+    }
+
+    /// 写入 SimpleList 头部并按预估的内容长度 `size_hint` 预留长度字段.
+    ///
+    /// 调用方随后直接向本 Writer 写入内容, 最后以返回值调用 `end_bytes` 回填长度,
+    /// 从而无需先将内容编码到临时缓冲区. 预留宽度与 `size_hint` 的最小宽度相同,
+    /// 实际长度落在同一宽度区间 (<128, <32768, <2^31) 时回填无需移动内容.
+    pub fn begin_bytes(&mut self, tag: u8, size_hint: usize) -> BytesMark {
+        self.write_tag(tag, JceType::SimpleList);
+        self.buffer.put_u8(0);
+        let head = self.buffer.len();
+        self.write_simple_list_len(size_hint);
+        BytesMark {
+            head,
+            start: self.buffer.len(),
+        }
+    }
+
+    /// 回填 `begin_bytes` 预留的长度.
+    ///
+    /// 长度按 `write_int(0, len)` 的最小宽度写入, 与 `write_bytes` 的输出完全一致.
+    /// 宽度与预留的不同时需要整体移动内容, 代价与内容长度成正比,
+    /// 因此 `size_hint` 应尽量落在实际长度的宽度区间内.
+    pub fn end_bytes(&mut self, mark: BytesMark) {
+        let BytesMark { head, start } = mark;
+        let len = self.buffer.len() - start;
+        let mut header = [0u8; 9];
+        let header_len = {
+            let mut slot = &mut header[..];
+            let mut header_writer = JceWriter::<_, E>::with_buffer(&mut slot);
+            header_writer.set_config(self.config);
+            header_writer.write_simple_list_len(len);
+            9 - slot.len()
+        };
+        let reserved = start - head;
+        if header_len > reserved {
+            let extra = header_len - reserved;
+            self.buffer
+                .splice(start..start, std::iter::repeat_n(0, extra));
+        } else if header_len < reserved {
+            self.buffer.copy_within(start.., head + header_len);
+            self.buffer.truncate(head + header_len + len);
+        }
+        self.buffer[head..head + header_len].copy_from_slice(&header[..header_len]);
+    }
+
+    /// 取出编码结果并重置 Writer.
+    ///
+    /// 通过 `std::mem::take` 移出内部缓冲区, Writer 保留一个空缓冲区以便继续复用.
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buffer)
+    }
 }
 
 #[cfg(test)]
@@ -177,6 +442,42 @@ mod tests {
         assert_eq!(writer.get_buffer(), b"\x01\x01\x00"); // Tag 0, Int2, Value 256 (0x0100)
     }
 
+    #[test]
+    fn test_write_int_fixed() {
+        let mut writer = JceWriter::new();
+        writer.write_int_fixed(0, 0, JceType::Int8);
+        assert_eq!(writer.get_buffer(), b"\x03\x00\x00\x00\x00\x00\x00\x00\x00");
+
+        let mut writer = JceWriter::new();
+        writer.write_int_fixed(1, 1, JceType::Int2);
+        assert_eq!(writer.get_buffer(), b"\x11\x00\x01");
+    }
+
+    #[test]
+    fn test_write_container_headers() {
+        let mut writer = JceWriter::new();
+        writer.write_map_header(0, 1);
+        writer.write_int(0, 1);
+        writer.write_string(1, "a");
+        writer.write_list_header(1, 2);
+        writer.write_int(0, 1);
+        writer.write_int(0, 2);
+        // Tag 0 Map 大小 1: {1: "a"}; Tag 1 List 大小 2: [1, 2]
+        assert_eq!(
+            writer.get_buffer(),
+            b"\x08\x00\x01\x00\x01\x16\x01a\x19\x00\x02\x00\x01\x00\x02"
+        );
+    }
+
+    #[test]
+    fn test_write_raw_field() {
+        let mut writer = JceWriter::new();
+        // Int2 宽度的 1 原样保留, 不会被压缩为 Int1
+        writer.write_raw_field(0, JceType::Int2, b"\x00\x01");
+        writer.write_raw_field(20, JceType::String1, b"\x01a");
+        assert_eq!(writer.get_buffer(), b"\x01\x00\x01\xf6\x14\x01a");
+    }
+
     #[test]
     fn test_write_string() {
         let mut writer = JceWriter::new();
@@ -191,6 +492,234 @@ mod tests {
         assert_eq!(writer.get_buffer(), b"\x0d\x00\x00\x03abc");
     }
 
+    #[test]
+    fn test_begin_end_bytes_matches_write_bytes() {
+        for size in [0usize, 3, 127, 128, 300, 40_000] {
+            let payload: Vec<u8> = (0..size).map(|i| i as u8).collect();
+
+            let mut expected = JceWriter::new();
+            expected.write_int(0, 1);
+            expected.write_bytes(1, &payload);
+            expected.write_int(2, 2);
+
+            // 预估长度偏小, 准确与偏大时输出都应一致
+            for size_hint in [0, size, 1 << 20] {
+                let mut writer = JceWriter::new();
+                writer.write_int(0, 1);
+                let mark = writer.begin_bytes(1, size_hint);
+                writer.write_raw(&payload);
+                writer.end_bytes(mark);
+                writer.write_int(2, 2);
+
+                assert_eq!(
+                    writer.get_buffer(),
+                    expected.get_buffer(),
+                    "size {size}, hint {size_hint}"
+                );
+            }
+        }
+    }
+
+    /// 统计当前线程的堆分配, 用于比较编码过程的峰值内存.
+    mod counting_alloc {
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::cell::Cell;
+
+        thread_local! {
+            static TRACKING: Cell<bool> = const { Cell::new(false) };
+            static LIVE: Cell<usize> = const { Cell::new(0) };
+            static PEAK: Cell<usize> = const { Cell::new(0) };
+        }
+
+        struct CountingAlloc;
+
+        #[global_allocator]
+        static ALLOC: CountingAlloc = CountingAlloc;
+
+        fn record(grow: usize, shrink: usize) {
+            let _ = TRACKING.try_with(|tracking| {
+                if tracking.get() {
+                    let live = LIVE.get() + grow - shrink;
+                    LIVE.set(live);
+                    PEAK.set(PEAK.get().max(live));
+                }
+            });
+        }
+
+        unsafe impl GlobalAlloc for CountingAlloc {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                record(layout.size(), 0);
+                unsafe { System.alloc(layout) }
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                unsafe { System.dealloc(ptr, layout) };
+                record(0, layout.size());
+            }
+        }
+
+        /// 执行 `f` 并返回其间当前线程的峰值堆内存 (字节).
+        pub(super) fn peak_alloc(f: impl FnOnce()) -> usize {
+            LIVE.set(0);
+            PEAK.set(0);
+            TRACKING.set(true);
+            f();
+            TRACKING.set(false);
+            PEAK.get()
+        }
+    }
+
+    #[test]
+    fn test_begin_end_bytes_peak_alloc() {
+        use counting_alloc::peak_alloc;
+
+        let payload = vec![7u8; 1 << 16];
+        let two_pass = || {
+            let mut inner = JceWriter::new();
+            inner.write_bytes(0, &payload);
+            let mut middle = JceWriter::new();
+            middle.write_bytes(0, inner.get_buffer());
+            let mut outer = JceWriter::new();
+            outer.write_bytes(0, middle.get_buffer());
+            outer.finish()
+        };
+        let expected = two_pass();
+        let total = expected.len();
+
+        // 三层嵌套的二进制块直接写入同一个预分配的缓冲区, 峰值内存即为该缓冲区
+        let in_place = peak_alloc(|| {
+            let mut writer = JceWriter::<_, BigEndian>::with_buffer(Vec::with_capacity(total));
+            let outer = writer.begin_bytes(0, total);
+            let middle = writer.begin_bytes(0, total);
+            writer.write_bytes(0, &payload);
+            writer.end_bytes(middle);
+            writer.end_bytes(outer);
+            assert_eq!(writer.get_buffer(), &expected[..]);
+        });
+        assert_eq!(in_place, total);
+
+        // 逐层编码到临时缓冲区时每层都持有一份内容的拷贝
+        let copied = peak_alloc(|| {
+            two_pass();
+        });
+        assert!(
+            copied >= 2 * total,
+            "two-pass peak {copied}, in-place {in_place}"
+        );
+    }
+
+    #[test]
+    fn test_fixed_simple_list_len() {
+        let config = WriterConfig {
+            fixed_simple_list_len: true,
+            ..WriterConfig::default()
+        };
+        let mut writer = JceWriter::new();
+        writer.set_config(config);
+        writer.write_bytes(0, b"abc");
+        writer.write_simple_list(1, JceType::Int2, &[1]).unwrap();
+        assert_eq!(
+            writer.get_buffer(),
+            b"\x0d\x00\x02\x00\x00\x00\x03abc\x1d\x01\x02\x00\x00\x00\x01\x00\x01"
+        );
+
+        let mut streamed = JceWriter::new();
+        streamed.set_config(config);
+        let mark = streamed.begin_bytes(0, 0);
+        streamed.write_raw(b"abc");
+        streamed.end_bytes(mark);
+        assert_eq!(streamed.get_buffer(), b"\x0d\x00\x02\x00\x00\x00\x03abc");
+    }
+
+    #[test]
+    fn test_patch_len_matches_two_pass() {
+        let payload = b"hello world";
+
+        let mut expected = JceWriter::new();
+        expected.write_tag(1, JceType::SimpleList);
+        expected.write_raw(&[0]);
+        expected.write_int_fixed(0, payload.len() as i64, JceType::Int4);
+        expected.write_raw(payload);
+
+        let mut writer = JceWriter::new();
+        writer.write_tag(1, JceType::SimpleList);
+        writer.write_raw(&[0]);
+        let pos = writer.write_bytes_len_placeholder();
+        writer.write_raw(payload);
+        writer.patch_len(pos, payload.len()).unwrap();
+
+        assert_eq!(writer.get_buffer(), expected.get_buffer());
+    }
+
+    #[test]
+    fn test_patch_len_little_endian() {
+        let mut writer = JceWriter::<Vec<u8>, byteorder::LittleEndian>::with_buffer(Vec::new());
+        let pos = writer.write_bytes_len_placeholder();
+        writer.patch_len(pos, 0x0102).unwrap();
+        assert_eq!(writer.get_buffer(), b"\x02\x02\x01\x00\x00");
+    }
+
+    #[test]
+    fn test_patch_len_out_of_range() {
+        let mut writer = JceWriter::new();
+        let pos = writer.write_bytes_len_placeholder();
+        assert!(writer.patch_len(pos + 1, 1).is_err());
+        assert!(writer.patch_len(usize::MAX, 1).is_err());
+        assert!(writer.patch_len(pos, i32::MAX as usize + 1).is_err());
+        assert_eq!(writer.get_buffer(), b"\x02\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn test_finish_resets_writer() {
+        let mut writer = JceWriter::new();
+        writer.write_int(0, 1);
+        assert_eq!(writer.finish(), b"\x00\x01");
+        assert!(writer.get_buffer().is_empty());
+
+        writer.write_int(1, 2);
+        assert_eq!(writer.finish(), b"\x10\x02");
+    }
+
+    #[test]
+    fn test_write_simple_list() {
+        let mut writer = JceWriter::new();
+        writer
+            .write_simple_list(1, JceType::Int2, &[1, -1])
+            .unwrap();
+        // Tag 1 SimpleList, 元素类型 Int2, 个数 2, 元素 0x0001 0xffff
+        assert_eq!(writer.get_buffer(), b"\x1d\x01\x00\x02\x00\x01\xff\xff");
+
+        // 超出元素宽度或元素类型非法时报错, 且不写入任何数据
+        assert!(
+            writer
+                .write_simple_list(2, JceType::Int2, &[32768])
+                .is_err()
+        );
+        assert!(writer.write_simple_list(2, JceType::Int1, &[-129]).is_err());
+        assert!(writer.write_simple_list(2, JceType::Double, &[1]).is_err());
+        assert_eq!(writer.get_buffer().len(), 8);
+    }
+
+    #[test]
+    fn test_string_length_config() {
+        let mut writer = JceWriter::new();
+        writer.set_config(WriterConfig {
+            string1_max: 3,
+            ..WriterConfig::default()
+        });
+        writer.write_string(0, "abc");
+        writer.write_string(0, "abcd");
+        assert_eq!(writer.get_buffer(), b"\x06\x03abc\x07\x00\x00\x00\x04abcd");
+
+        let mut writer = JceWriter::new();
+        writer.set_config(WriterConfig {
+            force_string4: true,
+            ..WriterConfig::default()
+        });
+        writer.write_string(0, "");
+        assert_eq!(writer.get_buffer(), b"\x07\x00\x00\x00\x00");
+    }
+
     #[test]
     fn test_high_tag() {
         let mut writer = JceWriter::new();
@@ -7,9 +7,53 @@ use pyo3::prelude::*;
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(bindings::serde::dumps, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::serde::loads, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::dumps_into, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::dumps_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::dumps_batch_framed, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::serde::dumps_generic, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::loads_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::loads_batch_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::serde::loads_generic, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::loads_generic_partial, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::loads_generic_value, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::stream::split_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::loads_projection, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::get_field, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::index_struct, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::canonical_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::canonicalize_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::jce_equal, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::serde::debug_dump, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::json::to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::json::from_json, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::schema::schema_compatible, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::schema::is_type_compatible, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::schema::normalize_struct, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::schema::denormalize_struct, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::schema_io::serialize_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::schema_io::deserialize_schema,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        bindings::serde::set_schema_method_name,
+        m
+    )?)?;
+    // 类型码常量由 `JceType` 生成, Python 端据此构造 `tarsio.JceType`
+    let jce_types: Vec<(&str, u8)> = codec::consts::JceType::ALL
+        .iter()
+        .map(|t| (t.name(), *t as u8))
+        .collect();
+    m.add("JCE_TYPES", jce_types)?;
+    m.add_class::<bindings::reader::Reader>()?;
+    m.add_class::<bindings::writer::Writer>()?;
+    m.add_class::<bindings::fixed::Fixed8>()?;
+    m.add_class::<bindings::fixed::Fixed16>()?;
+    m.add_class::<bindings::fixed::Fixed32>()?;
+    m.add_class::<bindings::fixed::Fixed64>()?;
+    m.add_class::<bindings::str_view::JceStr>()?;
     m.add_class::<bindings::stream::LengthPrefixedReader>()?;
+    m.add_class::<bindings::stream::PyJceFramer>()?;
     m.add_class::<bindings::stream::LengthPrefixedWriter>()?;
     Ok(())
 }